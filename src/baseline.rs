@@ -0,0 +1,211 @@
+//! Baseline:
+//! Saves the repetition samples of a run to a baseline file and compares a later run
+//! against it with Welch's t-test, which allows the two runs to have different variances.
+//! A day counts as a regression when the slowdown is both significant and larger than the
+//! threshold.
+
+use crate::prelude::*;
+use crate::registry;
+use crate::statistics::mean_variance;
+
+// Constants
+const DAYS: usize = registry::NUMBER_DAYS;
+const SIGNIFICANCE: f64 = 0.05;
+const HEADER: &str =
+    "# advent2020 baseline: day, then the total time of each repetition in nanoseconds";
+
+// -----------------------------------------------------------------------------
+// Baseline struct
+// -----------------------------------------------------------------------------
+/// Saved repetition samples for each day, in nanoseconds
+#[derive(Debug)]
+pub struct Baseline {
+    samples: Vec<Vec<f64>>,
+}
+
+impl Baseline {
+    /// Samples for a day, if the baseline has any
+    pub fn samples(&self, day: usize) -> Option<&[f64]> {
+        self.samples
+            .get(day - 1)
+            .filter(|samples| !samples.is_empty())
+            .map(|samples| &samples[..])
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Load and save
+// -----------------------------------------------------------------------------
+/// Load a baseline file
+pub fn load(path: &std::path::Path) -> Result<Baseline, Error> {
+    let buffer = std::fs::read_to_string(path)?;
+    let mut samples = vec![vec![]; DAYS];
+    for line in buffer.lines() {
+        let mut tokens = line.split_whitespace();
+        let token = match tokens.next() {
+            Some(token) if !token.starts_with('#') => token,
+            _ => continue,
+        };
+        let day: usize = error::number(&buffer, token)?;
+        if !(1..=DAYS).contains(&day) {
+            return Err(Error::parse(&buffer, token, format!("invalid day {}", day)));
+        }
+        samples[day - 1] = tokens
+            .map(|token| error::number::<u64>(&buffer, token).map(|sample| sample as f64))
+            .collect::<Result<_, _>>()?;
+        if samples[day - 1].is_empty() {
+            return Err(Error::parse(&buffer, line, "expected at least one sample"));
+        }
+    }
+    Ok(Baseline { samples })
+}
+
+/// Save the repetition samples for each day, in nanoseconds, as a baseline file
+pub fn save(path: &std::path::Path, days: &[(usize, Vec<f64>)]) -> std::io::Result<()> {
+    let mut buffer = format!("{}\n", HEADER);
+    for (day, samples) in days {
+        buffer.push_str(&format!("{:02}", day));
+        for sample in samples {
+            buffer.push_str(&format!(" {:.0}", sample));
+        }
+        buffer.push('\n');
+    }
+    std::fs::write(path, buffer)
+}
+
+// -----------------------------------------------------------------------------
+// Comparison struct
+// -----------------------------------------------------------------------------
+/// Result of comparing the timing of a day against the baseline
+#[derive(Debug)]
+pub struct Comparison {
+    /// Mean time in the baseline
+    pub baseline: std::time::Duration,
+    /// Mean time in this run
+    pub current: std::time::Duration,
+    /// Baseline time over current time, above 1 when faster
+    pub speedup: f64,
+    /// Two sided p-value from Welch's t-test
+    pub p_value: f64,
+    /// Significantly slower, by more than the threshold
+    pub regression: bool,
+}
+
+impl Comparison {
+    /// Significant difference in either direction
+    pub fn significant(&self) -> bool {
+        self.p_value < SIGNIFICANCE
+    }
+}
+
+/// Compare current samples against the baseline; threshold is a fraction, 0.05 for 5%
+pub fn compare(baseline: &[f64], current: &[f64], threshold: f64) -> Comparison {
+    let (baseline_mean, baseline_variance) = mean_variance(baseline);
+    let (current_mean, current_variance) = mean_variance(current);
+    let p_value = welch_t_test(
+        (baseline_mean, baseline_variance, baseline.len()),
+        (current_mean, current_variance, current.len()),
+    );
+    let slowdown = current_mean / baseline_mean - 1.0;
+    Comparison {
+        baseline: std::time::Duration::from_nanos(baseline_mean as u64),
+        current: std::time::Duration::from_nanos(current_mean as u64),
+        speedup: baseline_mean / current_mean,
+        p_value,
+        regression: p_value < SIGNIFICANCE && slowdown > threshold,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Statistics
+// -----------------------------------------------------------------------------
+// Two sided p-value for the difference in means, from (mean, variance, count)
+fn welch_t_test(a: (f64, f64, usize), b: (f64, f64, usize)) -> f64 {
+    // Need at least two samples on each side to estimate the variance
+    if a.2 < 2 || b.2 < 2 {
+        return 1.0;
+    }
+    let var_a = a.1 / a.2 as f64;
+    let var_b = b.1 / b.2 as f64;
+    let standard_error = (var_a + var_b).sqrt();
+    if standard_error == 0.0 {
+        return if a.0 == b.0 { 1.0 } else { 0.0 };
+    }
+    let t = (b.0 - a.0) / standard_error;
+    let df = (var_a + var_b).powi(2)
+        / (var_a.powi(2) / (a.2 - 1) as f64 + var_b.powi(2) / (b.2 - 1) as f64);
+    // Two sided tail of Student's t distribution
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+// Log of the gamma function, Lanczos approximation
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |acc, (i, c)| {
+            acc + c / (x + i as f64 + 1.0)
+        });
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+// Regularized incomplete beta function I_x(a, b)
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // Continued fraction converges quickly on this side, use symmetry for the other
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+// Continued fraction for the incomplete beta function, modified Lentz's method
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    const EPSILON: f64 = 1e-14;
+    let clamp = |value: f64| if value.abs() < TINY { TINY } else { value };
+    let mut c = 1.0;
+    let mut d = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+    let mut fraction = d;
+    for m in 1..200 {
+        let m = m as f64;
+        // Even step
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / clamp(1.0 + numerator * d);
+        c = clamp(1.0 + numerator / c);
+        fraction *= d * c;
+        // Odd step
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / clamp(1.0 + numerator * d);
+        c = clamp(1.0 + numerator / c);
+        let delta = d * c;
+        fraction *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    fraction
+}
+
+// -----------------------------------------------------------------------------
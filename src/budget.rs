@@ -0,0 +1,74 @@
+//! Budgets:
+//! Time budgets for the phases of each day in an optimized build. The tests and the
+//! `--check-budgets` driver mode report every phase that runs over its budget.
+
+use crate::prelude::*;
+use std::time::Duration;
+
+// -----------------------------------------------------------------------------
+// Budget struct
+// -----------------------------------------------------------------------------
+/// Expected upper bound on the time of each phase of a day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Budget {
+    /// Reading and parsing the input
+    pub setup: Duration,
+    /// Solving part 1
+    pub part_1: Duration,
+    /// Solving part 2
+    pub part_2: Duration,
+}
+
+impl Budget {
+    /// Budget for each phase, in milliseconds
+    pub const fn millis(setup: u64, part_1: u64, part_2: u64) -> Self {
+        Self {
+            setup: Duration::from_millis(setup),
+            part_1: Duration::from_millis(part_1),
+            part_2: Duration::from_millis(part_2),
+        }
+    }
+
+    /// Phases of the timing that ran over budget
+    pub fn overruns(&self, times: &Timing) -> Vec<Overrun> {
+        [
+            ("setup", times.setup, self.setup),
+            ("part 1", times.part_1, self.part_1),
+            ("part 2", times.part_2, self.part_2),
+        ]
+        .iter()
+        .filter(|(_, time, budget)| time > budget)
+        .map(|&(phase, time, budget)| Overrun {
+            phase,
+            time,
+            budget,
+        })
+        .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Overrun struct
+// -----------------------------------------------------------------------------
+/// A phase that took longer than its budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overrun {
+    /// Name of the phase
+    pub phase: &'static str,
+    /// Time the phase took
+    pub time: Duration,
+    /// Budget for the phase
+    pub budget: Duration,
+}
+
+impl std::fmt::Display for Overrun {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} took {:?}, over the budget of {:?}",
+            self.phase, self.time, self.budget
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
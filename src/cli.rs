@@ -0,0 +1,429 @@
+//! Command Line:
+//! Parses the driver options and subcommands by hand, with a usage message for errors
+//! and `--help`.
+
+use advent2020::{memory, registry, Input};
+
+// Constants
+const DAYS: usize = registry::NUMBER_DAYS;
+const DEFAULT_REPETITIONS: u32 = 5;
+const DEFAULT_WARMUP: u32 = 1;
+const DEFAULT_MAX_REPETITIONS: u32 = 100;
+const DEFAULT_THRESHOLD: f64 = 5.0;
+const DEFAULT_CASES: usize = 1000;
+const DEFAULT_TIMEOUT: u64 = 1000;
+const DEFAULT_INTERVAL: u64 = 500;
+
+// -----------------------------------------------------------------------------
+// Format enum
+// -----------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "invalid format '{}', expected text, json, csv, markdown or html",
+                s
+            )),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Command enum
+// -----------------------------------------------------------------------------
+#[derive(Debug)]
+pub(crate) enum Command {
+    Run(Options),
+    Generate {
+        day: usize,
+        seed: u64,
+        size: Option<usize>,
+    },
+    Compare {
+        days: Vec<usize>,
+        seeds: u64,
+        size: Option<usize>,
+    },
+    Fuzz {
+        days: Vec<usize>,
+        seeds: u64,
+        cases: usize,
+        timeout: std::time::Duration,
+        solve: bool,
+    },
+    Watch {
+        options: Options,
+        interval: std::time::Duration,
+    },
+}
+
+// -----------------------------------------------------------------------------
+// Options struct
+// -----------------------------------------------------------------------------
+#[derive(Debug)]
+pub(crate) struct Options {
+    pub(crate) days: Vec<usize>,
+    pub(crate) inputs: Vec<Input>,
+    pub(crate) repetitions: u32,
+    pub(crate) warmup: u32,
+    pub(crate) target_error: Option<f64>,
+    pub(crate) max_repetitions: u32,
+    pub(crate) format: Format,
+    pub(crate) baseline: Option<std::path::PathBuf>,
+    pub(crate) save_baseline: Option<std::path::PathBuf>,
+    pub(crate) threshold: f64,
+    pub(crate) check_budgets: bool,
+    pub(crate) parallel: bool,
+    pub(crate) no_color: bool,
+    pub(crate) ascii: bool,
+    pub(crate) width: Option<usize>,
+}
+
+impl Options {
+    // Input for a selected day
+    pub(crate) fn input(&self, day: usize) -> &Input {
+        &self.inputs[day - 1]
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Usage
+// -----------------------------------------------------------------------------
+pub(crate) const USAGE: &str = "\
+Usage: advent2020 [OPTIONS]
+       advent2020 generate --day N [--seed S] [--size K]
+       advent2020 compare [--day N | --days A-B] [--seeds K] [--size K]
+       advent2020 fuzz [--day N | --days A-B] [--seeds K] [--cases K]
+                       [--timeout MS] [--solve]
+       advent2020 watch --day N [OPTIONS] [--interval MS]
+
+Options:
+  -d, --day N              Run a single day
+      --days A-B           Run an inclusive range of days
+  -i, --input [DAY=]PATH   Read input from PATH instead of data/dayNN.txt
+                           Use '-' to read from stdin
+                           DAY may be omitted when a single day is selected
+  -r, --repetitions N      Minimum number of timing repetitions (default 5)
+  -w, --warmup N           Untimed runs before the repetitions (default 1)
+  -e, --target-error PERCENT
+                           Repeat until the standard error of the mean is
+                           below PERCENT of the mean
+      --max-repetitions N  Limit for --target-error (default 100)
+  -f, --format FORMAT      Output format: text, json, csv, markdown or html
+                           (default text)
+  -b, --baseline PATH      Compare timings against a saved baseline
+                           Exits with status 3 on a significant regression
+      --save-baseline PATH Save the timings of this run as a baseline
+  -t, --threshold PERCENT  Slowdown that counts as a regression (default 5)
+      --check-budgets      Report phases that ran over the time budget of
+                           their day; exits with status 4 on any overrun
+  -p, --parallel           Run the days side by side on a thread pool; faster,
+                           but the timings are skewed by the contention. Not
+                           available with the memory feature
+      --no-color           Plain text without colors; also set by NO_COLOR or
+                           when stdout is not a terminal
+      --ascii              Only print ASCII, without emoji
+      --width N            Width of the timing bars (default terminal width)
+  -h, --help               Print this message
+
+Generate:
+  Print a random input for day N, the same for the same seed (default 0).
+  The size scales the input, usually the number of lines (default the size
+  of the puzzle input).
+
+Compare:
+  Check the answers of each day against the reference solvers on K generated
+  inputs (default 10), with seeds 0 to K-1; exits with status 5 on the first
+  input where they differ. Requires the reference feature.
+
+Fuzz:
+  Parse K mutated copies (default 1000) of the generated inputs for seeds 0
+  to K-1 (default 1) of each day. With --solve, also run both parts and the
+  combined solver on each copy that parses. Exits with status 6 on the first
+  case that panics, runs past the timeout (default 1000 ms) or gets different
+  answers from the combined solver, and writes that case to fuzz-dayNN.txt.
+
+Watch:
+  Run day N, then poll its input file every MS milliseconds (default 500)
+  and run the day again when it changes. Each run prints the change in
+  timing since the run before it. Takes the same options as a normal run,
+  but only prints text.";
+
+// -----------------------------------------------------------------------------
+// Parse helpers
+// -----------------------------------------------------------------------------
+fn parse_day(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(day) if (1..=DAYS).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day '{}', expected 1-{}", s, DAYS)),
+    }
+}
+
+fn parse_range(s: &str) -> Result<Vec<usize>, String> {
+    let mut bounds = s.splitn(2, '-');
+    let lower = parse_day(bounds.next().unwrap_or(""))?;
+    let upper = match bounds.next() {
+        Some(upper) => parse_day(upper)?,
+        None => lower,
+    };
+    if lower > upper {
+        return Err(format!("invalid range '{}'", s));
+    }
+    Ok((lower..=upper).collect())
+}
+
+fn parse_count(s: &str, name: &str, minimum: u32) -> Result<u32, String> {
+    match s.parse() {
+        Ok(count) if count >= minimum => Ok(count),
+        _ => Err(format!("invalid {} '{}'", name, s)),
+    }
+}
+
+fn parse_input(s: &str) -> Result<Input, String> {
+    if s == "-" {
+        let mut buffer = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut buffer)
+            .map_err(|error| format!("failed to read stdin: {}", error))?;
+        Ok(Input::Buffer(buffer))
+    } else {
+        Ok(Input::Path(s.into()))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Parse arguments
+// -----------------------------------------------------------------------------
+pub(crate) fn parse_command<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    match args.peek().map(String::as_str) {
+        Some("generate") => parse_generate(args.skip(1)),
+        Some("compare") => parse_compare(args.skip(1)),
+        Some("fuzz") => parse_fuzz(args.skip(1)),
+        Some("watch") => parse_watch(args.skip(1)),
+        _ => parse_args(args).map(Command::Run),
+    }
+}
+
+fn parse_generate<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{}'", name))
+        };
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value(&arg)?)?),
+            "-s" | "--seed" => {
+                let s = value(&arg)?;
+                seed = s.parse().map_err(|_| format!("invalid seed '{}'", s))?;
+            }
+            "--size" => size = Some(parse_count(&value(&arg)?, "size", 1)? as usize),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("unknown argument '{}'\n\n{}", arg, USAGE)),
+        }
+    }
+    match day {
+        Some(day) => Ok(Command::Generate { day, seed, size }),
+        None => Err("generate requires --day".to_string()),
+    }
+}
+
+fn parse_compare<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut days = (1..=DAYS).collect();
+    let mut seeds = 10;
+    let mut size = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{}'", name))
+        };
+        match arg.as_str() {
+            "-d" | "--day" => days = vec![parse_day(&value(&arg)?)?],
+            "--days" => days = parse_range(&value(&arg)?)?,
+            "--seeds" => seeds = parse_count(&value(&arg)?, "seeds", 1)? as u64,
+            "--size" => size = Some(parse_count(&value(&arg)?, "size", 1)? as usize),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("unknown argument '{}'\n\n{}", arg, USAGE)),
+        }
+    }
+    Ok(Command::Compare { days, seeds, size })
+}
+
+fn parse_fuzz<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut days = (1..=DAYS).collect();
+    let mut seeds = 1;
+    let mut cases = DEFAULT_CASES;
+    let mut timeout = DEFAULT_TIMEOUT;
+    let mut solve = false;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{}'", name))
+        };
+        match arg.as_str() {
+            "-d" | "--day" => days = vec![parse_day(&value(&arg)?)?],
+            "--days" => days = parse_range(&value(&arg)?)?,
+            "--seeds" => seeds = parse_count(&value(&arg)?, "seeds", 1)? as u64,
+            "--cases" => cases = parse_count(&value(&arg)?, "cases", 1)? as usize,
+            "--timeout" => timeout = parse_count(&value(&arg)?, "timeout", 1)? as u64,
+            "--solve" => solve = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("unknown argument '{}'\n\n{}", arg, USAGE)),
+        }
+    }
+    Ok(Command::Fuzz {
+        days,
+        seeds,
+        cases,
+        timeout: std::time::Duration::from_millis(timeout),
+        solve,
+    })
+}
+
+fn parse_watch<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    // Everything but the interval is a normal run option
+    let mut interval = DEFAULT_INTERVAL;
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        if arg == "--interval" {
+            let s = args.next().ok_or("missing value for '--interval'")?;
+            interval = parse_count(&s, "interval", 1)? as u64;
+        } else {
+            rest.push(arg);
+        }
+    }
+    let options = parse_args(rest.into_iter())?;
+    if options.days.len() != 1 {
+        return Err("watch requires a single --day".to_string());
+    }
+    if options.format != Format::Text {
+        return Err("watch only prints text, not --format".to_string());
+    }
+    Ok(Command::Watch {
+        options,
+        interval: std::time::Duration::from_millis(interval),
+    })
+}
+
+pub(crate) fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut days: Option<Vec<usize>> = None;
+    let mut overrides: Vec<(Option<usize>, String)> = vec![];
+    let mut repetitions = DEFAULT_REPETITIONS;
+    let mut warmup = DEFAULT_WARMUP;
+    let mut target_error = None;
+    let mut max_repetitions = DEFAULT_MAX_REPETITIONS;
+    let mut format = Format::Text;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut check_budgets = false;
+    let mut parallel = false;
+    let mut no_color = false;
+    let mut ascii = false;
+    let mut width = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{}'", name))
+        };
+        match arg.as_str() {
+            "-d" | "--day" => days = Some(vec![parse_day(&value(&arg)?)?]),
+            "--days" => days = Some(parse_range(&value(&arg)?)?),
+            "-i" | "--input" => {
+                // A path can contain '=' too, so only a day number in front of it is a DAY=
+                let source = value(&arg)?;
+                match source
+                    .split_once('=')
+                    .and_then(|(day, path)| Some((parse_day(day).ok()?, path)))
+                {
+                    Some((day, path)) => overrides.push((Some(day), path.to_string())),
+                    None => overrides.push((None, source)),
+                }
+            }
+            "-r" | "--repetitions" => repetitions = parse_count(&value(&arg)?, "repetitions", 1)?,
+            "-w" | "--warmup" => warmup = parse_count(&value(&arg)?, "warmup", 0)?,
+            "-e" | "--target-error" => {
+                let percent = value(&arg)?;
+                target_error = match percent.parse::<f64>() {
+                    Ok(percent) if percent > 0.0 => Some(percent),
+                    _ => return Err(format!("invalid target error '{}'", percent)),
+                }
+            }
+            "--max-repetitions" => {
+                max_repetitions = parse_count(&value(&arg)?, "max repetitions", 1)?
+            }
+            "-f" | "--format" => format = value(&arg)?.parse()?,
+            "-b" | "--baseline" => baseline = Some(value(&arg)?.into()),
+            "--save-baseline" => save_baseline = Some(value(&arg)?.into()),
+            "-t" | "--threshold" => {
+                let percent = value(&arg)?;
+                threshold = match percent.parse::<f64>() {
+                    Ok(percent) if percent >= 0.0 => percent,
+                    _ => return Err(format!("invalid threshold '{}'", percent)),
+                }
+            }
+            "--check-budgets" => check_budgets = true,
+            "-p" | "--parallel" => parallel = true,
+            "--no-color" => no_color = true,
+            "--ascii" => ascii = true,
+            "--width" => width = Some(parse_count(&value(&arg)?, "width", 40)? as usize),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("unknown argument '{}'\n\n{}", arg, USAGE)),
+        }
+    }
+
+    // The allocation counters are shared by every thread
+    if parallel && memory::ENABLED {
+        return Err("--parallel cannot be used with the memory feature".to_string());
+    }
+
+    // Resolve inputs
+    let days = days.unwrap_or_else(|| (1..=DAYS).collect());
+    let mut inputs: Vec<Input> = (1..=DAYS).map(Input::day).collect();
+    for (day, source) in overrides {
+        let day = match day {
+            Some(day) => day,
+            None if days.len() == 1 => days[0],
+            None => return Err("--input requires DAY= when running multiple days".to_string()),
+        };
+        inputs[day - 1] = parse_input(&source)?;
+    }
+
+    Ok(Options {
+        days,
+        inputs,
+        repetitions,
+        warmup,
+        target_error,
+        max_repetitions: std::cmp::max(max_repetitions, repetitions),
+        format,
+        baseline,
+        save_baseline,
+        threshold,
+        check_budgets,
+        parallel,
+        no_color,
+        ascii,
+        width,
+    })
+}
+
+// -----------------------------------------------------------------------------
@@ -1,119 +1,225 @@
 //! Day 1:
-//! This solution uses a binary mask array that contains the values between 1 and 2020
-//! found in the challenge input. I read the values into a mask array and load the values
-//! into a vector at the same time to reduce the memory movement.
+//! The puzzle only asks for two or three entries that add up to 2020, but the same search
+//! comes in handy for reconciling real expense data, so [`KSum`] finds any number of
+//! entries that add up to any target. The entries are sorted once, and all but the last
+//! entry of a combination are picked in order, so the search can stop as soon as the
+//! smallest entries left are already too large. The last entry comes from a lookup by
+//! value; a mask array indexed by value when the values are small and non-negative, as in
+//! the challenge input, and a hash map otherwise.
 
 use crate::prelude::*;
+use crate::registry::Day;
+use rustc_hash::FxHashMap;
+use std::convert::TryFrom;
 
-// Constant
-const YEAR: usize = 2020;
+// Constants
+const YEAR: i64 = 2020;
+const MASK_LIMIT: i64 = 1 << 16;
+const NONE: u32 = u32::MAX;
 
 // -----------------------------------------------------------------------------
-// Find pair of flagged indices that sum to length of mask array
+// Lookup by value
 // -----------------------------------------------------------------------------
-#[inline(always)]
-fn find_two(array: &[bool]) -> Option<i32> {
-    array
-        .iter()
-        .enumerate()
-        .zip(array.iter().rev())
-        .find_map(|((i, a), b)| if a & b { Some(i as i32) } else { None })
+// Index of the first sorted entry with each value
+#[derive(Debug)]
+enum Lookup {
+    Mask(Vec<u32>),
+    Hash(FxHashMap<i64, u32>),
 }
 
-// -----------------------------------------------------------------------------
-// Part 1
-// -----------------------------------------------------------------------------
-fn part_1(mask: &[bool]) -> (i32, i32) {
-    match find_two(&mask) {
-        Some(index) => (index, YEAR as i32 - index),
-        None => panic!("No pair found"),
+impl Lookup {
+    fn new(sorted: &[(i64, usize)]) -> Self {
+        let dense = match (sorted.first(), sorted.last()) {
+            (Some(&(min, _)), Some(&(max, _))) => min >= 0 && max < MASK_LIMIT,
+            _ => true,
+        };
+        if dense {
+            let size = sorted.last().map_or(0, |&(max, _)| max as usize + 1);
+            let mut mask = vec![NONE; size];
+            for (i, &(value, _)) in sorted.iter().enumerate().rev() {
+                mask[value as usize] = i as u32;
+            }
+            Lookup::Mask(mask)
+        } else {
+            let mut map = FxHashMap::with_capacity_and_hasher(sorted.len(), Default::default());
+            for (i, &(value, _)) in sorted.iter().enumerate().rev() {
+                map.insert(value, i as u32);
+            }
+            Lookup::Hash(map)
+        }
+    }
+
+    fn get(&self, value: i128) -> Option<usize> {
+        let index = match self {
+            Lookup::Mask(mask) => *mask.get(usize::try_from(value).ok()?)?,
+            Lookup::Hash(map) => *map.get(&i64::try_from(value).ok()?)?,
+        };
+        if index == NONE {
+            None
+        } else {
+            Some(index as usize)
+        }
     }
 }
 
 // -----------------------------------------------------------------------------
-// Part 2
+// K-sum
 // -----------------------------------------------------------------------------
-fn part_2(values: &[usize], mask: &[bool]) -> (i32, i32, i32) {
-    for value in values {
-        let remainder = YEAR - *value;
-        let index = find_two(&mask[0..=remainder]);
-        if index != None {
-            let triple = (
-                *value as i32,
-                index.unwrap(),
-                YEAR as i32 - *value as i32 - index.unwrap(),
-            );
-            return triple;
+/// Search for `k` entries of a list that add up to a target
+///
+/// Combinations are reported as the positions of the entries in the original list, in
+/// increasing order. Each entry is used at most once, but equal values at different
+/// positions are different entries.
+#[derive(Debug)]
+pub struct KSum {
+    sorted: Vec<(i64, usize)>,
+    lookup: Lookup,
+}
+
+impl KSum {
+    /// Prepare the search over a list of values
+    pub fn new(values: &[i64]) -> Self {
+        let mut sorted: Vec<(i64, usize)> = values.iter().copied().zip(0..).collect();
+        sorted.sort_unstable();
+        let lookup = Lookup::new(&sorted);
+        Self { sorted, lookup }
+    }
+
+    /// Positions of the first combination of `k` entries that adds up to `target`, in
+    /// the order of the search, which tries the smallest values first
+    pub fn first(&self, k: usize, target: i64) -> Option<Vec<usize>> {
+        let mut first = None;
+        self.search(k, target as i128, 0, &mut vec![], &mut |positions| {
+            first = Some(positions);
+            false
+        });
+        first
+    }
+
+    /// Positions of every combination of `k` entries that adds up to `target`
+    pub fn all(&self, k: usize, target: i64) -> Vec<Vec<usize>> {
+        let mut all = vec![];
+        self.search(k, target as i128, 0, &mut vec![], &mut |positions| {
+            all.push(positions);
+            true
+        });
+        all
+    }
+
+    // Pick entries from `start` on, in sorted order; false once `visit` asks to stop
+    fn search(
+        &self,
+        k: usize,
+        target: i128,
+        start: usize,
+        chosen: &mut Vec<usize>,
+        visit: &mut dyn FnMut(Vec<usize>) -> bool,
+    ) -> bool {
+        let sorted = &self.sorted;
+        let mut report = |chosen: &[usize], last: usize| {
+            let mut positions: Vec<usize> = chosen
+                .iter()
+                .chain(std::iter::once(&last))
+                .map(|&i| sorted[i].1)
+                .collect();
+            positions.sort_unstable();
+            visit(positions)
+        };
+        match k {
+            0 => target != 0 || visit(vec![]),
+            // Last entry by lookup, with any equal values after it
+            1 => {
+                let first = match self.lookup.get(target) {
+                    Some(first) => std::cmp::max(first, start),
+                    None => return true,
+                };
+                (first..sorted.len())
+                    .take_while(|&i| sorted[i].0 as i128 == target)
+                    .all(|i| report(chosen, i))
+            }
+            _ => {
+                let largest: i128 = sorted
+                    .iter()
+                    .rev()
+                    .take(k - 1)
+                    .map(|&(value, _)| value as i128)
+                    .sum();
+                for i in start..sorted.len().saturating_sub(k - 1) {
+                    // The smallest entries left are already too large
+                    let smallest: i128 = sorted[i..i + k].iter().map(|&(v, _)| v as i128).sum();
+                    if smallest > target {
+                        break;
+                    }
+                    let value = sorted[i].0 as i128;
+                    if value + largest < target {
+                        continue;
+                    }
+                    chosen.push(i);
+                    let searching = self.search(k - 1, target - value, i + 1, chosen, visit);
+                    chosen.pop();
+                    if !searching {
+                        return false;
+                    }
+                }
+                true
+            }
         }
     }
-    panic!("No triple found");
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day01.txt").unwrap();
-
-    // Read to vector
-    let mut mask = [false; YEAR + 1];
-    let values: Vec<usize> = buffer
-        .lines()
-        .map(|line| {
-            // Read to array and mask at same time
-            let value = line.parse().expect("failed to parse line");
-            mask[value] = true;
-            value
-        })
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Look for pair
-    let start_part_1 = Instant::now();
-    let tuple = part_1(&mask);
-    let product_1 = tuple.0 * tuple.1;
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Look for triple
-    let start_part_2 = Instant::now();
-    let triple = part_2(&values, &mask);
-    let product_2 = triple.0 * triple.1 * triple.2;
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        product_1 as i64,
-        product_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+/// Expense report entries, ready to search for sums
+#[derive(Debug)]
+pub struct ExpenseReport {
+    values: Vec<i64>,
+    sums: KSum,
 }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(1, "Report Repair");
-    output::print_part(1, "📄 Product", &format!("{}", results.part_1));
-    output::print_part(2, "📄 Product", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+impl ExpenseReport {
+    // Product of the first k entries that add up to the year
+    fn product(&self, k: usize) -> Answer {
+        self.sums
+            .first(k, YEAR)
+            .and_then(|positions| {
+                positions
+                    .iter()
+                    .try_fold(1_i64, |product, &i| product.checked_mul(self.values[i]))
+            })
+            .map_or(Answer::None, Answer::from)
+    }
+}
+
+/// Day 1, Report Repair
+pub struct Day01;
+
+impl Day for Day01 {
+    const NUMBER: usize = 1;
+    const NAME: &'static str = "Report Repair";
+    const LABELS: [&'static str; 2] = ["📄 Product", "📄 Product"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = ExpenseReport;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let values: Vec<i64> = buffer
+            .lines()
+            .map(|line| error::number(buffer, line))
+            .collect::<Result<_, _>>()?;
+        let sums = KSum::new(&values);
+        Ok(ExpenseReport { values, sums })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Look for pair
+        data.product(2)
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Look for triple
+        data.product(3)
+    }
 }
 
 // -----------------------------------------------------------------------------
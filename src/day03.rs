@@ -1,98 +1,316 @@
 //! Day 3:
 //! An important observation from today is that you get better performance from a combined
 //! map and fold/for_each that you get from chaining the two.
+//!
+//! The map is read once into a [`TreeGrid`], a bit per square, which wraps around
+//! horizontally like the puzzle says and can also wrap vertically. Any list of slopes can
+//! be counted on it, and [`TreeGrid::search`] tries every slope in a range to find the
+//! ones with the fewest and most trees, with [`TreeGrid::render`] to draw the path.
 
 use crate::prelude::*;
+use crate::registry::Day;
+use fixedbitset::FixedBitSet;
+
+// Constants
+/// Slopes of the puzzle, with part 1 first
+pub const SLOPES: [Slope; 5] = [
+    Slope { right: 3, down: 1 },
+    Slope { right: 1, down: 1 },
+    Slope { right: 5, down: 1 },
+    Slope { right: 7, down: 1 },
+    Slope { right: 1, down: 2 },
+];
 
 // -----------------------------------------------------------------------------
 // Count trees
 // -----------------------------------------------------------------------------
 #[inline(always)]
-fn hit_tree(line: &String, i: &usize, right: usize, down: usize, line_length: usize) -> bool {
+fn hit_tree(line: &str, i: &usize, right: usize, down: usize, line_length: usize) -> bool {
     // Note: this probably incurs bounds checking, but this day is already fast
-    (i % down == 0) && (line.as_bytes()[right * i / down % line_length] == b'#')
+    i.is_multiple_of(down) && (line.as_bytes()[right * i / down % line_length] == b'#')
 }
 
-#[inline(always)]
-fn count_trees(data: &Vec<String>, right: usize, down: usize, line_length: usize) -> usize {
-    data.iter()
-        .enumerate()
-        .filter(|(i, line)| hit_tree(*line, i, right, down, line_length))
-        .count()
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Check rows
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day03.txt").unwrap();
-
-    // Read to object iterator
-    let data: Vec<String> = buffer.lines().map(|line| line.to_string()).collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find trees on path
-    let start_part_1 = Instant::now();
-    let line_length = data[0].chars().count();
-    let count_1 = count_trees(&data, 3, 1, line_length);
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find product of trees on paths
-    let start_part_2 = Instant::now();
-    let slopes = [(1, 1), (5, 1), (7, 1), (1, 2)];
-    let product_2 = slopes.iter().fold(count_1, |product, &(right, down)| {
-        product * count_trees(&data, right, down, line_length)
-    });
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let slopes = [(3, 1), (1, 1), (5, 1), (7, 1), (1, 2)];
-    let line_length = buffer.lines().nth(0).unwrap().chars().count();
-    let mut trees_hit = [0; 5];
-    buffer.lines().enumerate().for_each(|(i, line)| {
-        let data = line.to_string();
-        slopes.iter().enumerate().for_each(|(j, &(right, down))| {
-            trees_hit[j] += hit_tree(&data, &i, right, down, line_length) as usize
-        });
-    });
-    let combined_1 = trees_hit[0];
-    let combined_2: usize = trees_hit.iter().product();
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, count_1);
-    assert_eq!(combined_2, product_2);
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        product_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
+// Rows must be non-empty, equal length, and only contain open squares and trees
+fn check_row(buffer: &str, line: &str, line_length: usize) -> Result<(), Error> {
+    if let Some(index) = line.find(|c| c != '.' && c != '#') {
+        return Err(Error::parse(
+            buffer,
+            &line[index..],
+            format!(
+                "unexpected character '{}'",
+                line[index..].chars().next().unwrap()
+            ),
+        ));
+    }
+    if line.is_empty() || line.len() != line_length {
+        return Err(Error::parse(
+            buffer,
+            line,
+            format!(
+                "expected row of length {}, found {}",
+                line_length,
+                line.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// First row, which sets the row length
+fn first_row(buffer: &str) -> Result<&str, Error> {
+    buffer
+        .lines()
+        .next()
+        .ok_or_else(|| Error::truncated(buffer, "at least one row"))
 }
 
 // -----------------------------------------------------------------------------
-// Report
+// Slope struct
 // -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(3, "Toboggan Trajectory");
-    output::print_part(1, "🌲 Count", &format!("{}", results.part_1));
-    output::print_part(2, "🌲 Product", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Steps right and down for each move of the toboggan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slope {
+    /// Columns to the right
+    pub right: usize,
+    /// Rows down
+    pub down: usize,
+}
+
+impl std::fmt::Display for Slope {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "right {}, down {}", self.right, self.down)
+    }
+}
+
+/// Slopes with the fewest and the most trees from a search, with their tree counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlopeSearch {
+    /// First slope with the fewest trees
+    pub fewest: (Slope, usize),
+    /// First slope with the most trees
+    pub most: (Slope, usize),
+}
+
+// -----------------------------------------------------------------------------
+// Tree grid
+// -----------------------------------------------------------------------------
+/// Edges of the map that wrap around
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Wrap {
+    /// The map repeats to the right, and the path ends at the bottom
+    Horizontal,
+    /// The map repeats in both directions, and the path ends back at the start
+    Both,
+}
+
+/// Map of the trees, with one bit per square
+#[derive(Debug, Clone)]
+pub struct TreeGrid {
+    trees: FixedBitSet,
+    width: usize,
+    height: usize,
+    wrap: Wrap,
+}
+
+impl std::str::FromStr for TreeGrid {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width = first_row(s)?.len();
+        let height = s.lines().count();
+        let mut trees = FixedBitSet::with_capacity(width * height);
+        for (i, line) in s.lines().enumerate() {
+            check_row(s, line, width)?;
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, c)| c == b'#')
+                .for_each(|(j, _)| trees.insert(i * width + j));
+        }
+        Ok(Self {
+            trees,
+            width,
+            height,
+            wrap: Wrap::Horizontal,
+        })
+    }
+}
+
+impl TreeGrid {
+    /// The same map, wrapping around at the given edges
+    pub fn with_wrap(self, wrap: Wrap) -> Self {
+        Self { wrap, ..self }
+    }
+
+    /// Columns in one copy of the map
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Rows in one copy of the map
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// True if there is a tree at a row and column, which wrap around where the map does
+    pub fn is_tree(&self, row: usize, column: usize) -> bool {
+        let row = match self.wrap {
+            Wrap::Horizontal if row >= self.height => return false,
+            Wrap::Horizontal => row,
+            Wrap::Both => row % self.height,
+        };
+        self.trees.contains(row * self.width + column % self.width)
+    }
+
+    /// Squares visited along a slope from the top left, as (row, column) in the first
+    /// copy of the map; a slope that never moves down goes around the map once
+    pub fn path(&self, slope: Slope) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // Number of moves until the path leaves the map or is back where it started
+        let across = self.width / gcd(self.width, slope.right % self.width);
+        let moves = match (self.wrap, slope.down) {
+            (_, 0) => across,
+            (Wrap::Horizontal, down) => self.height.div_ceil(down),
+            (Wrap::Both, down) => {
+                let along = self.height / gcd(self.height, down % self.height);
+                along / gcd(along, across) * across
+            }
+        };
+        (0..moves).map(move |i| (i * slope.down % self.height, i * slope.right % self.width))
+    }
+
+    /// Trees hit along a slope
+    pub fn count_trees(&self, slope: Slope) -> usize {
+        self.path(slope)
+            .filter(|&(row, column)| self.is_tree(row, column))
+            .count()
+    }
+
+    /// Trees hit along each of a list of slopes
+    pub fn count_slopes(&self, slopes: &[Slope]) -> Vec<usize> {
+        slopes
+            .iter()
+            .map(|&slope| self.count_trees(slope))
+            .collect()
+    }
+
+    /// Slopes with the fewest and the most trees, of every slope in the ranges of steps;
+    /// `None` if the ranges are empty
+    pub fn search(
+        &self,
+        right: std::ops::RangeInclusive<usize>,
+        down: std::ops::RangeInclusive<usize>,
+    ) -> Option<SlopeSearch> {
+        down.flat_map(|down| right.clone().map(move |right| Slope { right, down }))
+            .map(|slope| (slope, self.count_trees(slope)))
+            .fold(None, |search, (slope, trees)| match search {
+                None => Some(SlopeSearch {
+                    fewest: (slope, trees),
+                    most: (slope, trees),
+                }),
+                Some(mut search) => {
+                    if trees < search.fewest.1 {
+                        search.fewest = (slope, trees);
+                    }
+                    if trees > search.most.1 {
+                        search.most = (slope, trees);
+                    }
+                    Some(search)
+                }
+            })
+    }
+
+    /// The map with the path along a slope drawn on it, as in the puzzle; `O` for open
+    /// squares and `X` for trees that were hit
+    pub fn render(&self, slope: Slope) -> String {
+        let mut squares: Vec<Vec<u8>> = (0..self.height)
+            .map(|row| {
+                (0..self.width)
+                    .map(|column| {
+                        if self.is_tree(row, column) {
+                            b'#'
+                        } else {
+                            b'.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        for (row, column) in self.path(slope) {
+            let square = &mut squares[row][column];
+            *square = if *square == b'#' { b'X' } else { b'O' };
+        }
+        squares
+            .iter()
+            .map(|row| String::from_utf8_lossy(row) + "\n")
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Day
+// -----------------------------------------------------------------------------
+/// Map of the trees on the slope
+#[derive(Debug)]
+pub struct Map {
+    grid: TreeGrid,
+    count_1: usize,
+}
+
+/// Day 3, Toboggan Trajectory
+pub struct Day03;
+
+impl Day for Day03 {
+    const NUMBER: usize = 3;
+    const NAME: &'static str = "Toboggan Trajectory";
+    const LABELS: [&'static str; 2] = ["🌲 Count", "🌲 Product"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Map;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to bit grid
+        Ok(Map {
+            grid: buffer.parse()?,
+            count_1: 0,
+        })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find trees on path
+        data.count_1 = data.grid.count_trees(SLOPES[0]);
+        data.count_1.into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find product of trees on paths
+        data.grid
+            .count_slopes(&SLOPES[1..])
+            .iter()
+            .fold(data.count_1, |product, trees| product * trees)
+            .into()
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let line_length = first_row(buffer)?.len();
+        let mut trees_hit = [0; 5];
+        for (i, line) in buffer.lines().enumerate() {
+            check_row(buffer, line, line_length)?;
+            SLOPES.iter().enumerate().for_each(|(j, slope)| {
+                trees_hit[j] += hit_tree(line, &i, slope.right, slope.down, line_length) as usize
+            });
+        }
+        let combined_1 = trees_hit[0];
+        let combined_2: usize = trees_hit.iter().product();
+        Ok(Some((combined_1.into(), combined_2.into())))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -1,247 +1,538 @@
 //! Day 4:
 //! Interestingly, in contrast to Day 3, we achieve better performance by mapping before
-//! folding in this case. This is another good example of where `splitn` outperforms
-//! `split`, even if some strings have fewer than n chunks. It seems that parsing the
-//! passport is complex enough that combining is not as important.
+//! folding in this case. It seems that parsing the passport is complex enough that
+//! combining is not as important.
+//!
+//! The field rules are data rather than code, in a [`Schema`] with one line per field,
+//! such as `hgt: 150-193 cm | 59-76 in` or `cid?: any`, so a schema can be read from a
+//! text file and used on any records of `key:value` fields. [`Schema::validate`] reports
+//! every field that is missing, unknown, repeated or invalid, and why, where the puzzle
+//! only needs to know if there was any.
 
 use crate::prelude::*;
+use crate::registry::Day;
+use std::ops::RangeInclusive;
+
+// Constants
+/// Schema of the passport fields in the puzzle
+pub const PASSPORT: &str = "\
+byr: 1920-2002
+iyr: 2010-2020
+eyr: 2020-2030
+hgt: 150-193 cm | 59-76 in
+hcl: color
+ecl: one of amb blu brn gry grn hzl oth
+pid: 9 digits
+cid?: any
+";
 
 // -----------------------------------------------------------------------------
-// Passport data struct
+// Fields
 // -----------------------------------------------------------------------------
-#[derive(Debug)]
-struct PassportData {
-    byr: i32,
-    iyr: i32,
-    eyr: i32,
-    hgt: String,
-    hcl: String,
-    ecl: String,
-    pid: String,
-    cid: String,
-    len: usize,
-}
-
-macro_rules! copy_five {
-    ($e:expr) => {
-        [$e, $e, $e, $e, $e]
-    };
-}
-
-impl std::str::FromStr for PassportData {
-    type Err = std::num::ParseIntError;
+// Split a field into identifier and value
+fn split_field<'a>(s: &str, field: &'a str) -> Result<(&'a str, &'a str), Error> {
+    match field.split_once(':') {
+        Some((name, data)) if !name.is_empty() => Ok((name, data)),
+        _ => Err(Error::parse(
+            s,
+            field,
+            format!("expected 'key:value' field, found '{}'", field),
+        )),
+    }
+}
+
+// Fields of a record, separated by spaces or newlines
+fn fields(s: &str) -> impl Iterator<Item = Result<(&str, &str), Error>> {
+    s.split_whitespace().map(move |field| split_field(s, field))
+}
+
+// Unknown field identifiers are errors, not invalid passports
+fn unknown_field(s: &str, name: &str) -> Error {
+    Error::parse(s, name, format!("unknown field '{}'", name))
+}
+
+// -----------------------------------------------------------------------------
+// Rules
+// -----------------------------------------------------------------------------
+/// Rule for the value of a field
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Any value, `any`
+    Any,
+    /// Integer in a range, `N-M`
+    Range(RangeInclusive<i64>),
+    /// Integer followed by one of the units, each with its own range, `N-M UNIT | ...`
+    Units(Vec<(String, RangeInclusive<i64>)>),
+    /// `#` and six lowercase hex digits, `color`
+    Color,
+    /// One of a list of values, `one of A B ...`
+    OneOf(Vec<String>),
+    /// Exactly this many decimal digits, `N digits`
+    Digits(usize),
+}
+
+fn write_range(f: &mut std::fmt::Formatter, range: &RangeInclusive<i64>) -> std::fmt::Result {
+    write!(f, "{}-{}", range.start(), range.end())
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Rule::Any => write!(f, "any"),
+            Rule::Range(range) => write_range(f, range),
+            Rule::Units(units) => {
+                for (i, (unit, range)) in units.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write_range(f, range)?;
+                    write!(f, " {}", unit)?;
+                }
+                Ok(())
+            }
+            Rule::Color => write!(f, "color"),
+            Rule::OneOf(values) => write!(f, "one of {}", values.join(" ")),
+            Rule::Digits(digits) => write!(f, "{} digits", digits),
+        }
+    }
+}
+
+// Range as `N-M`, where either bound can be negative, so the separator is the first `-`
+// after a digit
+fn parse_range(s: &str, token: &str) -> Result<RangeInclusive<i64>, Error> {
+    let (lower, upper) = token
+        .char_indices()
+        .find(|&(i, c)| c == '-' && token[..i].ends_with(|c: char| c.is_ascii_digit()))
+        .map(|(i, _)| (&token[..i], &token[i + 1..]))
+        .ok_or_else(|| Error::parse(s, token, format!("expected range, found '{}'", token)))?;
+    let (lower, upper): (i64, i64) = (error::number(s, lower)?, error::number(s, upper)?);
+    if lower > upper {
+        return Err(Error::parse(s, token, format!("invalid range '{}'", token)));
+    }
+    Ok(lower..=upper)
+}
+
+impl std::str::FromStr for Rule {
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let [mut byr, mut iyr, mut eyr]: [i32; 3] = [0; 3];
-        let [mut hgt, mut hcl, mut ecl, mut pid, mut cid]: [String; 5] =
-            copy_five!("invalid".to_string());
-        let mut len: usize = 0;
-        for field in s.trim().splitn(8, &['\n', ' '][..]) {
-            len += 1;
-            let data = &field[4..];
-            match &field[0..3] {
-                "byr" => byr = data.parse()?,
-                "iyr" => iyr = data.parse()?,
-                "eyr" => eyr = data.parse()?,
-                "hgt" => hgt = data.to_string(),
-                "hcl" => hcl = data.to_string(),
-                "ecl" => ecl = data.to_string(),
-                "pid" => pid = data.to_string(),
-                "cid" => cid = data.to_string(),
-                _ => panic!("Unknown field"),
+        if s == "any" {
+            Ok(Rule::Any)
+        } else if s == "color" {
+            Ok(Rule::Color)
+        } else if let Some(values) = s.strip_prefix("one of ") {
+            let values: Vec<String> = values.split_whitespace().map(str::to_string).collect();
+            if values.is_empty() {
+                return Err(Error::truncated(s, "at least one value"));
             }
+            Ok(Rule::OneOf(values))
+        } else if let Some(digits) = s.strip_suffix(" digits") {
+            Ok(Rule::Digits(error::number(s, digits)?))
+        } else if s.contains(' ') {
+            s.split(" | ")
+                .map(|alternative| {
+                    let (range, unit) = alternative
+                        .split_once(' ')
+                        .ok_or_else(|| Error::parse(s, alternative, "expected 'N-M UNIT'"))?;
+                    if unit.is_empty() || !unit.chars().all(char::is_alphabetic) {
+                        return Err(Error::parse(s, unit, format!("invalid unit '{}'", unit)));
+                    }
+                    Ok((unit.to_string(), parse_range(s, range)?))
+                })
+                .collect::<Result<_, _>>()
+                .map(Rule::Units)
+        } else if s.contains('-') {
+            parse_range(s, s).map(Rule::Range)
+        } else {
+            Err(Error::parse(s, s, format!("unknown rule '{}'", s)))
         }
-        Ok(Self {
-            byr,
-            iyr,
-            eyr,
-            hgt,
-            hcl,
-            ecl,
-            pid,
-            cid,
-            len,
-        })
     }
 }
 
-// -----------------------------------------------------------------------------
-// Part 1
-// -----------------------------------------------------------------------------
-#[inline(always)]
-fn part_1(data: &PassportData) -> bool {
-    (data.len == 8) ^ (data.len == 7 && data.cid == "invalid")
+// Integer in a range, or the reason it is not
+fn check_range(value: &str, range: &RangeInclusive<i64>, unit: &str) -> Result<(), String> {
+    let number: i64 = value
+        .parse()
+        .map_err(|_| format!("expected a number, found '{}'", value))?;
+    if range.contains(&number) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}{} is outside {}-{}{}",
+            number,
+            unit,
+            range.start(),
+            range.end(),
+            unit
+        ))
+    }
+}
+
+impl Rule {
+    /// Check a value, with the reason it breaks the rule
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            Rule::Any => Ok(()),
+            Rule::Range(range) => check_range(value, range, ""),
+            Rule::Units(units) => {
+                // One unit can end with another, like `m` and `cm`, so the number has to
+                // parse too
+                let mut matches = units
+                    .iter()
+                    .filter_map(|(unit, range)| Some((value.strip_suffix(&unit[..])?, range, unit)))
+                    .peekable();
+                let first = matches.peek().copied();
+                match matches
+                    .find(|(number, _, _)| number.parse::<i64>().is_ok())
+                    .or(first)
+                {
+                    Some((number, range, unit)) => check_range(number, range, unit),
+                    None => {
+                        let units: Vec<&str> = units.iter().map(|(unit, _)| &unit[..]).collect();
+                        Err(format!("expected a unit of {}", units.join(" or ")))
+                    }
+                }
+            }
+            Rule::Color => match value.strip_prefix('#') {
+                Some(hex)
+                    if hex.len() == 6
+                        && hex.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')) =>
+                {
+                    Ok(())
+                }
+                _ => Err("expected '#' and 6 hex digits".to_string()),
+            },
+            Rule::OneOf(values) => {
+                if values.iter().any(|allowed| allowed == value) {
+                    Ok(())
+                } else {
+                    Err(format!("expected one of {}", values.join(" ")))
+                }
+            }
+            Rule::Digits(digits) => {
+                if value.len() == *digits && value.bytes().all(|c| c.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(format!("expected {} digits", digits))
+                }
+            }
+        }
+    }
 }
 
 // -----------------------------------------------------------------------------
-// Part 2
+// Schema
 // -----------------------------------------------------------------------------
-// Birth year
-#[inline(always)]
-fn byr_valid(byr: i32) -> bool {
-    (1920..=2002).contains(&byr)
+/// Rule for one field of a schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldRule {
+    /// Identifier of the field
+    pub name: String,
+    /// Whether a record must have the field
+    pub required: bool,
+    /// Rule for the value
+    pub rule: Rule,
 }
 
-// Issue year
-#[inline(always)]
-fn iyr_valid(iyr: i32) -> bool {
-    (2010..=2020).contains(&iyr)
+impl std::fmt::Display for FieldRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let optional = if self.required { "" } else { "?" };
+        write!(f, "{}{}: {}", self.name, optional, self.rule)
+    }
 }
 
-// Expire year
-#[inline(always)]
-fn eyr_valid(eyr: i32) -> bool {
-    (2020..=2030).contains(&eyr)
+/// Fields a record may have, and the rules for their values
+///
+/// A schema is written one field per line as `KEY: RULE`, or `KEY?: RULE` for a field
+/// that may be left out, with the rules `any`, `N-M`, `N-M UNIT | N-M UNIT ...`, `color`,
+/// `one of A B ...` and `N digits`. Blank lines and lines starting with `#` are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    fields: Vec<FieldRule>,
 }
 
-// Eye color
-#[inline(always)]
-fn ecl_valid(ecl: &String) -> bool {
-    ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&ecl.as_str())
+impl std::fmt::Display for Schema {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.fields
+            .iter()
+            .try_for_each(|field| writeln!(f, "{}", field))
+    }
 }
 
-// Hair color
-#[inline(always)]
-fn hcl_valid(hcl: &String) -> bool {
-    (hcl.as_bytes()[0] == b'#') && (hcl.len() == 7) && (hcl.chars().skip(1).all(|x| x.is_digit(16)))
+impl std::str::FromStr for Schema {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields: Vec<FieldRule> = vec![];
+        for line in s.lines() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, rule) = line
+                .split_once(": ")
+                .ok_or_else(|| Error::parse(s, line, "expected 'KEY: RULE'"))?;
+            let (name, required) = match key.strip_suffix('?') {
+                Some(name) => (name, false),
+                None => (key, true),
+            };
+            if name.is_empty() || name.contains(|c: char| c == ':' || c.is_whitespace()) {
+                return Err(Error::parse(s, key, format!("invalid key '{}'", key)));
+            }
+            if fields.iter().any(|field| field.name == name) {
+                return Err(Error::parse(s, key, format!("duplicate field '{}'", name)));
+            }
+            fields.push(FieldRule {
+                name: name.to_string(),
+                required,
+                rule: rule.parse().map_err(|error: Error| error.within(s, rule))?,
+            });
+        }
+        Ok(Self { fields })
+    }
 }
 
-// Passport ID
-#[inline(always)]
-fn pid_valid(pid: &String) -> bool {
-    (pid.len() == 9) && (pid.chars().all(char::is_numeric))
-}
+impl Schema {
+    /// The passport fields of the puzzle, see [`PASSPORT`]
+    pub fn passport() -> Self {
+        PASSPORT.parse().expect("passport schema")
+    }
 
-// Passport ID
-#[inline(always)]
-fn hgt_valid(hgt: &String) -> bool {
-    let len = hgt.len();
-    (hgt.as_bytes()[len - 1] == b'm' && (150..=193).contains(&hgt[0..len - 2].parse().unwrap()))
-        || (hgt.as_bytes()[len - 1] == b'n'
-            && (59..=76).contains(&hgt[0..len - 2].parse().unwrap()))
-}
+    /// Read a schema from a text file
+    pub fn load(path: &std::path::Path) -> Result<Self, Error> {
+        std::fs::read_to_string(path)?.parse()
+    }
 
-fn part_2(data: &PassportData) -> bool {
-    (data.len >= 7)
-        && byr_valid(data.byr)
-        && iyr_valid(data.iyr)
-        && eyr_valid(data.eyr)
-        && ecl_valid(&data.ecl)
-        && hcl_valid(&data.hcl)
-        && pid_valid(&data.pid)
-        && hgt_valid(&data.hgt)
+    /// Rules for each field, in the order of the schema
+    pub fn fields(&self) -> &[FieldRule] {
+        &self.fields
+    }
+
+    /// Rule for a field, if the schema has it
+    pub fn field(&self, name: &str) -> Option<&FieldRule> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Check the fields of a record, as (key, value) pairs
+    pub fn check<'a>(&self, record: impl IntoIterator<Item = (&'a str, &'a str)>) -> Report {
+        let mut seen = vec![false; self.fields.len()];
+        let mut diagnostics = vec![];
+        for (name, value) in record {
+            let problem = match self.fields.iter().position(|field| field.name == name) {
+                None => Problem::Unknown,
+                Some(i) if seen[i] => Problem::Repeated,
+                Some(i) => {
+                    seen[i] = true;
+                    match self.fields[i].rule.check(value) {
+                        Ok(()) => continue,
+                        Err(reason) => Problem::Invalid {
+                            value: value.to_string(),
+                            reason,
+                        },
+                    }
+                }
+            };
+            diagnostics.push(Diagnostic {
+                field: name.to_string(),
+                problem,
+            });
+        }
+        diagnostics.extend(
+            self.fields
+                .iter()
+                .zip(seen)
+                .filter(|&(field, seen)| field.required && !seen)
+                .map(|(field, _)| Diagnostic {
+                    field: field.name.clone(),
+                    problem: Problem::Missing,
+                }),
+        );
+        Report { diagnostics }
+    }
+
+    /// Check a record of `key:value` fields, separated by spaces or newlines
+    pub fn validate(&self, record: &str) -> Result<Report, Error> {
+        let fields: Vec<(&str, &str)> = fields(record).collect::<Result<_, _>>()?;
+        Ok(self.check(fields))
+    }
 }
 
 // -----------------------------------------------------------------------------
-// Combined
+// Report
 // -----------------------------------------------------------------------------
-#[derive(Debug)]
-struct PassportValidityData {
-    part_1: bool,
-    part_2: bool,
+/// What is wrong with a field of a record
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// A required field is not in the record
+    Missing,
+    /// The field is not in the schema
+    Unknown,
+    /// The field appeared earlier in the record
+    Repeated,
+    /// The value breaks the rule for the field
+    Invalid {
+        /// The value
+        value: String,
+        /// Why the value breaks the rule
+        reason: String,
+    },
 }
 
-impl std::str::FromStr for PassportValidityData {
-    type Err = std::num::ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut fields = 0;
-        let mut valid_fields = 0;
-        let mut cid = false;
-        for field in s.trim().splitn(8, &['\n', ' '][..]) {
-            fields += 1;
-            let name = &field[0..3]; // First three chars are the identifier
-            let data = field[4..].to_string();
-            match name {
-                "byr" => valid_fields += byr_valid(data.parse().unwrap()) as usize,
-                "iyr" => valid_fields += iyr_valid(data.parse().unwrap()) as usize,
-                "eyr" => valid_fields += eyr_valid(data.parse().unwrap()) as usize,
-                "hgt" => valid_fields += hgt_valid(&data) as usize,
-                "hcl" => valid_fields += hcl_valid(&data) as usize,
-                "ecl" => valid_fields += ecl_valid(&data) as usize,
-                "pid" => valid_fields += pid_valid(&data) as usize,
-                "cid" => cid = true,
-                _ => panic!("Unknown field"),
+/// Problem with one field of a record
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Identifier of the field
+    pub field: String,
+    /// What is wrong with it
+    pub problem: Problem,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.problem {
+            Problem::Missing => write!(f, "{}: missing", self.field),
+            Problem::Unknown => write!(f, "{}: unknown field", self.field),
+            Problem::Repeated => write!(f, "{}: repeated", self.field),
+            Problem::Invalid { value, reason } => {
+                write!(f, "{}: invalid '{}', {}", self.field, value, reason)
+            }
+        }
+    }
+}
+
+/// Every problem found with a record, in the order of the record, then missing fields
+/// in the order of the schema
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    /// Problems with the fields
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_valid() {
+            return write!(f, "valid");
+        }
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
             }
+            write!(f, "{}", diagnostic)?;
         }
-        let part_1 = (fields == 8) || (fields == 7 && !cid);
-        let part_2 = valid_fields == 7;
-        Ok(Self { part_1, part_2 })
+        Ok(())
+    }
+}
+
+impl Report {
+    /// True if there are no problems at all
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// True if all the required fields are there, whatever their values
+    pub fn is_complete(&self) -> bool {
+        !self
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.problem == Problem::Missing)
     }
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Passport data struct
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer = std::fs::read_to_string("data/day04.txt").unwrap();
-
-    // Read to object iterator
-    let data: Vec<PassportData> = buffer
-        .split("\n\n")
-        .map(|line| {
-            line.parse::<PassportData>()
-                .expect("failed to parse passport")
-        })
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find matching passports
-    let start_part_1 = Instant::now();
-    let count_1 = data.iter().filter(|&d| part_1(d)).count();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find matching passports
-    let start_part_2 = Instant::now();
-    let count_2 = data.iter().filter(|&d| part_2(d)).count();
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let (combined_1, combined_2) = buffer
-        .split("\n\n")
-        .map(|line| {
-            line.parse::<PassportValidityData>()
-                .expect("failed to parse passport")
+/// Passport fields, as read
+#[derive(Debug)]
+pub struct PassportData {
+    fields: Vec<(String, String)>,
+}
+
+// Fields of a passport, which must all be in the schema
+fn passport<'a>(schema: &Schema, s: &'a str) -> Result<Vec<(&'a str, &'a str)>, Error> {
+    fields(s)
+        .map(|field| match field {
+            Ok((name, _)) if schema.field(name).is_none() => Err(unknown_field(s, name)),
+            field => field,
         })
-        .fold((0, 0), |mut acc, passport| {
-            acc.0 += passport.part_1 as usize;
-            acc.1 += passport.part_2 as usize;
-            acc
-        });
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, count_1);
-    assert_eq!(combined_2, count_2);
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
+        .collect()
+}
+
+impl PassportData {
+    /// Check the passport against a schema
+    pub fn check(&self, schema: &Schema) -> Report {
+        schema.check(
+            self.fields
+                .iter()
+                .map(|(name, value)| (&name[..], &value[..])),
+        )
+    }
 }
 
 // -----------------------------------------------------------------------------
-// Report
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(4, "Passport Processing");
-    output::print_part(1, "📘 Valid", &format!("{}", results.part_1));
-    output::print_part(2, "📘 Valid", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Passports and the schema to check them against
+#[derive(Debug)]
+pub struct Passports {
+    schema: Schema,
+    passports: Vec<PassportData>,
+}
+
+/// Day 4, Passport Processing
+pub struct Day04;
+
+impl Day for Day04 {
+    const NUMBER: usize = 4;
+    const NAME: &'static str = "Passport Processing";
+    const LABELS: [&'static str; 2] = ["📘 Valid", "📘 Valid"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Passports;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to object iterator
+        let schema = Schema::passport();
+        let passports = buffer
+            .split("\n\n")
+            .map(|s| {
+                let fields = passport(&schema, s)
+                    .map_err(|error| error.within(buffer, s))?
+                    .iter()
+                    .map(|&(name, value)| (name.to_string(), value.to_string()))
+                    .collect();
+                Ok(PassportData { fields })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Passports { schema, passports })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find passports with every required field
+        let schema = &data.schema;
+        data.passports
+            .iter()
+            .filter(|passport| passport.check(schema).is_complete())
+            .count()
+            .into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find passports with every field valid
+        let schema = &data.schema;
+        data.passports
+            .iter()
+            .filter(|passport| passport.check(schema).is_valid())
+            .count()
+            .into()
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let schema = Schema::passport();
+        let (combined_1, combined_2) = buffer.split("\n\n").try_fold((0, 0), |mut acc, s| {
+            let report =
+                schema.check(passport(&schema, s).map_err(|error| error.within(buffer, s))?);
+            acc.0 += report.is_complete() as i64;
+            acc.1 += report.is_valid() as i64;
+            Ok::<_, Error>(acc)
+        })?;
+        Ok(Some((combined_1.into(), combined_2.into())))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -2,110 +2,262 @@
 //! Two separate passes are required over the data, one to convert from a string to a
 //! binary and one to find the valid seat. However, we can still save some time by
 //! locating the max seat while filling the binary seat array
+//!
+//! A boarding pass is just the seat ID in binary, with `F`/`B` for the row bits and
+//! `L`/`R` for the column bits, so a [`Codec`] for any number of row and column bits
+//! both decodes and encodes them. A [`SeatMap`] of the decoded passes draws the plane
+//! and lists every run of empty seats, of which my seat is the one with a single seat.
 
 use crate::prelude::*;
-use itertools::Itertools;
+use crate::registry::Day;
+use fixedbitset::FixedBitSet;
+use std::ops::RangeInclusive;
+
+// Constants
+/// Layout of the plane in the puzzle, with 128 rows of 8 seats
+pub const PLANE: Codec = Codec {
+    row_bits: 7,
+    column_bits: 3,
+};
+const MAX_BITS: usize = 24;
 
 // -----------------------------------------------------------------------------
-// Parse FBLR encoded binary
+// Codec
 // -----------------------------------------------------------------------------
-#[inline(always)]
-fn parse_fblr_binary(s: &str) -> Result<usize, std::num::ParseIntError> {
-    Ok(s.as_bytes()
-        .iter()
-        .fold(0, |id, c| (id << 1) + (*c as usize % 7) % 2)) // B, R -> 1; F, L -> 0
+/// Boarding pass encoding for a plane with `2^row_bits` rows of `2^column_bits` seats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Codec {
+    row_bits: usize,
+    column_bits: usize,
 }
 
-// -----------------------------------------------------------------------------
-// Part 2
-// -----------------------------------------------------------------------------
-#[inline(always)]
-fn part_2(tuple: (&bool, &bool)) -> bool {
-    *tuple.0 && !*tuple.1 // First open seat, so only need to check pair, not triple
+impl Codec {
+    /// Codec for a plane size; panics past 24 bits in all, which would be a big plane
+    pub fn new(row_bits: usize, column_bits: usize) -> Self {
+        assert!(
+            row_bits + column_bits <= MAX_BITS,
+            "at most {} bits per boarding pass",
+            MAX_BITS
+        );
+        Self {
+            row_bits,
+            column_bits,
+        }
+    }
+
+    /// Rows in the plane
+    pub fn rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    /// Seats in each row
+    pub fn columns(&self) -> usize {
+        1 << self.column_bits
+    }
+
+    /// Seat IDs in the plane, which run from 0 to one less than this
+    pub fn seats(&self) -> usize {
+        self.rows() * self.columns()
+    }
+
+    /// Characters in a boarding pass
+    pub fn bits(&self) -> usize {
+        self.row_bits + self.column_bits
+    }
+
+    /// Seat ID of a boarding pass, with the location of a bad character on failure
+    pub fn decode(&self, s: &str) -> Result<usize, Error> {
+        let length = s.chars().count();
+        if length != self.bits() {
+            return Err(Error::parse(
+                s,
+                s,
+                format!("expected {} characters, found {}", self.bits(), length),
+            ));
+        }
+        s.char_indices()
+            .enumerate()
+            .try_fold(0, |id, (i, (offset, c))| {
+                let bit = match (i < self.row_bits, c) {
+                    (true, 'F') | (false, 'L') => 0,
+                    (true, 'B') | (false, 'R') => 1,
+                    (row, _) => {
+                        let expected = if row { "F or B" } else { "L or R" };
+                        return Err(Error::parse(
+                            s,
+                            &s[offset..],
+                            format!("unexpected character '{}', expected {}", c, expected),
+                        ));
+                    }
+                };
+                Ok((id << 1) | bit)
+            })
+    }
+
+    /// Boarding pass for a seat ID; `None` if the plane has no such seat
+    pub fn encode(&self, id: usize) -> Option<String> {
+        if id >= self.seats() {
+            return None;
+        }
+        Some(
+            (0..self.bits())
+                .rev()
+                .map(|bit| match (bit >= self.column_bits, id >> bit & 1) {
+                    (true, 0) => 'F',
+                    (true, _) => 'B',
+                    (false, 0) => 'L',
+                    (false, _) => 'R',
+                })
+                .collect(),
+        )
+    }
+
+    /// Row and column of a seat ID
+    pub fn seat(&self, id: usize) -> (usize, usize) {
+        (id >> self.column_bits, id & (self.columns() - 1))
+    }
+}
+
+// Lines to seat IDs
+fn parse_seats(buffer: &str, codec: Codec) -> impl Iterator<Item = Result<usize, Error>> + '_ {
+    buffer.lines().map(move |line| {
+        codec
+            .decode(line)
+            .map_err(|error| error.within(buffer, line))
+    })
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Seat map
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer = std::fs::read_to_string("data/day05.txt").unwrap();
-
-    // Read to object iterator
-    let data: Vec<usize> = buffer
-        .lines()
-        .map(|line| parse_fblr_binary(&line).expect("failed to parse line"))
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find matching passports
-    let start_part_1 = Instant::now();
-    let max_1 = *data.iter().max().unwrap();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find matching passports
-    let start_part_2 = Instant::now();
-    let mut mask = [false; 2 << 9];
-    data.iter().for_each(|&s| mask[s] = true);
-    let seat_2 = mask
-        .iter()
-        .tuple_windows::<(_, _)>()
-        .enumerate()
-        .find_map(|(i, t)| if part_2(t) { Some(i as i32) } else { None })
-        .unwrap()
-        + 1;
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let mut combined_mask = [false; 2 << 9];
-    let mut combined_1 = 0;
-    buffer.lines().for_each(|line| {
-        let s = parse_fblr_binary(&line).expect("failed to parse line");
-        combined_mask[s] = true;
-        combined_1 = std::cmp::max(combined_1, s);
-    });
-    let combined_2 = mask
-        .iter()
-        .tuple_windows::<(_, _)>()
-        .enumerate()
-        .find_map(|(i, t)| if part_2(t) { Some(i as i32) } else { None })
-        .unwrap()
-        + 1;
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, max_1);
-    assert_eq!(combined_2, seat_2);
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        max_1 as i64,
-        seat_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
+/// State of a seat on the map
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Seat {
+    /// Someone has a boarding pass for it
+    Occupied,
+    /// Nobody has a boarding pass for it, but there are occupied seats on either side
+    Empty,
+    /// In front of the first or behind the last occupied seat, so it may not exist
+    Missing,
+}
+
+/// Seats of a plane with a boarding pass
+#[derive(Debug, Clone)]
+pub struct SeatMap {
+    codec: Codec,
+    occupied: FixedBitSet,
+}
+
+impl SeatMap {
+    /// Map of the seat IDs of the boarding passes; panics if an ID is off the plane
+    pub fn new(codec: Codec, ids: &[usize]) -> Self {
+        let mut occupied = FixedBitSet::with_capacity(codec.seats());
+        ids.iter().for_each(|&id| occupied.insert(id));
+        Self { codec, occupied }
+    }
+
+    /// Lowest and highest occupied seat IDs, if any
+    pub fn span(&self) -> Option<RangeInclusive<usize>> {
+        let mut ids = self.occupied.ones();
+        let first = ids.next()?;
+        Some(first..=ids.last().unwrap_or(first))
+    }
+
+    /// State of a seat
+    pub fn seat(&self, id: usize) -> Seat {
+        match self.span() {
+            _ if self.occupied.contains(id) => Seat::Occupied,
+            Some(span) if span.contains(&id) => Seat::Empty,
+            _ => Seat::Missing,
+        }
+    }
+
+    /// Every run of empty seats, in order; my seat is the run with a single seat
+    pub fn gaps(&self) -> Vec<RangeInclusive<usize>> {
+        let mut gaps = vec![];
+        let mut previous = None;
+        for id in self.occupied.ones() {
+            match previous {
+                Some(previous) if id > previous + 1 => gaps.push(previous + 1..=id - 1),
+                _ => (),
+            }
+            previous = Some(id);
+        }
+        gaps
+    }
+
+    /// The plane, a row per line, with `#` for occupied seats, `L` for empty seats and `.`
+    /// for missing seats, as on the ferry in day 11
+    pub fn render(&self) -> String {
+        let span = self.span();
+        let mut map = String::with_capacity(self.codec.seats() + self.codec.rows());
+        for id in 0..self.codec.seats() {
+            map.push(match span {
+                _ if self.occupied.contains(id) => '#',
+                Some(ref span) if span.contains(&id) => 'L',
+                _ => '.',
+            });
+            if self.codec.seat(id).1 == self.codec.columns() - 1 {
+                map.push('\n');
+            }
+        }
+        map
+    }
 }
 
 // -----------------------------------------------------------------------------
-// Report
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(5, "Binary Boarding");
-    output::print_part(1, "💺 Largest", &format!("{}", results.part_1));
-    output::print_part(2, "💺 Available", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Day 5, Binary Boarding
+pub struct Day05;
+
+impl Day for Day05 {
+    const NUMBER: usize = 5;
+    const NAME: &'static str = "Binary Boarding";
+    const LABELS: [&'static str; 2] = ["💺 Largest", "💺 Available"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Vec<usize>;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to object iterator
+        parse_seats(buffer, PLANE).collect()
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find largest seat
+        match data.iter().max() {
+            Some(&seat) => seat.into(),
+            None => Answer::None,
+        }
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find open seat
+        SeatMap::new(PLANE, data)
+            .gaps()
+            .iter()
+            .find(|gap| gap.start() == gap.end())
+            .map_or(Answer::None, |gap| (*gap.start()).into())
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let mut combined_mask = [false; 2 << 9];
+        let mut combined_1 = None;
+        for s in parse_seats(buffer, PLANE) {
+            let s = s?;
+            combined_mask[s] = true;
+            combined_1 = std::cmp::max(combined_1, Some(s));
+        }
+        // Open seat between two occupied seats
+        let combined_2 = combined_mask
+            .windows(3)
+            .position(|seats| seats[0] && !seats[1] && seats[2])
+            .map(|i| i + 1);
+        Ok(Some((
+            combined_1.map_or(Answer::None, Answer::from),
+            combined_2.map_or(Answer::None, Answer::from),
+        )))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -4,22 +4,39 @@
 //! combined section.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // -----------------------------------------------------------------------------
 // Parse line to array
 // -----------------------------------------------------------------------------
-fn to_array(responses: &str) -> Result<[usize; 27], std::num::ParseIntError> {
+fn to_array(responses: &str) -> Result<[usize; 27], Error> {
     let mut array = [0; 27];
-    responses.trim().split("\n").for_each(|person| {
-        person
-            .as_bytes()
-            .iter()
-            .for_each(|answer| array[(answer - b'a') as usize] += 1);
+    for person in responses.trim().split('\n') {
+        for (i, answer) in person.bytes().enumerate() {
+            if !answer.is_ascii_lowercase() {
+                return Err(Error::parse(
+                    responses,
+                    &person[i..],
+                    format!(
+                        "unexpected answer '{}'",
+                        person[i..].chars().next().unwrap()
+                    ),
+                ));
+            }
+            array[(answer - b'a') as usize] += 1;
+        }
         array[26] += 1
-    });
+    }
     Ok(array)
 }
 
+// Groups to response arrays
+fn parse_groups(buffer: &str) -> impl Iterator<Item = Result<[usize; 27], Error>> + '_ {
+    buffer
+        .split("\n\n")
+        .map(move |group| to_array(group).map_err(|error| error.within(buffer, group)))
+}
+
 // -----------------------------------------------------------------------------
 // Part 1
 // -----------------------------------------------------------------------------
@@ -41,71 +58,41 @@ fn part_2(responses: &[usize; 27]) -> usize {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day06.txt").unwrap();
+/// Day 6, Custom Customs
+pub struct Day06;
 
-    // Read to object iterator
-    let data: Vec<[usize; 27]> = buffer
-        .split("\n\n")
-        .map(|line| to_array(line).expect("failed to parse line"))
-        .collect();
-    let time_setup = start_setup.elapsed();
+impl Day for Day06 {
+    const NUMBER: usize = 6;
+    const NAME: &'static str = "Custom Customs";
+    const LABELS: [&'static str; 2] = ["✅ Count", "✅ Shared"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Vec<[usize; 27]>;
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find any response
-    let start_part_1 = Instant::now();
-    let count_1: usize = data.iter().map(|d| part_1(&d)).sum();
-    let time_part_1 = start_part_1.elapsed();
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to object iterator
+        parse_groups(buffer).collect()
+    }
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find matching responses
-    let start_part_2 = Instant::now();
-    let count_2: usize = data.iter().map(|d| part_2(&d)).sum();
-    let time_part_2 = start_part_2.elapsed();
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find any response
+        data.iter().map(part_1).sum::<usize>().into()
+    }
 
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let (combined_1, combined_2) = buffer
-        .split("\n\n")
-        .map(|line| to_array(line).expect("failed to parse line"))
-        .fold((0, 0), |acc, responses| {
-            (acc.0 + part_1(&responses), acc.1 + part_2(&responses))
-        });
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, count_1);
-    assert_eq!(combined_2, count_2);
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find matching responses
+        data.iter().map(part_2).sum::<usize>().into()
+    }
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
-}
-
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(6, "Custom Customs");
-    output::print_part(1, "✅ Count", &format!("{}", results.part_1));
-    output::print_part(2, "✅ Shared", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let (combined_1, combined_2) =
+            parse_groups(buffer).try_fold((0, 0), |acc, responses| {
+                let responses = responses?;
+                Ok::<_, Error>((acc.0 + part_1(&responses), acc.1 + part_2(&responses)))
+            })?;
+        Ok(Some((combined_1.into(), combined_2.into())))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -4,8 +4,8 @@
 //! bags is straightforward and fast. Switching to the rustc hasher helped trim some time.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use rustc_hash::{FxHashMap, FxHashSet};
-use std::sync::RwLock;
 
 const CAPACITY: usize = 512;
 
@@ -24,8 +24,9 @@ impl Holding {
     }
 }
 
+/// Bag rule in the containment graph, keyed by bag color
 #[derive(Debug)]
-struct Node {
+pub struct Node {
     contained_by: Vec<u32>,
     contains: Vec<Holding>,
 }
@@ -40,27 +41,117 @@ impl Node {
 }
 
 #[inline(always)]
-fn str_to_key(t: &str) -> Result<u32, std::num::ParseIntError> {
-    Ok(t.as_bytes()
+fn str_to_key(t: &str) -> u32 {
+    t.as_bytes()
         .iter()
-        .fold(1 as u32, |acc, c| (acc << 1) + *c as u32))
+        .fold(1_u32, |acc, c| (acc << 1).wrapping_add(*c as u32))
 }
 
-fn add_to_graph(s: &str, bag_graph: &mut FxHashMap<u32, Node>) {
+// Color of the container and colors of the contained bags in a rule
+fn rule_colors(line: &str) -> (&str, impl Iterator<Item = &str>) {
+    let (container, contents) = line.split_once(" bags contain ").unwrap_or((line, ""));
+    (
+        container,
+        contents
+            .split(", ")
+            .filter_map(|holding| holding.split_once(' ')?.1.split(" bag").next())
+            .filter(|bag| !bag.is_empty() && *bag != "other"),
+    )
+}
+
+// The graph is keyed by a cheap hash of the color, so distinct colors must not collide
+fn check_keys(buffer: &str) -> Result<(), Error> {
+    let mut colors = FxHashMap::<u32, &str>::with_capacity_and_hasher(CAPACITY, Default::default());
+    let bags = buffer.lines().flat_map(|line| {
+        let (container, contained) = rule_colors(line);
+        std::iter::once(container).chain(contained)
+    });
+    for bag in bags {
+        match colors.insert(str_to_key(bag), bag) {
+            Some(other) if other != bag => {
+                return Err(Error::parse(
+                    buffer,
+                    bag,
+                    format!("bag colors '{}' and '{}' have the same key", other, bag),
+                ))
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+// Color of a bag that ends up inside of itself, if any; true in `visiting` while the bags
+// inside of a color are being searched
+fn find_cycle<'a>(
+    color: &'a str,
+    rules: &FxHashMap<&str, Vec<&'a str>>,
+    visiting: &mut FxHashMap<&'a str, bool>,
+) -> Option<&'a str> {
+    match visiting.insert(color, true) {
+        Some(true) => return Some(color),
+        Some(false) => {
+            visiting.insert(color, false);
+            return None;
+        }
+        None => (),
+    }
+    let cycle = rules
+        .get(color)
+        .into_iter()
+        .flatten()
+        .find_map(|&bag| find_cycle(bag, rules, visiting));
+    visiting.insert(color, false);
+    cycle
+}
+
+// Part 2 recurses into the contained bags, so no bag may contain itself
+fn check_cycles(buffer: &str) -> Result<(), Error> {
+    let rules: FxHashMap<&str, Vec<&str>> = buffer
+        .lines()
+        .map(|line| {
+            let (container, contained) = rule_colors(line);
+            (container, contained.collect())
+        })
+        .collect();
+    let mut visiting = FxHashMap::default();
+    match buffer
+        .lines()
+        .find_map(|line| find_cycle(rule_colors(line).0, &rules, &mut visiting))
+    {
+        Some(bag) => Err(Error::parse(
+            buffer,
+            bag,
+            format!("bag '{}' contains itself", bag),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn add_to_graph(s: &str, bag_graph: &mut FxHashMap<u32, Node>) -> Result<(), Error> {
     let mut input = s.splitn(2, " bags contain ");
-    let container_str = input.next();
-    let contents = input.next().unwrap();
+    let container_str = error::next(s, &mut input, "bag color")?;
+    let contents = error::next(s, &mut input, "' bags contain '")?;
     // Continue only if contents aren't 'no other bags'
-    if contents.as_bytes()[0] == b'n' {
-        return;
+    if contents == "no other bags." {
+        return Ok(());
     }
-    let container_key = str_to_key(container_str.unwrap()).expect("failed to create key");
+    let container_key = str_to_key(container_str);
     let mut contains = vec![];
     // Containing bags
     for line in contents.split(", ") {
-        let number = line.chars().nth(0).unwrap().to_digit(10).unwrap() as usize; // Number is always one digit
-        let contained_key =
-            str_to_key(line[2..].splitn(2, " bag").next().unwrap()).expect("failed to create key");
+        let (number, bag) = match line.split_once(' ') {
+            Some((number, bag)) if bag.contains(" bag") => (number, bag),
+            _ => {
+                return Err(Error::parse(
+                    s,
+                    line,
+                    format!("invalid contents '{}'", line),
+                ))
+            }
+        };
+        let number = error::number(s, number)?;
+        let contained_key = str_to_key(bag.split(" bag").next().unwrap());
         bag_graph
             .entry(contained_key)
             .or_insert(Node::new())
@@ -73,26 +164,20 @@ fn add_to_graph(s: &str, bag_graph: &mut FxHashMap<u32, Node>) {
         .entry(container_key)
         .or_insert(Node::new())
         .contains = contains;
+    Ok(())
 }
 
 // -----------------------------------------------------------------------------
 // Part 1
 // -----------------------------------------------------------------------------
-lazy_static! {
-    static ref UNIQUES: RwLock<FxHashSet<u32>> = RwLock::new(
-        FxHashSet::<u32>::with_capacity_and_hasher(CAPACITY, Default::default())
-    );
-}
-
-fn part_1(key: u32, bag_graph: &FxHashMap<u32, Node>) -> usize {
-    if !UNIQUES.write().unwrap().insert(key.clone()) {
+fn part_1(key: u32, bag_graph: &FxHashMap<u32, Node>, uniques: &mut FxHashSet<u32>) -> usize {
+    if !uniques.insert(key) {
         return 0;
     };
     match bag_graph.get(&key) {
-        Some(node) => node
-            .contained_by
-            .iter()
-            .fold(1, |acc, container| acc + part_1(*container, &bag_graph)),
+        Some(node) => node.contained_by.iter().fold(1, |acc, container| {
+            acc + part_1(*container, bag_graph, uniques)
+        }),
         None => 1,
     }
 }
@@ -100,74 +185,54 @@ fn part_1(key: u32, bag_graph: &FxHashMap<u32, Node>) -> usize {
 // -----------------------------------------------------------------------------
 // Part 2
 // -----------------------------------------------------------------------------
-fn part_2(key: u32, bag_graph: &FxHashMap<u32, Node>) -> usize {
+// Bags inside of a bag and the bag itself; `None` on overflow
+fn part_2(key: u32, bag_graph: &FxHashMap<u32, Node>) -> Option<usize> {
     match bag_graph.get(&key) {
-        Some(node) => node
-            .contains
-            .iter()
-            .fold(1, |acc, bag| acc + bag.number * part_2(bag.key, &bag_graph)),
-        None => 1,
+        Some(node) => node.contains.iter().try_fold(1_usize, |acc, bag| {
+            acc.checked_add(bag.number.checked_mul(part_2(bag.key, bag_graph)?)?)
+        }),
+        None => Some(1),
     }
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day07.txt").unwrap();
-
-    // Read to graph
-    let mut bag_graph =
-        FxHashMap::<u32, Node>::with_capacity_and_hasher(CAPACITY, Default::default());
-    buffer
-        .lines()
-        .for_each(|line| add_to_graph(line, &mut bag_graph));
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find number of containing bags
-    let start_part_1 = Instant::now();
-    let count_1 = part_1(str_to_key("shiny gold").unwrap(), &bag_graph) as i64 - 1;
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find number of contained bags
-    let start_part_2 = Instant::now();
-    let count_2 = part_2(str_to_key("shiny gold").unwrap(), &bag_graph) - 1;
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
-}
+/// Day 7, Handy Haversacks
+pub struct Day07;
+
+impl Day for Day07 {
+    const NUMBER: usize = 7;
+    const NAME: &'static str = "Handy Haversacks";
+    const LABELS: [&'static str; 2] = ["🧳 Containing", "🧳 Contains"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = FxHashMap<u32, Node>;
+
+    fn check(buffer: &str) -> Result<(), Error> {
+        check_keys(buffer)?;
+        check_cycles(buffer)
+    }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(7, "Handy Haversacks");
-    output::print_part(1, "🧳 Containing", &format!("{}", results.part_1));
-    output::print_part(2, "🧳 Contains", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to graph
+        let mut bag_graph =
+            FxHashMap::<u32, Node>::with_capacity_and_hasher(CAPACITY, Default::default());
+        for line in buffer.lines() {
+            add_to_graph(line, &mut bag_graph).map_err(|error| error.within(buffer, line))?;
+        }
+        Ok(bag_graph)
+    }
+
+    fn part_1(bag_graph: &mut Self::Data) -> Answer {
+        // Find number of containing bags
+        let mut uniques = FxHashSet::<u32>::with_capacity_and_hasher(CAPACITY, Default::default());
+        (part_1(str_to_key("shiny gold"), bag_graph, &mut uniques) as i64 - 1).into()
+    }
+
+    fn part_2(bag_graph: &mut Self::Data) -> Answer {
+        // Find number of contained bags
+        part_2(str_to_key("shiny gold"), bag_graph).map_or(Answer::None, |bags| (bags - 1).into())
+    }
 }
 
 // -----------------------------------------------------------------------------
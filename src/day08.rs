@@ -6,6 +6,7 @@
 //! Note that this is very messy code that should be cleaned up.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 const CAPACITY: usize = 512;
@@ -20,8 +21,9 @@ enum Instruction {
     Nop,
 }
 
+/// Instruction in the boot code, keyed by position
 #[derive(Debug)]
-struct Node {
+pub struct Node {
     instruction: Instruction,
     value: i32,
     increment: i32,
@@ -29,34 +31,41 @@ struct Node {
 }
 
 impl Node {
-    fn new(s: &str, i: i32) -> Result<Self, std::num::ParseIntError> {
+    fn new(s: &str, i: i32) -> Result<Self, Error> {
         // Instructions of the form
         //   0   4
         //   acc value
         //   jmp value
         //   nop value
-        let instruction_char = s.as_bytes()[0];
-        let value = s[4..].parse::<i32>()?;
-        match instruction_char {
-            b'a' => Ok(Self {
+        let (instruction, value) = s
+            .split_once(' ')
+            .ok_or_else(|| Error::truncated(s, "instruction and value"))?;
+        let value = error::number::<i32>(s, value)?;
+        match instruction {
+            "acc" => Ok(Self {
                 instruction: Instruction::Acc,
-                value: value,
+                value,
                 increment: value,
                 next: i + 1,
             }),
-            b'j' => Ok(Self {
+            "jmp" if i + value >= 0 => Ok(Self {
                 instruction: Instruction::Jmp,
-                value: value,
+                value,
                 increment: 0,
                 next: i + value,
             }),
-            b'n' => Ok(Self {
+            "jmp" => Err(Error::parse(s, s, "jump before first instruction")),
+            "nop" => Ok(Self {
                 instruction: Instruction::Nop,
-                value: value,
+                value,
                 increment: 0,
                 next: i + 1,
             }),
-            _ => panic!("instruction not found"),
+            _ => Err(Error::parse(
+                s,
+                instruction,
+                format!("unknown instruction '{}'", instruction),
+            )),
         }
     }
 }
@@ -82,8 +91,8 @@ fn part_1(
     let mut executed = previous_executed.clone();
     let mut count = 0;
     let mut current = start;
-    // Iterate until repeat or out of bounds
-    while executed.insert(current) && (current < number_instructions) {
+    // Iterate until repeat or out of bounds; a patched nop can jump before the start
+    while current >= 0 && executed.insert(current) && (current < number_instructions) {
         let node = instructions.get(&current).unwrap();
         current = node.next;
         count += node.increment;
@@ -94,53 +103,54 @@ fn part_1(
 // -----------------------------------------------------------------------------
 // Part 2
 // -----------------------------------------------------------------------------
-fn part_2(number_instructions: i32, instructions: &FxHashMap<i32, Node>) -> i32 {
+fn part_2(number_instructions: i32, instructions: &FxHashMap<i32, Node>) -> Option<i32> {
     let mut machine = Vec::<State>::with_capacity(CAPACITY);
     let mut executed = FxHashSet::<i32>::with_capacity_and_hasher(CAPACITY, Default::default());
     let mut count = 0;
     let mut current = 0;
-    while executed.insert(current) {
+    let trace = spans::enter("trace");
+    while current < number_instructions && executed.insert(current) {
         machine.push(State { count, current });
         let node = instructions.get(&current).unwrap();
         current = node.next;
         count += node.increment;
     }
-    machine
-        .iter()
-        .rev()
-        .find_map(|state| {
-            let node = instructions.get(&state.current).unwrap();
-            match node.instruction {
-                Instruction::Acc => None,
-                Instruction::Jmp => {
-                    let (terminated, updated_count) = part_1(
-                        state.current + 1,
-                        number_instructions,
-                        &instructions,
-                        &executed,
-                    );
-                    if terminated {
-                        Some(state.count + updated_count)
-                    } else {
-                        None
-                    }
+    drop(trace);
+    let _backtrack = spans::enter("backtrack");
+    machine.iter().rev().find_map(|state| {
+        let node = instructions.get(&state.current).unwrap();
+        match node.instruction {
+            Instruction::Acc => None,
+            Instruction::Jmp => {
+                let _patch = spans::enter("patched run");
+                let (terminated, updated_count) = part_1(
+                    state.current + 1,
+                    number_instructions,
+                    instructions,
+                    &executed,
+                );
+                if terminated {
+                    Some(state.count + updated_count)
+                } else {
+                    None
                 }
-                Instruction::Nop => {
-                    let (terminated, updated_count) = part_1(
-                        state.current + node.value,
-                        number_instructions,
-                        &instructions,
-                        &executed,
-                    );
-                    if terminated {
-                        Some(state.count + updated_count)
-                    } else {
-                        None
-                    }
+            }
+            Instruction::Nop => {
+                let _patch = spans::enter("patched run");
+                let (terminated, updated_count) = part_1(
+                    state.current + node.value,
+                    number_instructions,
+                    instructions,
+                    &executed,
+                );
+                if terminated {
+                    Some(state.count + updated_count)
+                } else {
+                    None
                 }
             }
-        })
-        .unwrap()
+        }
+    })
 }
 
 // -----------------------------------------------------------------------------
@@ -154,8 +164,13 @@ fn run_program(
 ) -> (bool, i32) {
     let mut count = 0;
     let mut current = start;
-    // Iterate until repeat or out of bounds
-    while !executed.contains(&current) && (current < number_instructions) {
+    // Iterate until repeat or out of bounds, which includes loops the patch creates
+    let mut patched = FxHashSet::<i32>::default();
+    while current >= 0
+        && !executed.contains(&current)
+        && (current < number_instructions)
+        && patched.insert(current)
+    {
         let node = instructions.get(&current).unwrap();
         current = node.next;
         count += node.increment;
@@ -163,142 +178,107 @@ fn run_program(
     (current >= number_instructions, count)
 }
 
-fn combined(number_instructions: i32, instructions: &FxHashMap<i32, Node>) -> (i32, i32) {
+fn combined(number_instructions: i32, instructions: &FxHashMap<i32, Node>) -> (i32, Option<i32>) {
     let mut machine = Vec::<State>::with_capacity(CAPACITY);
     let mut executed = FxHashSet::<i32>::with_capacity_and_hasher(CAPACITY, Default::default());
     let mut count = 0;
     let mut current = 0;
-    while executed.insert(current) {
+    while current < number_instructions && executed.insert(current) {
         machine.push(State { count, current });
         let node = instructions.get(&current).unwrap();
         current = node.next;
         count += node.increment;
     }
     let count_1 = count;
-    let count_2 = machine
-        .iter()
-        .rev()
-        .find_map(|state| {
-            let node = instructions.get(&state.current).unwrap();
-            match node.instruction {
-                Instruction::Acc => None,
-                Instruction::Jmp => {
-                    let (terminated, updated_count) = run_program(
-                        state.current + 1,
-                        number_instructions,
-                        &instructions,
-                        &executed,
-                    );
-                    if terminated {
-                        Some(state.count + updated_count)
-                    } else {
-                        None
-                    }
+    let count_2 = machine.iter().rev().find_map(|state| {
+        let node = instructions.get(&state.current).unwrap();
+        match node.instruction {
+            Instruction::Acc => None,
+            Instruction::Jmp => {
+                let (terminated, updated_count) = run_program(
+                    state.current + 1,
+                    number_instructions,
+                    instructions,
+                    &executed,
+                );
+                if terminated {
+                    Some(state.count + updated_count)
+                } else {
+                    None
                 }
-                Instruction::Nop => {
-                    let (terminated, updated_count) = run_program(
-                        state.current + node.value,
-                        number_instructions,
-                        &instructions,
-                        &executed,
-                    );
-                    if terminated {
-                        Some(state.count + updated_count)
-                    } else {
-                        None
-                    }
+            }
+            Instruction::Nop => {
+                let (terminated, updated_count) = run_program(
+                    state.current + node.value,
+                    number_instructions,
+                    instructions,
+                    &executed,
+                );
+                if terminated {
+                    Some(state.count + updated_count)
+                } else {
+                    None
                 }
             }
-        })
-        .unwrap();
+        }
+    });
     (count_1, count_2)
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day08.txt").unwrap();
-
-    // Read to graph
-    let instructions: FxHashMap<i32, Node> = buffer
+fn parse_instructions(buffer: &str) -> Result<FxHashMap<i32, Node>, Error> {
+    buffer
         .lines()
         .enumerate()
         .map(|(i, line)| {
-            (
-                i as i32,
-                Node::new(line, i as i32).expect("failed to parse line"),
-            )
+            let node = Node::new(line, i as i32).map_err(|error| error.within(buffer, line))?;
+            Ok((i as i32, node))
         })
-        .collect();
-    let number_instructions = instructions.len() as i32;
-    let time_setup = start_setup.elapsed();
+        .collect()
+}
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find first repeated instruction
-    let start_part_1 = Instant::now();
-    let (_, count_1) = part_1(
-        0,
-        number_instructions,
-        &instructions,
-        &FxHashSet::<i32>::default(),
-    );
-    let time_part_1 = start_part_1.elapsed();
+/// Day 8, Handheld Halting
+pub struct Day08;
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find matching passwords
-    let start_part_2 = Instant::now();
-    let count_2 = part_2(number_instructions, &instructions);
-    let time_part_2 = start_part_2.elapsed();
+impl Day for Day08 {
+    const NUMBER: usize = 8;
+    const NAME: &'static str = "Handheld Halting";
+    const LABELS: [&'static str; 2] = ["🎮 Infinite", "🎮 Corrected"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = FxHashMap<i32, Node>;
 
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let instructions: FxHashMap<i32, Node> = buffer
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            (
-                i as i32,
-                Node::new(line, i as i32).expect("failed to parse line"),
-            )
-        })
-        .collect();
-    let number_instructions = instructions.len() as i32;
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to graph
+        parse_instructions(buffer)
+    }
 
-    let (combined_1, combined_2) = combined(number_instructions, &instructions);
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, count_1);
-    assert_eq!(combined_2, count_2);
+    fn part_1(instructions: &mut Self::Data) -> Answer {
+        // Find first repeated instruction
+        let (_, count_1) = part_1(
+            0,
+            instructions.len() as i32,
+            instructions,
+            &FxHashSet::<i32>::default(),
+        );
+        count_1.into()
+    }
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
-}
+    fn part_2(instructions: &mut Self::Data) -> Answer {
+        // Find corrected instruction
+        part_2(instructions.len() as i32, instructions).map_or(Answer::None, Answer::from)
+    }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(8, "Handheld Halting");
-    output::print_part(1, "🎮 Infinite", &format!("{}", results.part_1));
-    output::print_part(2, "🎮 Corrected", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let instructions = parse_instructions(buffer)?;
+        let number_instructions = instructions.len() as i32;
+        let (combined_1, combined_2) = combined(number_instructions, &instructions);
+        Ok(Some((
+            combined_1.into(),
+            combined_2.map_or(Answer::None, Answer::from),
+        )))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -5,6 +5,7 @@
 //! produce fast code.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // Constant
 const WINDOW: usize = 25;
@@ -13,120 +14,86 @@ const WINDOW: usize = 25;
 // Check for pair that sum to target in current range
 // -----------------------------------------------------------------------------
 #[inline(always)]
-fn find_two(target: &i64, values: &Vec<&i64>) -> Option<i64> {
+fn has_pair(target: i64, values: &[i64]) -> bool {
     values
         .iter()
-        .take(WINDOW)
         .enumerate()
-        .find_map(|(i, &value)| {
-            let search = target - value;
-            if values.iter().skip(i + 1).any(|&second| *second == search) {
-                Some(*value)
-            } else {
-                None
-            }
-        })
+        .any(|(i, &value)| values[i + 1..].contains(&(target - value)))
+}
+
+/// First value that is not the sum of two of the window values before it
+pub fn first_invalid(values: &[i64], window: usize) -> Option<i64> {
+    values
+        .windows(window + 1)
+        .find(|range| !has_pair(range[window], &range[..window]))
+        .map(|range| range[window])
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Find contiguous range that sums to target
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day09.txt").unwrap();
+/// Sum of the smallest and largest values in the contiguous range of at least two
+/// values that sums to target; values must be non-negative
+pub fn encryption_weakness(values: &[i64], target: i64) -> Option<i64> {
+    let (mut lower, mut sum) = (0, 0);
+    for upper in 0..values.len() {
+        sum += values[upper];
+        while sum > target && lower < upper {
+            sum -= values[lower];
+            lower += 1;
+        }
+        if sum == target && lower < upper {
+            let range = &values[lower..=upper];
+            return Some(range.iter().min()? + range.iter().max()?);
+        }
+    }
+    None
+}
 
-    // Read to vector
-    let values: Vec<i64> = buffer
-        .lines()
-        .map(|line| line.parse().expect("failed to parse line"))
-        .collect();
-    let time_setup = start_setup.elapsed();
+// -----------------------------------------------------------------------------
+// Day
+// -----------------------------------------------------------------------------
+/// XMAS encoded data stream
+#[derive(Debug)]
+pub struct Encoding {
+    values: Vec<i64>,
+    value_1: Option<i64>,
+}
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Look for pair
-    let start_part_1 = Instant::now();
-    let value_1 = values
-        .iter()
-        .skip(WINDOW)
-        .enumerate()
-        .find_map(|(i, &value)| {
-            match find_two(
-                &value,
-                &values.iter().skip(i).take(WINDOW).collect::<Vec<_>>(),
-            ) {
-                Some(_) => None,
-                None => Some(value),
-            }
-        })
-        .unwrap();
-    let time_part_1 = start_part_1.elapsed();
+/// Day 9, Encoding Error
+pub struct Day09;
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Look for range
-    let start_part_2 = Instant::now();
-    let (lower, upper) = values
-        .iter()
-        .skip(WINDOW)
-        .enumerate()
-        .find_map(|(i, &value)| {
-            let mut sum = value;
-            let (j, _) = values
-                .iter()
-                .skip(i + WINDOW + 1)
-                .enumerate()
-                .find(|(_, &next)| {
-                    sum += next;
-                    sum >= value_1
-                })
-                .unwrap();
-            if sum == value_1 {
-                Some((i, j))
-            } else {
-                None
-            }
+impl Day for Day09 {
+    const NUMBER: usize = 9;
+    const NAME: &'static str = "Encoding Error";
+    const LABELS: [&'static str; 2] = ["💾 Invalid", "💾 Sum"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Encoding;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let values: Vec<i64> = buffer
+            .lines()
+            .map(|line| error::number(buffer, line))
+            .collect::<Result<_, _>>()?;
+        Ok(Encoding {
+            values,
+            value_1: None,
         })
-        .unwrap();
-    let (min, max) = values
-        .iter()
-        .skip(WINDOW + lower)
-        .take(upper)
-        .fold((value_1, 0), |acc, &value| {
-            (std::cmp::min(acc.0, value), std::cmp::max(acc.1, value))
-        });
-    let sum_2 = min + max;
-    let time_part_2 = start_part_2.elapsed();
+    }
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        value_1 as i64,
-        sum_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
-}
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Look for pair
+        data.value_1 = first_invalid(&data.values, WINDOW);
+        data.value_1.map_or(Answer::None, Answer::from)
+    }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(9, "Encoding Error");
-    output::print_part(1, "💾 Invalid", &format!("{}", results.part_1));
-    output::print_part(2, "💾 Sum", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Look for range
+        data.value_1
+            .and_then(|value_1| encryption_weakness(&data.values, value_1))
+            .map_or(Answer::None, Answer::from)
+    }
 }
 
 // -----------------------------------------------------------------------------
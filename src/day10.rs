@@ -5,88 +5,90 @@
 //! cannot be combined.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day10.txt").unwrap();
+/// Joltage adapters in the bag
+#[derive(Debug)]
+pub struct Adapters {
+    mask: Vec<usize>,
+    max: usize,
+}
 
-    // Read to vector
-    let mut max = 0;
-    let values: Vec<usize> = buffer
-        .lines()
-        .map(|line| {
-            let value = line.parse().expect("failed to parse line");
-            max = std::cmp::max(max, value);
-            value
-        })
-        .collect();
+/// Day 10, Adapter Array
+pub struct Day10;
 
-    // Collect into mask array
-    let mut mask: Vec<usize> = vec![0; max + 1];
-    mask[0] = 1;
-    values.iter().for_each(|&value| mask[value] = 1);
-    let time_setup = start_setup.elapsed();
+impl Day for Day10 {
+    const NUMBER: usize = 10;
+    const NAME: &'static str = "Adapter Array";
+    const LABELS: [&'static str; 2] = ["🔌 Product", "🔌 Combinations"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Adapters;
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Count gaps
-    let start_part_1 = Instant::now();
-    let mut count_1 = [0, 0, 1];
-    let mut last = 0;
-    mask.iter().enumerate().skip(1).for_each(|(i, &value)| {
-        if value > 0 {
-            count_1[i - last - 1] += 1;
-            last = i;
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let (mut max, mut max_line) = (0, "");
+        let values: Vec<usize> = buffer
+            .lines()
+            .map(|line| {
+                let value = error::number(buffer, line)?;
+                if value > max {
+                    max = value;
+                    max_line = line;
+                }
+                Ok(value)
+            })
+            .collect::<Result<_, Error>>()?;
+        // Steps are at most 3 jolts, which also bounds the size of the mask
+        if max > 3 * values.len() {
+            return Err(Error::parse(
+                buffer,
+                max_line,
+                format!(
+                    "joltage {} is out of reach of {} adapters",
+                    max,
+                    values.len()
+                ),
+            ));
         }
-    });
-    let product_1 = count_1[0] * count_1[2];
-    let time_part_1 = start_part_1.elapsed();
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find number of possible paths
-    let start_part_2 = Instant::now();
-    mask.append(&mut vec![0, 0, 1]);
-    (0..max).rev().for_each(|i| {
-        if mask[i] > 0 {
-            mask[i] = mask[i + 1] + mask[i + 2] + mask[i + 3]
-        }
-    });
-    let count_2 = mask[0];
-    let time_part_2 = start_part_2.elapsed();
+        // Collect into mask array
+        let mut mask: Vec<usize> = vec![0; max + 1];
+        mask[0] = 1;
+        values.iter().for_each(|&value| mask[value] = 1);
+        Ok(Adapters { mask, max })
+    }
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        product_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
-}
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Count gaps
+        let mut count_1 = [0, 0, 1];
+        let mut last = 0;
+        for (i, &value) in data.mask.iter().enumerate().skip(1) {
+            if value > 0 {
+                // Gaps larger than 3 leave the chain broken
+                match count_1.get_mut(i - last - 1) {
+                    Some(count) => *count += 1,
+                    None => return Answer::None,
+                }
+                last = i;
+            }
+        }
+        (count_1[0] * count_1[2]).into()
+    }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(10, "Adapter Array");
-    output::print_part(1, "🔌 Product", &format!("{}", results.part_1));
-    output::print_part(2, "🔌 Combinations", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find number of possible paths
+        let mask = &mut data.mask;
+        mask.append(&mut vec![0, 0, 1]);
+        (0..data.max).rev().for_each(|i| {
+            if mask[i] > 0 {
+                mask[i] = mask[i + 1] + mask[i + 2] + mask[i + 3]
+            }
+        });
+        mask[0].into()
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -7,6 +7,7 @@
 //! to vectorize.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use arrayvec::ArrayVec;
 
 // Constants
@@ -15,14 +16,17 @@ const CAPACITY: usize = 16_384;
 // -----------------------------------------------------------------------------
 // Game of Life
 // -----------------------------------------------------------------------------
+// False if the seats never settle; the list of seats to recheck only shrinks, so the
+// same seats changing in two rounds running means they flip back and forth forever
 #[inline]
 fn game_of_life(
     seats: &mut ArrayVec<[u8; CAPACITY]>,
     check_seats: &mut Vec<u16>,
     neighbors_bool: &[bool; 256],
     check_neighbors: &ArrayVec<[[u16; 8]; CAPACITY]>,
-) {
+) -> bool {
     let mut repeat = true;
+    let mut changes = usize::MAX;
     while repeat {
         check_seats.retain(|&i| {
             // Check seats for change
@@ -30,20 +34,20 @@ fn game_of_life(
                 .iter()
                 .map(|&index| seats[index as usize] % 2)
                 .fold(0, |acc, value| (acc << 1) | value);
-            if (seats[i as usize] == 0 && count == 0)
+            (seats[i as usize] == 0 && count == 0)
                 || (seats[i as usize] == 1 && neighbors_bool[count as usize])
-            {
-                true
-            } else {
-                false
-            }
         });
         // Apply changes
         check_seats.iter().for_each(|&index| {
             seats[index as usize] = (seats[index as usize] + 1) % 2;
         });
-        repeat = check_seats.len() > 0;
+        if check_seats.len() == changes {
+            return false;
+        }
+        changes = check_seats.len();
+        repeat = !check_seats.is_empty();
     }
+    true
 }
 
 // -----------------------------------------------------------------------------
@@ -144,100 +148,127 @@ fn part_2(
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day11.txt").unwrap();
-
-    // Read to vector
-    let row_length = buffer.lines().nth(0).unwrap().chars().count() as u16 + 2;
-    let number_rows = buffer.lines().count() as u16 + 2;
-    let mut seats: ArrayVec<[u8; CAPACITY]> = ArrayVec::from([0; CAPACITY]);
-    buffer.lines().enumerate().for_each(|(i, line)| {
-        line.chars().enumerate().for_each(|(j, c)| {
-            seats[(i + 1) * row_length as usize + j + 1] = if c == 'L' { 1 } else { 2 }
-        })
-    });
-    let mut check_seats = seats
-        .iter()
-        .enumerate()
-        .filter_map(|(i, &value)| if value == 1 { Some(i as u16) } else { None })
-        .collect::<Vec<u16>>();
-
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find stable configuration
-    let start_part_1 = Instant::now();
-
-    // Neighbors to check
-    let check_neighbors = part_1(row_length, number_rows);
-
-    // Run Game of Life
-    game_of_life(&mut seats, &mut check_seats, &NEIGHBORS_1, &check_neighbors);
-    let count_1 = seats.iter().filter(|&s| *s == 1).count();
+/// Seat layout of the waiting area
+#[derive(Debug)]
+pub struct SeatMap {
+    seats: ArrayVec<[u8; CAPACITY]>,
+    check_seats: Vec<u16>,
+    row_length: u16,
+    number_rows: u16,
+}
 
-    let time_part_1 = start_part_1.elapsed();
+/// Day 11, Seating System
+pub struct Day11;
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Revised seat rules
-    let start_part_2 = Instant::now();
+impl Day for Day11 {
+    const NUMBER: usize = 11;
+    const NAME: &'static str = "Seating System";
+    const LABELS: [&'static str; 2] = ["⛴ Occupied", "⛴ Occupied"];
+    const BUDGET: Budget = Budget::millis(5, 10, 20);
+    type Data = SeatMap;
 
-    // Reset
-    (1..number_rows as usize - 1).for_each(|i| {
-        (1..row_length as usize - 1).for_each(|j| {
-            if seats[i * row_length as usize + j] == 0 {
-                seats[i * row_length as usize + j] = 1;
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let first = buffer
+            .lines()
+            .next()
+            .ok_or_else(|| Error::truncated(buffer, "at least one row"))?;
+        let row_length = first.len() + 2;
+        let number_rows = buffer.lines().count() + 2;
+        if row_length * number_rows > CAPACITY {
+            return Err(Error::parse(
+                buffer,
+                buffer,
+                format!("seat map is larger than {} seats", CAPACITY),
+            ));
+        }
+        let mut seats: ArrayVec<[u8; CAPACITY]> = ArrayVec::from([0; CAPACITY]);
+        for (i, line) in buffer.lines().enumerate() {
+            if line.len() != row_length - 2 {
+                return Err(Error::parse(
+                    buffer,
+                    line,
+                    format!(
+                        "expected row of length {}, found {}",
+                        row_length - 2,
+                        line.len()
+                    ),
+                ));
+            }
+            for (j, c) in line.bytes().enumerate() {
+                seats[(i + 1) * row_length + j + 1] = match c {
+                    b'L' => 1,
+                    b'.' => 2,
+                    _ => {
+                        return Err(Error::parse(
+                            buffer,
+                            &line[j..],
+                            format!("unexpected character '{}'", c as char),
+                        ))
+                    }
+                }
             }
+        }
+        let check_seats = seats
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &value)| if value == 1 { Some(i as u16) } else { None })
+            .collect::<Vec<u16>>();
+        Ok(SeatMap {
+            seats,
+            check_seats,
+            row_length: row_length as u16,
+            number_rows: number_rows as u16,
         })
-    });
-    let mut check_seats = seats
-        .iter()
-        .enumerate()
-        .filter_map(|(i, &value)| if value == 1 { Some(i as u16) } else { None })
-        .collect::<Vec<u16>>();
+    }
 
-    // Neighbors to check
-    let check_neighbors = part_2(&seats, row_length, number_rows);
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find stable configuration
+        // Neighbors to check
+        let check_neighbors = part_1(data.row_length, data.number_rows);
 
-    // Run Game of Life
-    game_of_life(&mut seats, &mut check_seats, &NEIGHBORS_2, &check_neighbors);
-    let count_2 = seats.iter().filter(|&s| *s == 1).count();
+        // Run Game of Life
+        if !game_of_life(
+            &mut data.seats,
+            &mut data.check_seats,
+            &NEIGHBORS_1,
+            &check_neighbors,
+        ) {
+            return Answer::None;
+        }
+        data.seats.iter().filter(|&s| *s == 1).count().into()
+    }
 
-    let time_part_2 = start_part_2.elapsed();
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Revised seat rules
+        let (row_length, number_rows) = (data.row_length as usize, data.number_rows as usize);
+        let seats = &mut data.seats;
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
-}
+        // Reset
+        (1..number_rows - 1).for_each(|i| {
+            (1..row_length - 1).for_each(|j| {
+                if seats[i * row_length + j] == 0 {
+                    seats[i * row_length + j] = 1;
+                }
+            })
+        });
+        let mut check_seats = seats
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &value)| if value == 1 { Some(i as u16) } else { None })
+            .collect::<Vec<u16>>();
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(11, "Seating System");
-    output::print_part(1, "⛴ Occupied", &format!("{}", results.part_1));
-    output::print_part(2, "⛴ Occupied", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+        // Neighbors to check
+        let check_neighbors = part_2(seats, data.row_length, data.number_rows);
+
+        // Run Game of Life
+        if !game_of_life(seats, &mut check_seats, &NEIGHBORS_2, &check_neighbors) {
+            return Answer::None;
+        }
+        seats.iter().filter(|&s| *s == 1).count().into()
+    }
 }
 
 // -----------------------------------------------------------------------------
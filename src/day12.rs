@@ -4,12 +4,14 @@
 //! directly handle the rotation matrix effects in part 2.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // -----------------------------------------------------------------------------
 // Read line to array
 // -----------------------------------------------------------------------------
+/// Navigation instruction for the ferry
 #[derive(Debug)]
-struct Instruction {
+pub struct Instruction {
     north: i16,
     east: i16,
     bearing: i16,
@@ -17,19 +19,35 @@ struct Instruction {
 }
 
 impl std::str::FromStr for Instruction {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = s[1..].parse::<i16>()?;
+        if !s.is_char_boundary(1) {
+            return Err(Error::truncated(s, "instruction"));
+        }
+        let value = error::number::<i16>(s, &s[1..])?;
         let (mut north, mut east, mut bearing, mut forward) = (0, 0, 0, 0);
         match s.as_bytes()[0] {
             b'N' => north = value,
             b'S' => north = -value,
             b'E' => east = value,
             b'W' => east = -value,
+            b'L' | b'R' if ![90, 180, 270].contains(&value) => {
+                return Err(Error::parse(
+                    s,
+                    &s[1..],
+                    format!("unsupported rotation {}", value),
+                ))
+            }
             b'L' => bearing = value,
             b'R' => bearing = 360 - value,
             b'F' => forward = value,
-            _ => panic!("unknown instruction"),
+            _ => {
+                return Err(Error::parse(
+                    s,
+                    s,
+                    format!("unknown instruction '{}'", &s[0..1]),
+                ))
+            }
         }
         Ok(Self {
             north,
@@ -125,105 +143,81 @@ fn part_2(current: &PositionWaypoint, instruction: &Instruction) -> PositionWayp
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day12.txt").unwrap();
-
-    // Read to vector
-    let values: Vec<Instruction> = buffer
-        .lines()
-        .map(|line| line.parse::<Instruction>().expect("failed to parse line"))
-        .collect();
-    let time_setup = start_setup.elapsed();
+/// Day 12, Rain Risk
+pub struct Day12;
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Move in directions given
-    let start_part_1 = Instant::now();
-    let position_1 = values.iter().fold(
-        Position {
-            north: 0,
-            east: 0,
-            bearing: 0,
-        },
-        |acc, instruction| part_1(&acc, instruction),
-    );
-    let distance_1 = position_1.north.abs() + position_1.east.abs();
-    let time_part_1 = start_part_1.elapsed();
+impl Day for Day12 {
+    const NUMBER: usize = 12;
+    const NAME: &'static str = "Rain Risk";
+    const LABELS: [&'static str; 2] = ["🧭 Distance", "🧭 Distance"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Vec<Instruction>;
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Move towards waypoint
-    let start_part_2 = Instant::now();
-    let position_2 = values.iter().fold(
-        PositionWaypoint {
-            north: 0,
-            east: 0,
-            waypoint_north: 1,
-            waypoint_east: 10,
-        },
-        |acc, instruction| part_2(&acc, instruction),
-    );
-    let distance_2 = position_2.north.abs() + position_2.east.abs();
-    let time_part_2 = start_part_2.elapsed();
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        error::lines(buffer)
+    }
 
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let (result_1, result_2) = buffer
-        .lines()
-        .map(|line| line.parse::<Instruction>().expect("failed to parse line"))
-        .fold(
-            (
-                Position {
-                    north: 0,
-                    east: 0,
-                    bearing: 0,
-                },
-                PositionWaypoint {
-                    north: 0,
-                    east: 0,
-                    waypoint_north: 1,
-                    waypoint_east: 10,
-                },
-            ),
-            |acc, instruction| (part_1(&acc.0, &instruction), part_2(&acc.1, &instruction)),
+    fn part_1(values: &mut Self::Data) -> Answer {
+        // Move in directions given
+        let position_1 = values.iter().fold(
+            Position {
+                north: 0,
+                east: 0,
+                bearing: 0,
+            },
+            |acc, instruction| part_1(&acc, instruction),
         );
-    let (combined_1, combined_2) = (
-        result_1.north.abs() + result_1.east.abs(),
-        result_2.north.abs() + result_2.east.abs(),
-    );
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, distance_1);
-    assert_eq!(combined_2, distance_2);
+        (position_1.north.abs() + position_1.east.abs()).into()
+    }
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        distance_1 as i64,
-        distance_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
-}
+    fn part_2(values: &mut Self::Data) -> Answer {
+        // Move towards waypoint
+        let position_2 = values.iter().fold(
+            PositionWaypoint {
+                north: 0,
+                east: 0,
+                waypoint_north: 1,
+                waypoint_east: 10,
+            },
+            |acc, instruction| part_2(&acc, instruction),
+        );
+        (position_2.north.abs() + position_2.east.abs()).into()
+    }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(12, "Rain Risk");
-    output::print_part(1, "🧭 Distance", &format!("{}", results.part_1));
-    output::print_part(2, "🧭 Distance", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let (result_1, result_2) = buffer
+            .lines()
+            .map(|line| {
+                line.parse::<Instruction>()
+                    .map_err(|error| error.within(buffer, line))
+            })
+            .try_fold(
+                (
+                    Position {
+                        north: 0,
+                        east: 0,
+                        bearing: 0,
+                    },
+                    PositionWaypoint {
+                        north: 0,
+                        east: 0,
+                        waypoint_north: 1,
+                        waypoint_east: 10,
+                    },
+                ),
+                |acc, instruction| {
+                    let instruction = instruction?;
+                    Ok::<_, Error>((part_1(&acc.0, &instruction), part_2(&acc.1, &instruction)))
+                },
+            )?;
+        Ok(Some((
+            (result_1.north.abs() + result_1.east.abs()).into(),
+            (result_2.north.abs() + result_2.east.abs()).into(),
+        )))
+    }
 }
 
 // -----------------------------------------------------------------------------
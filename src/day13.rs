@@ -3,6 +3,7 @@
 //! arithmetic. Nothing particularly noteworthy in the code.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // -----------------------------------------------------------------------------
 // Bus object
@@ -16,9 +17,21 @@ struct Bus {
 // -----------------------------------------------------------------------------
 // Extended Euclidean algorithm
 // -----------------------------------------------------------------------------
-fn euclid_inverse(mut a: i64, mut b: i64) -> usize {
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Inverse of a modulo b; `None` if they share a factor
+fn euclid_inverse(mut a: i64, mut b: i64) -> Option<usize> {
     if b == 1 {
-        return 1;
+        return Some(1);
+    }
+    if a == 0 {
+        return None;
     }
     let b0 = b;
     let mut t;
@@ -26,6 +39,9 @@ fn euclid_inverse(mut a: i64, mut b: i64) -> usize {
     let mut x0 = 0;
     let mut x1 = 1;
     while a > 1 {
+        if b == 0 {
+            return None;
+        }
         q = a / b;
         t = b;
         b = a % b;
@@ -37,102 +53,141 @@ fn euclid_inverse(mut a: i64, mut b: i64) -> usize {
     if x1 < 0 {
         x1 += b0;
     }
-    x1 as usize
+    Some(x1 as usize)
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Chinese Remainder Theorem
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day13.txt").unwrap();
-
-    // Read to vector
-    let earliest: usize = buffer
-        .lines()
-        .nth(0)
-        .unwrap()
-        .parse()
-        .expect("failed to parse line");
-    let values: Vec<Bus> = buffer
-        .lines()
-        .nth(1)
-        .unwrap()
-        .split(",")
-        .enumerate()
-        .filter(|(_, entry)| *entry != "x")
-        .map(|(i, entry)| {
-            let value = entry.parse().expect("failed to parse line");
-            Bus {
-                id: value,
-                time: value - (i % value),
-            }
-        })
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find first bus
-    let start_part_1 = Instant::now();
-    let (bus_1, minutes_1) = values
+/// Smallest non-negative x with x = residue (mod modulus) for each (residue, modulus)
+/// pair; `None` if the moduli are not pairwise coprime, or their product overflows
+pub fn chinese_remainder(congruences: &[(usize, usize)]) -> Option<usize> {
+    let m = congruences
         .iter()
-        .map(|bus| (bus.id, bus.id - (earliest % bus.id)))
-        .fold((earliest, earliest), |acc, time| {
-            if time.1 < acc.1 {
-                time
-            } else {
-                acc
-            }
-        });
-    let product_1 = bus_1 * minutes_1;
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find bus sequence
-    let start_part_2 = Instant::now();
-    let m = values.iter().fold(1, |acc, bus| acc * bus.id);
-    let departure_2: usize = values
+        .try_fold(1_usize, |acc, &(_, modulus)| acc.checked_mul(modulus))?;
+    if m == 0 {
+        return None;
+    }
+    congruences
         .iter()
-        .skip(1)
-        .map(|bus| {
-            let m_i = m / bus.id;
-            (bus.time * euclid_inverse(m_i as i64, bus.id as i64) * m_i) % m
+        .try_fold(0_u128, |sum, &(residue, modulus)| {
+            let m_i = m / modulus;
+            let inverse = euclid_inverse((m_i % modulus) as i64, modulus as i64)?;
+            let term = (residue % modulus) as u128 * inverse as u128 % modulus as u128;
+            Some((sum + term * m_i as u128) % m as u128)
         })
-        .sum::<usize>()
-        % m;
-    let time_part_2 = start_part_2.elapsed();
+        .map(|x| x as usize)
+}
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        product_1 as i64,
-        departure_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+// -----------------------------------------------------------------------------
+// Check bus IDs
+// -----------------------------------------------------------------------------
+// The sequence is found with the Chinese Remainder Theorem, which needs the bus IDs to
+// be pairwise coprime
+fn check_coprime(buffer: &str) -> Result<(), Error> {
+    let line = match buffer.lines().nth(1) {
+        Some(line) => line,
+        None => return Ok(()),
+    };
+    let mut buses: Vec<(usize, &str)> = vec![];
+    for entry in line.split(',') {
+        let id = match entry.parse::<usize>() {
+            Ok(id) if id > 0 => id,
+            _ => continue,
+        };
+        if let Some(&(other, _)) = buses.iter().find(|&&(other, _)| gcd(id, other) > 1) {
+            return Err(Error::parse(
+                buffer,
+                entry,
+                format!(
+                    "bus IDs {} and {} share a factor of {}",
+                    other,
+                    id,
+                    gcd(id, other)
+                ),
+            ));
+        }
+        buses.push((id, entry));
+    }
+    Ok(())
 }
 
 // -----------------------------------------------------------------------------
-// Report
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(13, "Shuttle Search");
-    output::print_part(1, "🚌 Product", &format!("{}", results.part_1));
-    output::print_part(2, "🚌 Sequence", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Earliest departure time and bus schedule
+#[derive(Debug)]
+pub struct Schedule {
+    earliest: usize,
+    buses: Vec<Bus>,
+}
+
+/// Day 13, Shuttle Search
+pub struct Day13;
+
+impl Day for Day13 {
+    const NUMBER: usize = 13;
+    const NAME: &'static str = "Shuttle Search";
+    const LABELS: [&'static str; 2] = ["🚌 Product", "🚌 Sequence"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Schedule;
+
+    fn check(buffer: &str) -> Result<(), Error> {
+        check_coprime(buffer)
+    }
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let mut lines = buffer.lines();
+        let earliest: usize =
+            error::number(buffer, error::next(buffer, &mut lines, "earliest time")?)?;
+        let buses: Vec<Bus> = error::next(buffer, &mut lines, "bus schedule")?
+            .split(',')
+            .enumerate()
+            .filter(|(_, entry)| *entry != "x")
+            .map(|(i, entry)| {
+                let value = error::number(buffer, entry)?;
+                if value == 0 {
+                    return Err(Error::parse(buffer, entry, "bus ID must be positive"));
+                }
+                Ok(Bus {
+                    id: value,
+                    time: value - (i % value),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        if buses.is_empty() {
+            return Err(Error::truncated(buffer, "at least one bus"));
+        }
+        Ok(Schedule { earliest, buses })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find first bus
+        let earliest = data.earliest;
+        let (bus_1, minutes_1) = data
+            .buses
+            .iter()
+            .map(|bus| (bus.id, bus.id - (earliest % bus.id)))
+            .fold((earliest, earliest), |acc, time| {
+                if time.1 < acc.1 {
+                    time
+                } else {
+                    acc
+                }
+            });
+        (bus_1 * minutes_1).into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find bus sequence
+        let congruences: Vec<(usize, usize)> = data
+            .buses
+            .iter()
+            .map(|bus| (bus.time % bus.id, bus.id))
+            .collect();
+        chinese_remainder(&congruences).map_or(Answer::None, Answer::from)
+    }
 }
 
 // -----------------------------------------------------------------------------
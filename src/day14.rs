@@ -5,13 +5,14 @@
 //! visiting the instructions in reverse, to avoid repetition in traversing the map.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use arrayvec::ArrayVec;
 use rustc_hash::FxHashSet;
 
 // Constants
 const CAPACITY: usize = 4096;
 const INSTRUCTIONS: usize = 8;
-const MAX_36_BITS: u64 = u64::max_value() >> (64 - 36);
+const MAX_36_BITS: u64 = u64::MAX >> (64 - 36);
 
 // -----------------------------------------------------------------------------
 // Instructions
@@ -24,28 +25,45 @@ struct Instructions {
 }
 
 impl std::str::FromStr for Instructions {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut data = s.lines();
         // Masks
         let mut set_mask = 0;
         let mut clear_mask = 0;
-        data.next()
-            .unwrap()
-            .as_bytes()
-            .iter()
-            .enumerate()
-            .for_each(|(i, &b)| match b {
+        let mask = error::next(s, &mut data, "mask")?;
+        if mask.len() != 36 {
+            return Err(Error::parse(
+                s,
+                mask,
+                format!("expected 36 bit mask, found {} bits", mask.len()),
+            ));
+        }
+        for (i, &b) in mask.as_bytes().iter().enumerate() {
+            match b {
                 b'1' => set_mask |= 1 << (35 - i),
                 b'0' => clear_mask |= 1 << (35 - i),
                 b'X' => (), // Inferred from other masks
-                _ => panic!("invalid bit"),
-            });
+                _ => {
+                    return Err(Error::parse(
+                        s,
+                        &mask[i..],
+                        format!("invalid bit '{}'", b as char),
+                    ))
+                }
+            }
+        }
         clear_mask = !clear_mask;
         // Updates
-        let updates = data
-            .map(|line| line.parse::<Update>().expect("failed to parse update"))
-            .collect();
+        let mut updates = ArrayVec::new();
+        for line in data {
+            let update = line
+                .parse::<Update>()
+                .map_err(|error| error.within(s, line))?;
+            updates.try_push(update).map_err(|_| {
+                Error::parse(s, line, format!("more than {} updates", INSTRUCTIONS))
+            })?;
+        }
         // Return
         Ok(Self {
             set_mask,
@@ -62,11 +80,17 @@ struct Update {
 }
 
 impl std::str::FromStr for Update {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut data = s.splitn(2, "]");
-        let address: u64 = data.next().unwrap()[4..].parse().unwrap(); // First 4 are 'mem['
-        let value: u64 = data.next().unwrap()[3..].parse().unwrap(); // First 3 are ' = '
+        let (address, value) = s
+            .strip_prefix("mem[")
+            .and_then(|update| update.split_once("] = "))
+            .ok_or_else(|| Error::parse(s, s, "expected 'mem[ADDRESS] = VALUE'"))?;
+        let address: u64 = error::number(s, address)?;
+        let value: u64 = error::number(s, value)?;
+        if address > MAX_36_BITS || value > MAX_36_BITS {
+            return Err(Error::parse(s, s, "value larger than 36 bits"));
+        }
         Ok(Self { address, value })
     }
 }
@@ -110,76 +134,68 @@ fn update_memory_2(instructions: &Instructions, memory: &mut FxHashSet<u64>) ->
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day14.txt").unwrap();
-
-    // Read to object iterator
-    let data: Vec<Instructions> = buffer
-        .split("mask = ")
-        .skip(1)
-        .map(|line| {
-            line.parse::<Instructions>()
-                .expect("failed to parse instructions")
-        })
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Apply value bitmasks
-    let start_part_1 = Instant::now();
-    let mut memory = FxHashSet::<u64>::with_capacity_and_hasher(CAPACITY, Default::default());
-    let sum_1 = data
-        .iter()
-        .rev()
-        .map(|instructions| update_memory_1(instructions, &mut memory))
-        .sum::<u64>();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Apply memory bitmasks
-    let start_part_2 = Instant::now();
-    memory.clear();
-    let sum_2 = data
-        .iter()
-        .rev()
-        .map(|instructions| update_memory_2(instructions, &mut memory))
-        .sum::<u64>();
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        sum_1 as i64,
-        sum_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+/// Initialization program for the docking computer
+#[derive(Debug)]
+pub struct Program {
+    instructions: Vec<Instructions>,
+    memory: FxHashSet<u64>,
 }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(14, "Docking Data");
-    output::print_part(1, "🏗 Sum", &format!("{}", results.part_1));
-    output::print_part(2, "🏗 Sum", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Day 14, Docking Data
+pub struct Day14;
+
+impl Day for Day14 {
+    const NUMBER: usize = 14;
+    const NAME: &'static str = "Docking Data";
+    const LABELS: [&'static str; 2] = ["🏗 Sum", "🏗 Sum"];
+    const BUDGET: Budget = Budget::millis(5, 5, 25);
+    type Data = Program;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to object iterator
+        let mut blocks = buffer.split("mask = ");
+        let leading = blocks.next().unwrap_or("");
+        if !leading.is_empty() {
+            return Err(Error::parse(buffer, leading, "expected 'mask = '"));
+        }
+        let instructions: Vec<Instructions> = blocks
+            .map(|block| {
+                block
+                    .parse::<Instructions>()
+                    .map_err(|error| error.within(buffer, block))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Program {
+            instructions,
+            memory: FxHashSet::<u64>::default(),
+        })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Apply value bitmasks
+        data.memory = FxHashSet::<u64>::with_capacity_and_hasher(CAPACITY, Default::default());
+        let memory = &mut data.memory;
+        data.instructions
+            .iter()
+            .rev()
+            .map(|instructions| update_memory_1(instructions, memory))
+            .sum::<u64>()
+            .into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Apply memory bitmasks
+        let memory = &mut data.memory;
+        memory.clear();
+        data.instructions
+            .iter()
+            .rev()
+            .map(|instructions| update_memory_2(instructions, memory))
+            .sum::<u64>()
+            .into()
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -5,6 +5,7 @@
 //! is much faster than using the hash map. Hashing is expensive again.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use fixedbitset::FixedBitSet;
 
 // Constants
@@ -25,7 +26,7 @@ macro_rules! turns_since_said {
 }
 
 #[inline]
-fn part_1(n: usize, starters: &Vec<usize>) -> u32 {
+fn part_1(n: usize, starters: &[usize]) -> u32 {
     // Setup
     let mut said = vec![u32::MAX; n + 1];
     let number_starters = starters.len();
@@ -62,7 +63,7 @@ macro_rules! turns_since_said_big {
 }
 
 #[inline]
-fn part_2(n: usize, starters: &Vec<usize>) -> u32 {
+fn part_2(n: usize, starters: &[usize]) -> u32 {
     // Setup
     let mut said = vec![u32::MAX; REALLY_BIG].into_boxed_slice();
     let mut said_bitset = FixedBitSet::with_capacity(REALLY_BIG);
@@ -95,7 +96,7 @@ fn part_2(n: usize, starters: &Vec<usize>) -> u32 {
 // Combined
 // -----------------------------------------------------------------------------
 #[inline]
-fn combined(first: usize, second: usize, starters: &Vec<usize>) -> (u32, u32) {
+fn combined(first: usize, second: usize, starters: &[usize]) -> (u32, u32) {
     // Setup
     let mut said = vec![u32::MAX; REALLY_BIG].into_boxed_slice();
     let mut said_bitset = FixedBitSet::with_capacity(REALLY_BIG);
@@ -130,72 +131,56 @@ fn combined(first: usize, second: usize, starters: &Vec<usize>) -> (u32, u32) {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day15.txt").unwrap();
-
-    // Read to vector
-    let values: Vec<usize> = buffer
-        .trim()
-        .split(',')
-        .map(|line| line.parse().expect("failed to parse line"))
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find 2020th number
-    let start_part_1 = Instant::now();
-    let number_1 = part_1(YEAR, &values);
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find 30,000,000th number
-    let start_part_2 = Instant::now();
-    let number_2 = part_2(REALLY_BIG, &values);
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let values: Vec<usize> = buffer
+fn parse_starters(buffer: &str) -> Result<Vec<usize>, Error> {
+    buffer
         .trim()
         .split(',')
-        .map(|line| line.parse().expect("failed to parse line"))
-        .collect();
-    let (combined_1, combined_2) = combined(YEAR, REALLY_BIG, &values);
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, number_1);
-    assert_eq!(combined_2, number_2);
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        number_1 as i64,
-        number_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
+        .map(|value| {
+            let number = error::number(buffer, value)?;
+            if number >= YEAR {
+                return Err(Error::parse(
+                    buffer,
+                    value,
+                    format!("starting number must be less than {}", YEAR),
+                ));
+            }
+            Ok(number)
+        })
+        .collect()
 }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(15, "Rambunctious Recitation");
-    output::print_part(1, "🧝 Number", &format!("{}", results.part_1));
-    output::print_part(2, "🧝 Number", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Day 15, Rambunctious Recitation
+pub struct Day15;
+
+impl Day for Day15 {
+    const NUMBER: usize = 15;
+    const NAME: &'static str = "Rambunctious Recitation";
+    const LABELS: [&'static str; 2] = ["🧝 Number", "🧝 Number"];
+    const BUDGET: Budget = Budget::millis(5, 5, 2_500);
+    type Data = Vec<usize>;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        parse_starters(buffer)
+    }
+
+    fn part_1(values: &mut Self::Data) -> Answer {
+        // Find 2020th number
+        part_1(YEAR, values).into()
+    }
+
+    fn part_2(values: &mut Self::Data) -> Answer {
+        // Find 30,000,000th number
+        part_2(REALLY_BIG, values).into()
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let values = parse_starters(buffer)?;
+        let (combined_1, combined_2) = combined(YEAR, REALLY_BIG, &values);
+        Ok(Some((combined_1.into(), combined_2.into())))
+    }
 }
 
 // -----------------------------------------------------------------------------
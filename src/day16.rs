@@ -4,6 +4,7 @@
 //! it yet.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use arrayvec::ArrayVec;
 
 // Constants
@@ -20,18 +21,27 @@ struct TicketField {
     upper_range: std::ops::RangeInclusive<u16>,
 }
 
+// Range of form LOWER-UPPER
+fn parse_range(s: &str, range: &str) -> Result<std::ops::RangeInclusive<u16>, Error> {
+    let (lower, upper) = range
+        .split_once('-')
+        .ok_or_else(|| Error::parse(s, range, format!("invalid range '{}'", range)))?;
+    Ok(error::number(s, lower)?..=error::number(s, upper)?)
+}
+
 impl std::str::FromStr for TicketField {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Line of form FIELD NAME: LOWER-RANGE or UPPER-RANGE
-        let mut line = s.splitn(2, ": ");
-        let name = line.next().unwrap().to_string();
-
-        let mut ranges = line.next().unwrap().splitn(4, &['-', 'o'][..]);
-        let lower_range = // need to remove extra spaces
-            ranges.next().unwrap().parse()?..=ranges.next().unwrap().trim().parse()?;
-        let upper_range = // need to remove 'r ' and '\n'
-            ranges.next().unwrap()[2..].parse()?..=ranges.next().unwrap().trim().parse()?;
+        let (name, ranges) = s
+            .split_once(": ")
+            .ok_or_else(|| Error::parse(s, s, "expected 'FIELD NAME: RANGE or RANGE'"))?;
+        let (lower_range, upper_range) = ranges
+            .split_once(" or ")
+            .ok_or_else(|| Error::truncated(s, "' or '"))?;
+        let name = name.to_string();
+        let lower_range = parse_range(s, lower_range)?;
+        let upper_range = parse_range(s, upper_range)?;
 
         Ok(Self {
             name,
@@ -41,6 +51,48 @@ impl std::str::FromStr for TicketField {
     }
 }
 
+// -----------------------------------------------------------------------------
+// Tickets
+// -----------------------------------------------------------------------------
+// Ticket values, one for each field
+fn parse_ticket(
+    buffer: &str,
+    line: &str,
+    number_fields: usize,
+) -> Result<[u16; NUMBER_FIELDS], Error> {
+    let mut ticket = [0; NUMBER_FIELDS];
+    let mut values = line.split(',');
+    for value in ticket.iter_mut().take(number_fields) {
+        *value = error::number(buffer, error::next(line, &mut values, "ticket value")?)?;
+    }
+    if let Some(extra) = values.next() {
+        return Err(Error::parse(
+            buffer,
+            extra,
+            format!("ticket has more than {} values", number_fields),
+        ));
+    }
+    Ok(ticket)
+}
+
+// Section with a header line
+fn section<'a>(
+    buffer: &'a str,
+    sections: &mut impl Iterator<Item = &'a str>,
+    header: &str,
+) -> Result<std::str::Lines<'a>, Error> {
+    let section = error::next(buffer, sections, header)?;
+    let mut lines = section.lines();
+    match lines.next() {
+        Some(line) if line == header => Ok(lines),
+        _ => Err(Error::parse(
+            buffer,
+            section,
+            format!("expected '{}'", header),
+        )),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Ticket field checker
 // -----------------------------------------------------------------------------
@@ -55,142 +107,152 @@ fn valid_fields(value: &u16, fields: &ArrayVec<[TicketField; NUMBER_FIELDS]>) ->
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day16.txt").unwrap();
-
-    // Read ticket fields
-    let mut data = buffer.split("\n\n");
-    let fields: ArrayVec<[TicketField; NUMBER_FIELDS]> = data
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| line.parse::<TicketField>().expect("failed to parse field"))
-        .collect();
-
-    // My ticket
-    let my_ticket: Vec<u16> = data
-        .next()
-        .unwrap()
-        .lines()
-        .nth(1)
-        .unwrap()
-        .split(",")
-        .map(|value| value.parse().expect("failed to parse data"))
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Check nearby tickets, filter out invalid
-    let start_part_1 = Instant::now();
-    let mut error_rate_1 = 0;
-    let other_tickets: ArrayVec<[[u16; NUMBER_FIELDS]; CAPACITY]> = data
-        .next()
-        .unwrap()
-        .lines()
-        .skip(1)
-        .filter_map(|line| {
-            let mut other_ticket = [0; NUMBER_FIELDS];
-            let mut invalid_data = false;
-            line.split(",").enumerate().for_each(|(i, raw)| {
-                let value = raw.parse().expect("failed to parse data");
-                if !valid_fields(&value, &fields) {
-                    error_rate_1 += value;
-                    invalid_data = true;
+/// Ticket rules, my ticket and the valid nearby tickets
+#[derive(Debug)]
+pub struct Notes {
+    fields: ArrayVec<[TicketField; NUMBER_FIELDS]>,
+    number_fields: usize,
+    my_ticket: [u16; NUMBER_FIELDS],
+    nearby: Vec<[u16; NUMBER_FIELDS]>,
+    other_tickets: ArrayVec<[[u16; NUMBER_FIELDS]; CAPACITY]>,
+}
+
+/// Day 16, Ticket Translation
+pub struct Day16;
+
+impl Day for Day16 {
+    const NUMBER: usize = 16;
+    const NAME: &'static str = "Ticket Translation";
+    const LABELS: [&'static str; 2] = ["🎫 Invalid", "🎫 Product"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Notes;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read ticket fields
+        let mut data = buffer.split("\n\n");
+        let mut fields: ArrayVec<[TicketField; NUMBER_FIELDS]> = ArrayVec::new();
+        for line in error::next(buffer, &mut data, "ticket fields")?.lines() {
+            let field = line
+                .parse::<TicketField>()
+                .map_err(|error| error.within(buffer, line))?;
+            fields.try_push(field).map_err(|_| {
+                Error::parse(buffer, line, format!("more than {} fields", NUMBER_FIELDS))
+            })?;
+        }
+        let number_fields = fields.len();
+        if number_fields == 0 {
+            return Err(Error::truncated(buffer, "ticket fields"));
+        }
+
+        // My ticket
+        let mut mine = section(buffer, &mut data, "your ticket:")?;
+        let my_ticket = parse_ticket(
+            buffer,
+            error::next(buffer, &mut mine, "your ticket")?,
+            number_fields,
+        )?;
+
+        // Nearby tickets are checked in part 1
+        let nearby: Vec<[u16; NUMBER_FIELDS]> = section(buffer, &mut data, "nearby tickets:")?
+            .map(|line| parse_ticket(buffer, line, number_fields))
+            .collect::<Result<_, _>>()?;
+        if nearby.len() > CAPACITY {
+            return Err(Error::parse(
+                buffer,
+                buffer,
+                format!("more than {} nearby tickets", CAPACITY),
+            ));
+        }
+        Ok(Notes {
+            fields,
+            number_fields,
+            my_ticket,
+            nearby,
+            other_tickets: ArrayVec::new(),
+        })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Check nearby tickets, filter out invalid
+        let fields = &data.fields;
+        let mut error_rate_1: u32 = 0;
+        data.other_tickets = data
+            .nearby
+            .iter()
+            .filter_map(|other_ticket| {
+                let mut invalid_data = false;
+                other_ticket[..data.number_fields].iter().for_each(|value| {
+                    if !valid_fields(value, fields) {
+                        error_rate_1 += *value as u32;
+                        invalid_data = true;
+                    }
+                });
+                if invalid_data {
+                    // Strip tickets with invalid data
+                    None
                 } else {
-                    other_ticket[i] = value;
+                    Some(*other_ticket)
                 }
-            });
-            if invalid_data {
-                // Strip tickets with invalid data
-                None
-            } else {
-                Some(other_ticket)
-            }
-        })
-        .collect();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Match fields to location
-    let start_part_2 = Instant::now();
-    let mut match_count = 0;
-    let mut matches = [NUMBER_FIELDS + 1; NUMBER_FIELDS];
-    let mut unmatched: Vec<ArrayVec<[usize; NUMBER_FIELDS]>> =
-        vec![(0..NUMBER_FIELDS).collect(); NUMBER_FIELDS];
-
-    while match_count < NUMBER_FIELDS {
-        other_tickets.iter().for_each(|ticket| {
-            let mut found = NUMBER_FIELDS + 1;
-            // Remove invalid options
-            unmatched.iter_mut().enumerate().for_each(|(i, position)| {
-                position.retain(|&mut possible| valid_field(&ticket[i], &fields[possible]));
-                // Clear position if found
-                if position.len() == 1 {
-                    matches[i] = position[0];
-                    found = position[0];
-                    position.clear();
+            })
+            .collect();
+        error_rate_1.into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Match fields to location
+        let (fields, number_fields) = (&data.fields, data.number_fields);
+        let mut match_count = 0;
+        let mut matches = [NUMBER_FIELDS + 1; NUMBER_FIELDS];
+        let mut unmatched: Vec<ArrayVec<[usize; NUMBER_FIELDS]>> =
+            vec![(0..number_fields).collect(); number_fields];
+
+        while match_count < number_fields {
+            let previous_count = match_count;
+            data.other_tickets.iter().for_each(|ticket| {
+                let mut found = NUMBER_FIELDS + 1;
+                // Remove invalid options
+                unmatched.iter_mut().enumerate().for_each(|(i, position)| {
+                    position.retain(|&mut possible| valid_field(&ticket[i], &fields[possible]));
+                    // Clear position if found
+                    if position.len() == 1 {
+                        matches[i] = position[0];
+                        found = position[0];
+                        position.clear();
+                    }
+                });
+                // Remove index if found
+                if found != NUMBER_FIELDS + 1 {
+                    unmatched
+                        .iter_mut()
+                        .for_each(|position| position.retain(|&mut value| value != found));
+                    match_count += 1;
                 }
             });
-            // Remove index if found
-            if found != NUMBER_FIELDS + 1 {
-                unmatched
-                    .iter_mut()
-                    .for_each(|position| position.retain(|&mut value| value != found));
-                match_count += 1;
+            match_count = matches
+                .iter()
+                .filter(|&value| *value != NUMBER_FIELDS + 1)
+                .count();
+            // Tickets do not determine a unique assignment
+            if match_count == previous_count {
+                return Answer::None;
             }
-        });
-        match_count = matches
+        }
+
+        matches
             .iter()
-            .filter(|&value| *value != NUMBER_FIELDS + 1)
-            .count();
+            .take(number_fields)
+            .enumerate()
+            .fold(1, |acc, (match_index, &ticket_index)| {
+                if fields[ticket_index].name.starts_with("de") {
+                    acc * data.my_ticket[match_index] as u64
+                } else {
+                    acc
+                }
+            })
+            .into()
     }
-
-    let product_2 = matches
-        .iter()
-        .enumerate()
-        .fold(1, |acc, (match_index, &ticket_index)| {
-            if fields[ticket_index].name[0..2] == *"de" {
-                acc * my_ticket[match_index] as usize
-            } else {
-                acc
-            }
-        });
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        error_rate_1 as i64,
-        product_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
-}
-
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(16, "Ticket Translation");
-    output::print_part(1, "🎫 Invalid", &format!("{}", results.part_1));
-    output::print_part(2, "🎫 Product", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
 }
 
 // -----------------------------------------------------------------------------
@@ -7,6 +7,7 @@
 //! computation by a factor of nearly 2 and 4, respectively.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use arrayvec::ArrayVec;
 
 // Constants
@@ -87,10 +88,9 @@ fn game_of_life_3d(
             (CYCLES - cycle - 1..column_length - CYCLES - 1 + cycle).for_each(|j| {
                 (0..2 + cycle).for_each(|k| {
                     let index = index_3d!(i, j, k, row_length, column_length);
-                    let count = count_neighbors_3d(index, &neighbors, &cells);
+                    let count = count_neighbors_3d(index, &neighbors, cells);
                     let activated = cells[index + offset as usize];
-                    next_cells[index + offset as usize] =
-                        (activated && (count == 2 || count == 3)) || (!activated && count == 3);
+                    next_cells[index + offset as usize] = count == 3 || (activated && count == 2);
                 });
             })
         });
@@ -171,7 +171,7 @@ fn game_of_life_4d(
         2 * row * column + 2 * row,
         2 * row * column + 2 * row + 1,
         2 * row * column + 2 * row + 2,
-        slab + 0,
+        slab,
         slab + 1,
         slab + 2,
         slab + row,
@@ -233,10 +233,10 @@ fn game_of_life_4d(
                 (0..2 + cycle).for_each(|k| {
                     (0..2 + cycle).for_each(|l| {
                         let index = index_4d!(i, j, k, l, row_length, column_length);
-                        let count = count_neighbors_4d(index, &neighbors, &cells);
+                        let count = count_neighbors_4d(index, &neighbors, cells);
                         let activated = cells[index + offset as usize];
                         next_cells[index + offset as usize] =
-                            (activated && (count == 2 || count == 3)) || (!activated && count == 3);
+                            count == 3 || (activated && count == 2);
                     })
                 })
             })
@@ -261,122 +261,149 @@ fn game_of_life_4d(
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day17.txt").unwrap();
+/// Initial slice of the pocket dimension
+#[derive(Debug)]
+pub struct PocketDimension {
+    initial: Vec<String>,
+    row_length: usize,
+    column_length: usize,
+    cells: ArrayVec<[bool; CAPACITY_3D]>,
+}
+
+/// Day 17, Conway Cubes
+pub struct Day17;
 
-    // Read to vector
-    let row_length = buffer.lines().nth(0).unwrap().chars().count() + 2 * (CYCLES + 1);
-    let column_length = buffer.lines().count() + 2 * (CYCLES + 1);
-    let mut cells: ArrayVec<[bool; CAPACITY_3D]> = ArrayVec::from([false; CAPACITY_3D]);
-    buffer.lines().enumerate().for_each(|(i, line)| {
-        line.chars().enumerate().for_each(|(j, c)| {
-            if c == '#' {
-                cells[index_3d!(i + CYCLES + 1, j + CYCLES + 1, 1, row_length, column_length)] =
-                    true
+impl Day for Day17 {
+    const NUMBER: usize = 17;
+    const NAME: &'static str = "Conway Cubes";
+    const LABELS: [&'static str; 2] = ["🛰 Activated", "🛰 Activated"];
+    const BUDGET: Budget = Budget::millis(5, 5, 15);
+    type Data = PocketDimension;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let first = buffer
+            .lines()
+            .next()
+            .ok_or_else(|| Error::truncated(buffer, "at least one row"))?;
+        let row_length = first.len() + 2 * (CYCLES + 1);
+        let column_length = buffer.lines().count() + 2 * (CYCLES + 1);
+        let slab = row_length * column_length * (CYCLES + 3);
+        if slab > CAPACITY_3D || slab * (CYCLES + 3) > CAPACITY_4D {
+            return Err(Error::parse(buffer, buffer, "initial state is too large"));
+        }
+        let mut cells: ArrayVec<[bool; CAPACITY_3D]> = ArrayVec::from([false; CAPACITY_3D]);
+        for (i, line) in buffer.lines().enumerate() {
+            if line.len() != first.len() {
+                return Err(Error::parse(
+                    buffer,
+                    line,
+                    format!(
+                        "expected row of length {}, found {}",
+                        first.len(),
+                        line.len()
+                    ),
+                ));
+            }
+            for (j, c) in line.bytes().enumerate() {
+                match c {
+                    b'#' => {
+                        cells[index_3d!(
+                            i + CYCLES + 1,
+                            j + CYCLES + 1,
+                            1,
+                            row_length,
+                            column_length
+                        )] = true
+                    }
+                    b'.' => (),
+                    _ => {
+                        return Err(Error::parse(
+                            buffer,
+                            &line[j..],
+                            format!("unexpected character '{}'", c as char),
+                        ))
+                    }
+                }
             }
+        }
+        Ok(PocketDimension {
+            initial: buffer.lines().map(|line| line.to_string()).collect(),
+            row_length,
+            column_length,
+            cells,
         })
-    });
-    let time_setup = start_setup.elapsed();
+    }
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find 3D initialization
-    let start_part_1 = Instant::now();
-    game_of_life_3d(row_length, column_length, &mut cells);
-    let count_1 = cells
-        .iter()
-        .skip(row_length * column_length)
-        .take(row_length * column_length)
-        .filter(|&cell| *cell)
-        .count()
-        + 2 * cells
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find 3D initialization
+        let (row_length, column_length) = (data.row_length, data.column_length);
+        let cells = &mut data.cells;
+        game_of_life_3d(row_length, column_length, cells);
+        (cells
             .iter()
-            .skip(2 * row_length * column_length)
+            .skip(row_length * column_length)
+            .take(row_length * column_length)
             .filter(|&cell| *cell)
-            .count();
-    let time_part_1 = start_part_1.elapsed();
+            .count()
+            + 2 * cells
+                .iter()
+                .skip(2 * row_length * column_length)
+                .filter(|&cell| *cell)
+                .count())
+        .into()
+    }
 
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find 4D initialization
-    let start_part_2 = Instant::now();
-    let mut cells: ArrayVec<[bool; CAPACITY_4D]> = ArrayVec::from([false; CAPACITY_4D]);
-    buffer.lines().enumerate().for_each(|(i, line)| {
-        line.chars().enumerate().for_each(|(j, c)| {
-            if c == '#' {
-                cells[index_4d!(
-                    i + CYCLES + 1,
-                    j + CYCLES + 1,
-                    1,
-                    1,
-                    row_length,
-                    column_length
-                )] = true
-            }
-        })
-    });
-    game_of_life_4d(row_length, column_length, &mut cells);
-    let mut count_2 = 0;
-    (1..row_length - 1).for_each(|i| {
-        (1..column_length - 1).for_each(|j| {
-            // Not repeated
-            if cells[index_4d!(i, j, 1, 1, row_length, column_length)] {
-                count_2 += 1;
-            }
-            // Repeated twice
-            (2..CYCLES + 2).for_each(|k| {
-                if cells[index_4d!(i, j, k, 1, row_length, column_length)] {
-                    count_2 += 2;
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find 4D initialization
+        let (row_length, column_length) = (data.row_length, data.column_length);
+        let mut cells: ArrayVec<[bool; CAPACITY_4D]> = ArrayVec::from([false; CAPACITY_4D]);
+        data.initial.iter().enumerate().for_each(|(i, line)| {
+            line.chars().enumerate().for_each(|(j, c)| {
+                if c == '#' {
+                    cells[index_4d!(
+                        i + CYCLES + 1,
+                        j + CYCLES + 1,
+                        1,
+                        1,
+                        row_length,
+                        column_length
+                    )] = true
                 }
-            });
-            (2..CYCLES + 2).for_each(|l| {
-                // Repeated twice
-                if cells[index_4d!(i, j, 1, l, row_length, column_length)] {
-                    count_2 += 2;
+            })
+        });
+        game_of_life_4d(row_length, column_length, &mut cells);
+        let mut count_2 = 0;
+        (1..row_length - 1).for_each(|i| {
+            (1..column_length - 1).for_each(|j| {
+                // Not repeated
+                if cells[index_4d!(i, j, 1, 1, row_length, column_length)] {
+                    count_2 += 1;
                 }
-                // Repeated four times
+                // Repeated twice
                 (2..CYCLES + 2).for_each(|k| {
-                    if cells[index_4d!(i, j, k, l, row_length, column_length)] {
-                        count_2 += 4;
+                    if cells[index_4d!(i, j, k, 1, row_length, column_length)] {
+                        count_2 += 2;
                     }
                 });
-            });
-        })
-    });
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
-}
-
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(17, "Conway Cubes");
-    output::print_part(1, "🛰 Activated", &format!("{}", results.part_1));
-    output::print_part(2, "🛰 Activated", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+                (2..CYCLES + 2).for_each(|l| {
+                    // Repeated twice
+                    if cells[index_4d!(i, j, 1, l, row_length, column_length)] {
+                        count_2 += 2;
+                    }
+                    // Repeated four times
+                    (2..CYCLES + 2).for_each(|k| {
+                        if cells[index_4d!(i, j, k, l, row_length, column_length)] {
+                            count_2 += 4;
+                        }
+                    });
+                });
+            })
+        });
+        count_2.into()
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -6,6 +6,56 @@
 //! numbers are all one character long and knowing the distribution of the spaces.
 
 use crate::prelude::*;
+use crate::registry::Day;
+
+// -----------------------------------------------------------------------------
+// Check expression
+// -----------------------------------------------------------------------------
+// Evaluation assumes single digit numbers, and operators surrounded by single spaces
+fn check_term<'a>(line: &str, s: &'a str) -> Result<&'a str, Error> {
+    match s.as_bytes().first() {
+        Some(b'0'..=b'9') if s.as_bytes().get(1).is_some_and(u8::is_ascii_digit) => {
+            Err(Error::parse(line, s, "numbers must be single digits"))
+        }
+        Some(b'0'..=b'9') => Ok(&s[1..]),
+        Some(b'(') => {
+            let remainder = check_expression(line, &s[1..])?;
+            match remainder.strip_prefix(')') {
+                Some(remainder) => Ok(remainder),
+                None => Err(Error::parse(line, remainder, "expected ')'")),
+            }
+        }
+        Some(_) => Err(Error::parse(line, s, "expected digit or '('")),
+        None => Err(Error::truncated(line, "digit or '('")),
+    }
+}
+
+fn check_expression<'a>(line: &str, s: &'a str) -> Result<&'a str, Error> {
+    let mut remainder = check_term(line, s)?;
+    while let Some(operation) = remainder
+        .strip_prefix(" + ")
+        .or_else(|| remainder.strip_prefix(" * "))
+    {
+        remainder = check_term(line, operation)?;
+    }
+    Ok(remainder)
+}
+
+fn check_line(line: &str) -> Result<(), Error> {
+    let remainder = check_expression(line, line)?;
+    if remainder.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::parse(line, remainder, "expected ' + ' or ' * '"))
+    }
+}
+
+// Each line in the buffer
+fn check_lines(buffer: &str) -> Result<(), Error> {
+    buffer
+        .lines()
+        .try_for_each(|line| check_line(line).map_err(|error| error.within(buffer, line)))
+}
 
 // -----------------------------------------------------------------------------
 // Parse rightmost token
@@ -38,7 +88,7 @@ fn evaluate_next(s: &str, f: fn(&str) -> usize) -> (usize, usize) {
             })
             .unwrap()
             + 3;
-        right = f(&s[length - operation_index + 3..length - 1]);
+        right = f(&s[length + 3 - operation_index..length - 1]);
     } else {
         right = (s.as_bytes()[length - 1] - b'0') as usize;
         operation_index = 3;
@@ -88,63 +138,44 @@ fn part_2(s: &str) -> usize {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day18.txt").unwrap();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Parse 'new math'
-    let start_part_1 = Instant::now();
-    let calculation_1: usize = buffer.lines().map(|line| part_1(line)).sum();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Parse advanced 'new math'
-    let start_part_2 = Instant::now();
-    let calculation_2: usize = buffer.lines().map(|line| part_2(line)).sum();
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let (combined_1, combined_2) = buffer
-        .lines()
-        .map(|line| (part_1(line), part_2(line)))
-        .fold((0, 0), |acc, values| (acc.0 + values.0, acc.1 + values.1));
-    let time_combined = start_combined.elapsed();
-    assert_eq!(combined_1, calculation_1);
-    assert_eq!(combined_2, calculation_2);
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        calculation_1 as i64,
-        calculation_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
-}
+/// Day 18, Operation Order
+pub struct Day18;
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(18, "Operation Order");
-    output::print_part(1, "🧮 Calculation", &format!("{}", results.part_1));
-    output::print_part(2, "🧮 Calculation", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+impl Day for Day18 {
+    const NUMBER: usize = 18;
+    const NAME: &'static str = "Operation Order";
+    const LABELS: [&'static str; 2] = ["🧮 Calculation", "🧮 Calculation"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = String;
+
+    fn check(buffer: &str) -> Result<(), Error> {
+        check_lines(buffer)
+    }
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Expressions are evaluated directly from the text
+        Ok(buffer.to_string())
+    }
+
+    fn part_1(buffer: &mut Self::Data) -> Answer {
+        // Parse 'new math'
+        buffer.lines().map(part_1).sum::<usize>().into()
+    }
+
+    fn part_2(buffer: &mut Self::Data) -> Answer {
+        // Parse advanced 'new math'
+        buffer.lines().map(part_2).sum::<usize>().into()
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let (combined_1, combined_2) = buffer
+            .lines()
+            .map(|line| (part_1(line), part_2(line)))
+            .fold((0, 0), |acc, values| (acc.0 + values.0, acc.1 + values.1));
+        Ok(Some((combined_1.into(), combined_2.into())))
+    }
 }
 
 // -----------------------------------------------------------------------------
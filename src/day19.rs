@@ -4,6 +4,7 @@
 //! approach which works much better.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use rayon::prelude::*;
 
 // Constants
@@ -12,14 +13,18 @@ const CAPACITY: usize = 135;
 // -----------------------------------------------------------------------------
 // Rules
 // -----------------------------------------------------------------------------
+/// Message rule, referring to other rules by number
 #[derive(Debug, Clone)]
-enum Rule {
-    Terminal(u8),      // id: "a", "b"
-    Any(Vec<Vec<u8>>), // id: 1 | 2
-    All(Vec<u8>),      // id: 1 2
+pub enum Rule {
+    /// Single character, `id: "a"`
+    Terminal(u8),
+    /// Any one of the sequences matches, `id: 1 2 | 3 4`
+    Any(Vec<Vec<u8>>),
+    /// All of the rules match in sequence, `id: 1 2`
+    All(Vec<u8>),
 }
 
-fn check_rule<'a>(message: &'a str, rules: &Vec<Rule>, rule: &Rule) -> Result<&'a str, ()> {
+fn check_rule<'a>(message: &'a str, rules: &[Rule], rule: &Rule) -> Result<&'a str, ()> {
     match rule {
         // Character matches terminal rule
         Rule::Terminal(c) => {
@@ -51,201 +56,196 @@ fn check_rule<'a>(message: &'a str, rules: &Vec<Rule>, rule: &Rule) -> Result<&'
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day19.txt").unwrap();
-    let mut data = buffer.split("\n\n");
-
-    // Read to rules map
-    let mut rules: Vec<Rule> = vec![Rule::Terminal(b'z' as u8); CAPACITY];
-    data.next().unwrap().lines().for_each(|line| {
-        let mut data = line.splitn(2, ": ");
-        let index: u8 = data.next().unwrap().parse().unwrap();
-        let rule: Rule;
-        let right_side = data.next().unwrap();
-        if &right_side[0..1] == "\"" {
-            rule = Rule::Terminal(right_side.as_bytes()[1]);
-        } else {
-            let subs = right_side
-                .split(" | ")
-                .map(|sub| {
-                    sub.split(" ")
-                        .map(|c| c.parse().unwrap())
-                        .collect::<Vec<u8>>()
-                })
-                .collect::<Vec<Vec<u8>>>();
-            if subs.len() == 1 {
-                rule = Rule::All(subs[0].clone());
-            } else {
-                rule = Rule::Any(subs);
-            }
-        }
-        rules[index as usize] = rule;
-    });
-    let time_setup = start_setup.elapsed();
+fn parse_rule_number(line: &str, token: &str) -> Result<u8, Error> {
+    match token.parse::<u8>() {
+        Ok(number) if (number as usize) < CAPACITY => Ok(number),
+        _ => Err(Error::parse(
+            line,
+            token,
+            format!(
+                "invalid rule number '{}', expected 0-{}",
+                token,
+                CAPACITY - 1
+            ),
+        )),
+    }
+}
 
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Count valid messages
-    let start_part_1 = Instant::now();
-    let messages: Vec<String> = data
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| line.to_string())
-        .collect();
-    let invalid_messages: Vec<String> = messages
-        .par_iter()
-        .filter_map(|message| {
-            let result = check_rule(message, &rules, &rules[0]);
-            if result.is_ok() && result.unwrap().is_empty() {
-                None
-            } else {
-                Some(message.to_string())
-            }
+fn parse_rule(line: &str) -> Result<(u8, Rule), Error> {
+    let (index, right_side) = line
+        .split_once(": ")
+        .ok_or_else(|| Error::parse(line, line, "expected 'NUMBER: RULE'"))?;
+    let index = parse_rule_number(line, index)?;
+    if let Some(terminal) = right_side.strip_prefix('"') {
+        return match terminal.as_bytes() {
+            [c, b'"'] => Ok((index, Rule::Terminal(*c))),
+            _ => Err(Error::parse(
+                line,
+                right_side,
+                "expected single character terminal",
+            )),
+        };
+    }
+    let subs = right_side
+        .split(" | ")
+        .map(|sub| {
+            sub.split(' ')
+                .map(|c| parse_rule_number(line, c))
+                .collect::<Result<Vec<u8>, Error>>()
         })
-        .collect();
-    let count_1 =
-        buffer.split("\n\n").skip(1).next().unwrap().lines().count() - invalid_messages.len();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Count additional messages
-    let start_part_2 = Instant::now();
-    let count_extra = invalid_messages
-        .par_iter()
-        .filter(|&message| {
-            // Check rule 42
-            let mut count_42 = 0;
-            let mut remainder = message.as_str();
-            let mut result = check_rule(&remainder, &rules, &rules[42]);
-            while let Ok(current) = result {
-                count_42 += 1;
-                remainder = current;
-                result = check_rule(&remainder, &rules, &rules[42]);
-            }
+        .collect::<Result<Vec<Vec<u8>>, Error>>()?;
+    if subs.len() == 1 {
+        Ok((index, Rule::All(subs[0].clone())))
+    } else {
+        Ok((index, Rule::Any(subs)))
+    }
+}
 
-            if count_42 < 2 {
-                return false;
-            }
+// Rules that refer back to themselves would never finish matching
+fn find_cycle(rules: &[Rule], index: usize, visiting: &mut [u8]) -> Option<usize> {
+    match visiting[index] {
+        1 => return Some(index),
+        2 => return None,
+        _ => visiting[index] = 1,
+    }
+    let cycle = match &rules[index] {
+        Rule::Terminal(_) => None,
+        Rule::Any(sub_rules) => sub_rules
+            .iter()
+            .flatten()
+            .find_map(|&sub| find_cycle(rules, sub as usize, visiting)),
+        Rule::All(sub_rules) => sub_rules
+            .iter()
+            .find_map(|&sub| find_cycle(rules, sub as usize, visiting)),
+    };
+    visiting[index] = 2;
+    cycle
+}
 
-            // Check rule 31
-            let mut count_31 = 0;
-            let mut result = check_rule(&remainder, &rules, &rules[31]);
-            while let Ok(current) = result {
-                count_31 += 1;
-                remainder = current;
-                result = check_rule(&remainder, &rules, &rules[31]);
-            }
+fn parse_rules_section(buffer: &str, rules_section: &str) -> Result<Vec<Rule>, Error> {
+    let mut rules: Vec<Rule> = vec![Rule::Terminal(b'z'); CAPACITY];
+    let mut lines: Vec<&str> = vec![""; CAPACITY];
+    for line in rules_section.lines() {
+        let (index, rule) = parse_rule(line).map_err(|error| error.within(buffer, line))?;
+        rules[index as usize] = rule;
+        lines[index as usize] = line;
+    }
+    let mut visiting = vec![0; CAPACITY];
+    match (0..CAPACITY).find_map(|index| find_cycle(&rules, index, &mut visiting)) {
+        Some(index) => Err(Error::parse(
+            buffer,
+            lines[index],
+            format!("rule {} refers back to itself", index),
+        )),
+        None => Ok(rules),
+    }
+}
 
-            remainder.is_empty() && count_31 > 0 && count_42 > count_31
-        })
-        .count();
-    let count_2 = count_1 + count_extra;
-    let time_part_2 = start_part_2.elapsed();
+/// Parse the rules section of the input, one rule per line
+pub fn parse_rules(rules: &str) -> Result<Vec<Rule>, Error> {
+    parse_rules_section(rules, rules)
+}
 
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    let start_combined = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day19.txt").unwrap();
-    let mut data = buffer.split("\n\n");
+// -----------------------------------------------------------------------------
+// Match messages
+// -----------------------------------------------------------------------------
+/// Whether the whole message matches rule 0
+pub fn matches(message: &str, rules: &[Rule]) -> bool {
+    check_rule(message, rules, &rules[0]) == Ok("")
+}
 
-    let mut rules: Vec<Rule> = vec![Rule::Terminal(b'z' as u8); CAPACITY];
-    data.next().unwrap().lines().for_each(|line| {
-        let mut data = line.splitn(2, ": ");
-        let index: u8 = data.next().unwrap().parse().unwrap();
-        let rule: Rule;
-        let right_side = data.next().unwrap();
-        if &right_side[0..1] == "\"" {
-            rule = Rule::Terminal(right_side.as_bytes()[1]);
-        } else {
-            let subs = right_side
-                .split(" | ")
-                .map(|sub| {
-                    sub.split(" ")
-                        .map(|c| c.parse().unwrap())
-                        .collect::<Vec<u8>>()
-                })
-                .collect::<Vec<Vec<u8>>>();
-            if subs.len() == 1 {
-                rule = Rule::All(subs[0].clone());
-            } else {
-                rule = Rule::Any(subs);
-            }
+/// Whether the whole message matches rule 0 with the looping rules from part 2,
+/// `8: 42 | 42 8` and `11: 42 31 | 42 11 31`
+pub fn matches_looping(message: &str, rules: &[Rule]) -> bool {
+    // Rule 0 is 8 11, so some number of 42s followed by fewer 31s
+    let count = |rule: &Rule, remainder: &mut &str| {
+        let mut count = 0;
+        while let Ok(current) = check_rule(remainder, rules, rule) {
+            count += 1;
+            *remainder = current;
         }
-        rules[index as usize] = rule;
-    });
+        count
+    };
+    let mut remainder = message;
+    let count_42 = count(&rules[42], &mut remainder);
+    let count_31 = count(&rules[31], &mut remainder);
+    remainder.is_empty() && count_42 >= 2 && count_31 > 0 && count_42 > count_31
+}
 
-    let (combined_1, combined_2) = data
-        .next()
-        .unwrap()
-        .par_lines()
-        .map(|line| {
-            let message = line.clone();
-            // Part 1
-            let result = check_rule(message, &rules, &rules[0]);
-            let part_1 = result.is_ok() && result.unwrap().is_empty();
-            // Part 2
-            let part_2 = part_1 || {
-                // Check rule 42
-                let mut count_42 = 0;
-                let mut remainder = message;
-                let mut result = check_rule(&remainder, &rules, &rules[42]);
-                while let Ok(current) = result {
-                    count_42 += 1;
-                    remainder = current;
-                    result = check_rule(&remainder, &rules, &rules[42]);
-                }
+/// Message rules and received messages
+#[derive(Debug)]
+pub struct Messages {
+    rules: Vec<Rule>,
+    messages: String,
+    invalid_messages: Vec<String>,
+    count_1: usize,
+}
 
-                // Check rule 31
-                let mut count_31 = 0;
-                let mut result = check_rule(&remainder, &rules, &rules[31]);
-                while let Ok(current) = result {
-                    count_31 += 1;
-                    remainder = current;
-                    result = check_rule(&remainder, &rules, &rules[31]);
-                }
+/// Day 19, Monster Messages
+pub struct Day19;
+
+impl Day for Day19 {
+    const NUMBER: usize = 19;
+    const NAME: &'static str = "Monster Messages";
+    const LABELS: [&'static str; 2] = ["🦕 Valid", "🦕 Valid"];
+    const BUDGET: Budget = Budget::millis(5, 10, 10);
+    type Data = Messages;
 
-                remainder.is_empty() && count_42 >= 2 && count_31 > 0 && count_42 > count_31
-            };
-            (part_1 as usize, part_2 as usize)
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to rules map
+        let mut data = buffer.split("\n\n");
+        let rules = parse_rules_section(buffer, error::next(buffer, &mut data, "rules")?)?;
+        Ok(Messages {
+            rules,
+            messages: error::next(buffer, &mut data, "messages")?.to_string(),
+            invalid_messages: vec![],
+            count_1: 0,
         })
-        .reduce(
-            || (0, 0),
-            |acc, partial| (acc.0 + partial.0, acc.1 + partial.1),
-        );
-    let time_combined = start_combined.elapsed();
-    assert_eq!(count_1, combined_1);
-    assert_eq!(count_2, combined_2);
+    }
 
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
-}
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Count valid messages
+        let rules = &data.rules;
+        let messages: Vec<String> = data.messages.lines().map(|line| line.to_string()).collect();
+        data.invalid_messages = messages
+            .par_iter()
+            .filter(|message| !matches(message, rules))
+            .cloned()
+            .collect();
+        data.count_1 = messages.len() - data.invalid_messages.len();
+        data.count_1.into()
+    }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(19, "Monster Messages");
-    output::print_part(1, "🦕 Valid", &format!("{}", results.part_1));
-    output::print_part(2, "🦕 Valid", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Count additional messages
+        let rules = &data.rules;
+        let count_extra = data
+            .invalid_messages
+            .par_iter()
+            .filter(|message| matches_looping(message, rules))
+            .count();
+        (data.count_1 + count_extra).into()
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let mut data = buffer.split("\n\n");
+        let rules = parse_rules_section(buffer, error::next(buffer, &mut data, "rules")?)?;
+
+        let (combined_1, combined_2) = error::next(buffer, &mut data, "messages")?
+            .par_lines()
+            .map(|message| {
+                let part_1 = matches(message, &rules);
+                let part_2 = part_1 || matches_looping(message, &rules);
+                (part_1 as i64, part_2 as i64)
+            })
+            .reduce(
+                || (0, 0),
+                |acc, partial| (acc.0 + partial.0, acc.1 + partial.1),
+            );
+        Ok(Some((combined_1.into(), combined_2.into())))
+    }
 }
 
 // -----------------------------------------------------------------------------
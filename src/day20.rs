@@ -2,6 +2,8 @@
 //! Well then. That was hard, but the overall implementation was somewhat fast.
 
 use crate::prelude::*;
+use crate::registry::Day;
+use rustc_hash::FxHashSet;
 
 // Constants
 const TILE_SIZE: usize = 10;
@@ -50,16 +52,48 @@ struct Tile {
 //    Note, this value will be a multiple of 2, for radians and antiradians
 
 impl std::str::FromStr for Tile {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut data = s.splitn(2, "\n");
-        let id = data.next().unwrap()[5..9].parse()?;
-        let image: Vec<bool> = data
-            .next()
-            .unwrap()
-            .chars()
-            .filter_map(|c| if c == '\n' { None } else { Some(c == '#') })
-            .collect();
+        let mut data = s.lines();
+        let header = error::next(s, &mut data, "tile header")?;
+        let id = match header
+            .strip_prefix("Tile ")
+            .and_then(|header| header.strip_suffix(':'))
+        {
+            Some(id) => error::number(s, id)?,
+            None => return Err(Error::parse(s, header, "expected 'Tile ID:'")),
+        };
+        let mut image: Vec<bool> = Vec::with_capacity(TILE_SIZE * TILE_SIZE);
+        for _ in 0..TILE_SIZE {
+            let line = error::next(s, &mut data, "tile row")?;
+            if line.len() != TILE_SIZE {
+                return Err(Error::parse(
+                    s,
+                    line,
+                    format!("expected row of length {}, found {}", TILE_SIZE, line.len()),
+                ));
+            }
+            for (j, c) in line.bytes().enumerate() {
+                match c {
+                    b'#' => image.push(true),
+                    b'.' => image.push(false),
+                    _ => {
+                        return Err(Error::parse(
+                            s,
+                            &line[j..],
+                            format!("unexpected character '{}'", c as char),
+                        ))
+                    }
+                }
+            }
+        }
+        if let Some(extra) = data.find(|line| !line.is_empty()) {
+            return Err(Error::parse(
+                s,
+                extra,
+                format!("tile has more than {} rows", TILE_SIZE),
+            ));
+        }
         let side = Side::Up;
         let orientation = Orientation::Right;
         let (antiradians_0, radians_0) = image
@@ -139,7 +173,7 @@ impl Tile {
             Orientation::Bottom => 1,
         };
         match &self.side {
-            Side::Up => self.edges[(index as usize)].radians,
+            Side::Up => self.edges[index as usize].radians,
             Side::Down => self.edges[(4 - index as usize) % 4].antiradians,
         }
     }
@@ -168,7 +202,7 @@ impl Tile {
     }
 
     // Set left edge
-    fn set_left(&mut self, target_edge: u16) {
+    fn set_left(&mut self, target_edge: u16) -> Option<()> {
         let mut flip = false;
         let index = self.edges.iter().enumerate().find_map(|(i, edge)| {
             if edge.antiradians == target_edge {
@@ -179,36 +213,34 @@ impl Tile {
             } else {
                 None
             }
-        });
+        })?;
         if flip {
             self.flip();
         }
         match index {
-            Some(value) => match value {
-                0 => self.orientation = Orientation::Left,
-                2 => self.orientation = Orientation::Right,
-                1 => {
-                    self.orientation = if self.side == Side::Up {
-                        Orientation::Top
-                    } else {
-                        Orientation::Bottom
-                    }
+            0 => self.orientation = Orientation::Left,
+            2 => self.orientation = Orientation::Right,
+            1 => {
+                self.orientation = if self.side == Side::Up {
+                    Orientation::Top
+                } else {
+                    Orientation::Bottom
                 }
-                3 => {
-                    self.orientation = if self.side == Side::Up {
-                        Orientation::Bottom
-                    } else {
-                        Orientation::Top
-                    }
+            }
+            3 => {
+                self.orientation = if self.side == Side::Up {
+                    Orientation::Bottom
+                } else {
+                    Orientation::Top
                 }
-                _ => unreachable!(),
-            },
-            None => panic!("invalid index"),
+            }
+            _ => unreachable!(),
         };
+        Some(())
     }
 
     // Set top edge
-    fn set_top(&mut self, target_edge: u16) {
+    fn set_top(&mut self, target_edge: u16) -> Option<()> {
         let mut flip = false;
         let index = self.edges.iter().enumerate().find_map(|(i, edge)| {
             if edge.antiradians == target_edge {
@@ -219,36 +251,37 @@ impl Tile {
             } else {
                 None
             }
-        });
+        })?;
         if flip {
             self.flip();
         }
         match index {
-            Some(value) => match value {
-                0 => self.orientation = Orientation::Top,
-                2 => self.orientation = Orientation::Bottom,
-                1 => {
-                    self.orientation = if self.side == Side::Up {
-                        Orientation::Right
-                    } else {
-                        Orientation::Left
-                    }
+            0 => self.orientation = Orientation::Top,
+            2 => self.orientation = Orientation::Bottom,
+            1 => {
+                self.orientation = if self.side == Side::Up {
+                    Orientation::Right
+                } else {
+                    Orientation::Left
                 }
-                3 => {
-                    self.orientation = if self.side == Side::Up {
-                        Orientation::Left
-                    } else {
-                        Orientation::Right
-                    }
+            }
+            3 => {
+                self.orientation = if self.side == Side::Up {
+                    Orientation::Left
+                } else {
+                    Orientation::Right
                 }
-                _ => unreachable!(),
-            },
-            None => panic!("invalid index"),
+            }
+            _ => unreachable!(),
         };
+        Some(())
     }
 
     // Set top corner
-    fn set_top_left(&mut self, corner: &[u16]) {
+    fn set_top_left(&mut self, corner: &[u16]) -> Option<()> {
+        if corner.len() != 2 {
+            return None;
+        }
         let index_1 = self.edges.iter().enumerate().find_map(|(i, edge)| {
             if edge.radians == corner[0] {
                 Some(i)
@@ -263,8 +296,12 @@ impl Tile {
                 None
             }
         });
-        let top = std::cmp::min(index_1.unwrap(), index_2.unwrap());
-        self.set_top(self.edges[top].antiradians);
+        // The edge after the other one, radianswise, goes on top
+        let top = match (index_1?, index_2?) {
+            (0, 3) | (3, 0) => 3,
+            (index_1, index_2) => std::cmp::min(index_1, index_2),
+        };
+        self.set_top(self.edges[top].antiradians)
     }
 }
 
@@ -296,12 +333,12 @@ fn is_monster(
     head: usize,
     row_size: usize,
     monster_indices: &[(i32, i32); 14],
-    image: &Vec<bool>,
+    image: &[bool],
 ) -> bool {
     image[head]
         && monster_indices
             .iter()
-            .all(|(i, j)| image[head + (i * row_size as i32 + j) as usize])
+            .all(|(i, j)| image[(head as i32 + i * row_size as i32 + j) as usize])
 }
 
 // -----------------------------------------------------------------------------
@@ -314,78 +351,28 @@ macro_rules! index_2d {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Assemble image
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day20.txt").unwrap();
-
-    let mut pairs = [[MAX_TILE_SIDE; 2]; MAX_TILE_SIDE];
-    let mut tiles: Vec<Tile> = buffer
-        .split("\n\n")
-        .enumerate()
-        .map(|(i, tile_string)| {
-            let tile = tile_string.parse::<Tile>().expect("failed to parse tile");
-            tile.edges.iter().for_each(|edge| {
-                let index = if pairs[edge.radians as usize][0] == MAX_TILE_SIDE {
-                    0
-                } else {
-                    1
-                };
-                pairs[edge.radians as usize][index] = i;
-                if edge.radians != edge.antiradians {
-                    let index = if pairs[edge.antiradians as usize][0] == MAX_TILE_SIDE {
-                        0
-                    } else {
-                        1
-                    };
-                    pairs[edge.antiradians as usize][index] = i;
-                }
-            });
-            tile
-        })
-        .collect();
+// Tile on the other side of an edge
+#[inline]
+fn other_tile(pairs: &[[usize; 2]], target_edge: u16, target_index: usize) -> usize {
+    if pairs[target_edge as usize][0] != target_index {
+        pairs[target_edge as usize][0]
+    } else {
+        pairs[target_edge as usize][1]
+    }
+}
+
+fn assemble(
+    tiles: &mut [Tile],
+    pairs: &[[usize; 2]],
+    corner: usize,
+    side_length: usize,
+) -> Option<Vec<bool>> {
     let number_tiles = tiles.len();
-    let side_length = (number_tiles as f64).sqrt() as usize;
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find product of corners
-    let start_part_1 = Instant::now();
-    pairs.iter().for_each(|pair| {
-        if pair[0] != MAX_TILE_SIDE && pair[1] != MAX_TILE_SIDE {
-            tiles[pair[0]].shared_edges += 1;
-            tiles[pair[1]].shared_edges += 1;
-        }
-    });
-    let mut corner = number_tiles;
-    let product_1: usize = tiles
-        .iter()
-        .enumerate()
-        .filter_map(|(i, tile)| {
-            if tile.shared_edges == 4 {
-                corner = i as usize;
-                Some(tile.id as usize)
-            } else {
-                None
-            }
-        })
-        .product();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Assemble image and count sea monsters
-    let start_part_2 = Instant::now();
     // Setup corner
-    let corner_edges: Vec<u16> = tiles[corner]
+    let corner_edges: Vec<u16> = tiles
+        .get(corner)?
         .edges
         .iter()
         .filter_map(|edge| {
@@ -396,7 +383,7 @@ pub(crate) fn run() -> Results {
             }
         })
         .collect();
-    tiles[corner].set_top_left(&corner_edges);
+    tiles[corner].set_top_left(&corner_edges)?;
 
     // Fill grid and image
     let mut grid = vec![0; number_tiles];
@@ -404,32 +391,24 @@ pub(crate) fn run() -> Results {
     let row_size = side_length * (TILE_SIZE - 2);
     let mut image = vec![false; row_size * row_size];
 
-    (0..side_length).for_each(|i| {
-        (0..side_length).for_each(|j| {
+    for i in 0..side_length {
+        for j in 0..side_length {
             // Find next tile
             if j == 0 {
                 // Match on top
                 if i != 0 {
                     let target_index = grid[index_2d!(i - 1, j, side_length)];
                     let target_edge = tiles[target_index].get_bottom();
-                    let next_tile = if pairs[target_edge as usize][0] != target_index {
-                        pairs[target_edge as usize][0]
-                    } else {
-                        pairs[target_edge as usize][1]
-                    };
-                    tiles[next_tile].set_top(target_edge);
+                    let next_tile = other_tile(pairs, target_edge, target_index);
+                    tiles.get_mut(next_tile)?.set_top(target_edge)?;
                     grid[index_2d!(i, j, side_length)] = next_tile;
                 }
             } else {
                 // Match on left
                 let target_index = grid[index_2d!(i, j - 1, side_length)];
                 let target_edge = tiles[target_index].get_right();
-                let next_tile = if pairs[target_edge as usize][0] != target_index {
-                    pairs[target_edge as usize][0]
-                } else {
-                    pairs[target_edge as usize][1]
-                };
-                tiles[next_tile].set_left(target_edge);
+                let next_tile = other_tile(pairs, target_edge, target_index);
+                tiles.get_mut(next_tile)?.set_left(target_edge)?;
                 grid[index_2d!(i, j, side_length)] = next_tile;
             }
             // Add to tile image
@@ -443,59 +422,186 @@ pub(crate) fn run() -> Results {
                         tile.image[(ii as i32 * step_i + jj as i32 * step_j + start) as usize];
                 })
             });
-        })
-    });
+        }
+    }
+    Some(image)
+}
 
-    // Count sea monsters
-    let total_features = image.iter().filter(|&p| *p).count();
-    let sea_monster_count = SEA_MONSTER_INDICES
-        .iter()
-        .enumerate()
-        .find_map(|(index, &indices)| {
-            let mut count = 0;
-            (SEA_MONSTER_OFFSETS[index].0..row_size - SEA_MONSTER_OFFSETS[index].1).for_each(|i| {
-                (SEA_MONSTER_OFFSETS[index].2..row_size - SEA_MONSTER_OFFSETS[index].3).for_each(
-                    |j| {
-                        if is_monster(index_2d!(i, j, row_size), row_size, &indices, &image) {
-                            count += 1;
-                        }
-                    },
-                )
-            });
-            if count > 0 {
-                Some(count)
-            } else {
-                None
-            }
-        })
-        .unwrap();
-
-    let count_2 = total_features - sea_monster_count * SEA_MONSTER_SIZE;
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        product_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+// -----------------------------------------------------------------------------
+// Checks
+// -----------------------------------------------------------------------------
+// Assembly assumes a square of tiles, each with its own id
+fn check_tiles(buffer: &str) -> Result<(), Error> {
+    let mut ids = FxHashSet::default();
+    let mut number_tiles = 0;
+    for tile_string in buffer.split("\n\n") {
+        let header = tile_string.lines().next().unwrap_or(tile_string);
+        let id = match header
+            .strip_prefix("Tile ")
+            .and_then(|header| header.strip_suffix(':'))
+        {
+            Some(id) => id,
+            None => return Err(Error::parse(buffer, header, "expected 'Tile ID:'")),
+        };
+        if !ids.insert(id) {
+            return Err(Error::parse(
+                buffer,
+                id,
+                format!("duplicate tile id {}", id),
+            ));
+        }
+        number_tiles += 1;
+    }
+    let side_length = (number_tiles as f64).sqrt() as usize;
+    if side_length * side_length != number_tiles {
+        return Err(Error::parse(
+            buffer,
+            buffer,
+            format!("{} tiles do not make a square image", number_tiles),
+        ));
+    }
+    Ok(())
 }
 
 // -----------------------------------------------------------------------------
-// Report
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(20, "Jurassic Jigsaw");
-    output::print_part(1, "🎞 Product", &format!("{}", results.part_1));
-    output::print_part(2, "🎞 Count", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Camera image tiles and their shared edges
+#[derive(Debug)]
+pub struct Jigsaw {
+    tiles: Vec<Tile>,
+    pairs: Vec<[usize; 2]>,
+    side_length: usize,
+    corner: usize,
+}
+
+/// Day 20, Jurassic Jigsaw
+pub struct Day20;
+
+impl Day for Day20 {
+    const NUMBER: usize = 20;
+    const NAME: &'static str = "Jurassic Jigsaw";
+    const LABELS: [&'static str; 2] = ["🎞 Product", "🎞 Count"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Jigsaw;
+
+    fn check(buffer: &str) -> Result<(), Error> {
+        check_tiles(buffer)
+    }
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        let _span = spans::enter("tile parsing");
+        let mut pairs = vec![[MAX_TILE_SIDE; 2]; MAX_TILE_SIDE];
+        let tiles: Vec<Tile> = buffer
+            .split("\n\n")
+            .enumerate()
+            .map(|(i, tile_string)| {
+                let tile = tile_string
+                    .parse::<Tile>()
+                    .map_err(|error| error.within(buffer, tile_string))?;
+                for edge in tile.edges.iter() {
+                    let number_values = if edge.radians != edge.antiradians {
+                        2
+                    } else {
+                        1
+                    };
+                    for &value in [edge.radians, edge.antiradians].iter().take(number_values) {
+                        let index = match pairs[value as usize] {
+                            [MAX_TILE_SIDE, _] => 0,
+                            [_, MAX_TILE_SIDE] => 1,
+                            _ => {
+                                return Err(Error::parse(
+                                    buffer,
+                                    tile_string,
+                                    format!("tile {} shares an edge with two other tiles", tile.id),
+                                ))
+                            }
+                        };
+                        pairs[value as usize][index] = i;
+                    }
+                }
+                Ok(tile)
+            })
+            .collect::<Result<_, _>>()?;
+        let number_tiles = tiles.len();
+        let side_length = (number_tiles as f64).sqrt() as usize;
+        if side_length * side_length != number_tiles {
+            return Err(Error::parse(
+                buffer,
+                buffer,
+                format!("{} tiles do not form a square", number_tiles),
+            ));
+        }
+        Ok(Jigsaw {
+            tiles,
+            pairs,
+            side_length,
+            corner: number_tiles,
+        })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find product of corners
+        let _span = spans::enter("edge matching");
+        let tiles = &mut data.tiles;
+        data.pairs.iter().for_each(|pair| {
+            if pair[0] != MAX_TILE_SIDE && pair[1] != MAX_TILE_SIDE {
+                tiles[pair[0]].shared_edges += 1;
+                tiles[pair[1]].shared_edges += 1;
+            }
+        });
+        let mut corner = data.corner;
+        let mut product_1 = Some(1_usize);
+        for (i, tile) in tiles.iter().enumerate() {
+            if tile.shared_edges == 4 {
+                corner = i;
+                product_1 = product_1.and_then(|product| product.checked_mul(tile.id as usize));
+            }
+        }
+        data.corner = corner;
+        product_1.map_or(Answer::None, Answer::from)
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Assemble image and count sea monsters
+        let (tiles, pairs) = (&mut data.tiles, &data.pairs);
+        let (corner, side_length) = (data.corner, data.side_length);
+        let row_size = side_length * (TILE_SIZE - 2);
+        let assembly = spans::enter("assembly");
+        let image = match assemble(tiles, pairs, corner, side_length) {
+            Some(image) => image,
+            None => return Answer::None,
+        };
+        drop(assembly);
+
+        // Count sea monsters
+        let _search = spans::enter("monster search");
+        let total_features = image.iter().filter(|&p| *p).count();
+        let sea_monster_count = SEA_MONSTER_INDICES
+            .iter()
+            .enumerate()
+            .map(|(index, &indices)| {
+                let mut count = 0;
+                (SEA_MONSTER_OFFSETS[index].0
+                    ..row_size.saturating_sub(SEA_MONSTER_OFFSETS[index].1))
+                    .for_each(|i| {
+                        (SEA_MONSTER_OFFSETS[index].2
+                            ..row_size.saturating_sub(SEA_MONSTER_OFFSETS[index].3))
+                            .for_each(|j| {
+                                if is_monster(index_2d!(i, j, row_size), row_size, &indices, &image)
+                                {
+                                    count += 1;
+                                }
+                            })
+                    });
+                count
+            })
+            // A stray monster can turn up in the wrong orientation by chance
+            .max()
+            .unwrap_or(0);
+
+        (total_features - sea_monster_count * SEA_MONSTER_SIZE).into()
+    }
 }
 
 // -----------------------------------------------------------------------------
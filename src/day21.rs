@@ -3,6 +3,7 @@
 //! reading and consuming the data at the same time.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 // Constants
@@ -18,24 +19,30 @@ struct Food {
 }
 
 impl std::str::FromStr for Food {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut line = s.splitn(2, " (contains ");
-        let ingredients = line
-            .next()
-            .unwrap()
-            .split(" ")
+        let ingredients: Vec<String> = error::next(s, &mut line, "ingredients")?
+            .split(' ')
             .map(|item| item.to_string())
             .collect();
-        let allergens = line
-            .next()
-            .unwrap()
-            .splitn(2, ")")
-            .next()
-            .unwrap()
-            .split(", ")
-            .map(|allergen| allergen.to_string())
-            .collect();
+        if ingredients.iter().any(|item| item.is_empty()) {
+            return Err(Error::parse(
+                s,
+                s,
+                "expected ingredients separated by single spaces",
+            ));
+        }
+        let allergens = match line.next() {
+            Some(allergens) => match allergens.strip_suffix(')') {
+                Some(allergens) => allergens
+                    .split(", ")
+                    .map(|allergen| allergen.to_string())
+                    .collect(),
+                None => return Err(Error::truncated(s, "')'")),
+            },
+            None => vec![],
+        };
         Ok(Self {
             ingredients,
             allergens,
@@ -44,115 +51,54 @@ impl std::str::FromStr for Food {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Canonical dangerous ingredient list
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day21.txt").unwrap();
-
-    // Read to vector
-    let data: Vec<Food> = buffer
-        .lines()
-        .map(|line| line.parse::<Food>().expect("failed to parse food"))
-        .collect();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Count safe ingredients
-    let start_part_1 = Instant::now();
-    let mut allergen_ingredients_map =
-        FxHashMap::<String, Vec<String>>::with_capacity_and_hasher(CAPACITY, Default::default());
-    // Find possible allergens
-    data.iter().for_each(|food| {
-        food.allergens.iter().for_each(|allergen| {
-            if allergen_ingredients_map.contains_key(allergen) {
-                let ingredients = allergen_ingredients_map.get_mut(allergen).unwrap();
-                if ingredients.len() != 1 {
-                    ingredients.retain(|ingredient| food.ingredients.contains(ingredient));
-                }
-            } else {
-                allergen_ingredients_map.insert(allergen.clone(), food.ingredients.clone());
-            }
-        })
-    });
-
-    // Set of ingredients with allergens
-    let mut allergen_ingredients_set =
-        FxHashSet::<String>::with_capacity_and_hasher(CAPACITY, Default::default());
-    allergen_ingredients_map
+fn canonical_list(found_allergens: &[(String, String)]) -> String {
+    found_allergens
         .iter()
-        .for_each(|(_, ingredients)| {
-            ingredients.iter().for_each(|ingredient| {
-                allergen_ingredients_set.insert(ingredient.clone());
-            })
-        });
-    // Count safe ingredients
-    let count_1: usize = data
-        .iter()
-        .map(|food| {
-            food.ingredients
-                .iter()
-                .filter(|&ingredient| !allergen_ingredients_set.contains(ingredient))
-                .count()
+        .map(|(_, ingredient)| ingredient.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// -----------------------------------------------------------------------------
+// Day
+// -----------------------------------------------------------------------------
+/// Food ingredient lists with allergens
+#[derive(Debug)]
+pub struct Foods {
+    foods: Vec<Food>,
+    allergen_ingredients_map: FxHashMap<String, Vec<String>>,
+}
+
+/// Day 21, Allergen Assessment
+pub struct Day21;
+
+impl Day for Day21 {
+    const NUMBER: usize = 21;
+    const NAME: &'static str = "Allergen Assessment";
+    const LABELS: [&'static str; 2] = ["🛒 Safe", "🛒 Dangerous"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Foods;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let foods: Vec<Food> = error::lines(buffer)?;
+        Ok(Foods {
+            foods,
+            allergen_ingredients_map: FxHashMap::default(),
         })
-        .sum();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // List all allergen ingredients
-    let start_part_2 = Instant::now();
-    let number_allergens = allergen_ingredients_map.len();
-    let mut found_allergens: Vec<(String, String)> = Vec::with_capacity(number_allergens);
-
-    let mut number_found = 0;
-    while number_found != number_allergens {
-        let current = allergen_ingredients_map
-            .iter()
-            .find_map(|(allergen, ingredients)| {
-                if ingredients.len() == 1 {
-                    Some((allergen.clone(), ingredients[0].clone()))
-                } else {
-                    None
-                }
-            })
-            .unwrap();
-        allergen_ingredients_map.remove(&current.0);
-        allergen_ingredients_map
-            .iter_mut()
-            .for_each(|(_, ingredients)| {
-                ingredients.retain(|ingredient| *ingredient != current.1);
-            });
-        found_allergens.append(&mut vec![current]);
-        number_found += 1;
     }
-    found_allergens.sort_by(|a, b| a.0.cmp(&b.0));
-    // println!("{:?}", found_allergens) // Uncomment to print answer
-    let ordered_2 = number_found;
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Combined
-    // -------------------------------------------------------------------------
-    // Count safe ingredients
-    let start_combined = Instant::now();
-
-    let buffer: String = std::fs::read_to_string("data/day21.txt").unwrap();
-    let mut allergen_ingredients_map =
-        FxHashMap::<String, Vec<String>>::with_capacity_and_hasher(CAPACITY, Default::default());
-
-    // Find possible allergens
-    buffer
-        .lines()
-        .map(|line| line.parse::<Food>().expect("failed to parse food"))
-        .for_each(|food| {
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Count safe ingredients
+        let mut allergen_ingredients_map =
+            FxHashMap::<String, Vec<String>>::with_capacity_and_hasher(
+                CAPACITY,
+                Default::default(),
+            );
+        // Find possible allergens
+        data.foods.iter().for_each(|food| {
             food.allergens.iter().for_each(|allergen| {
                 if allergen_ingredients_map.contains_key(allergen) {
                     let ingredients = allergen_ingredients_map.get_mut(allergen).unwrap();
@@ -165,76 +111,146 @@ pub(crate) fn run() -> Results {
             })
         });
 
-    // Set of ingredients with allergens
-    let mut allergen_ingredients_set =
-        FxHashSet::<String>::with_capacity_and_hasher(CAPACITY, Default::default());
-    allergen_ingredients_map
-        .iter()
-        .for_each(|(_, ingredients)| {
+        // Set of ingredients with allergens
+        let mut allergen_ingredients_set =
+            FxHashSet::<String>::with_capacity_and_hasher(CAPACITY, Default::default());
+        allergen_ingredients_map.values().for_each(|ingredients| {
             ingredients.iter().for_each(|ingredient| {
                 allergen_ingredients_set.insert(ingredient.clone());
             })
         });
-    // Count safe ingredients
-    let combined_1: usize = data
-        .iter()
-        .map(|food| {
-            food.ingredients
+        // Count safe ingredients
+        let count_1: usize = data
+            .foods
+            .iter()
+            .map(|food| {
+                food.ingredients
+                    .iter()
+                    .filter(|&ingredient| !allergen_ingredients_set.contains(ingredient))
+                    .count()
+            })
+            .sum();
+        data.allergen_ingredients_map = allergen_ingredients_map;
+        count_1.into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // List all allergen ingredients
+        let allergen_ingredients_map = &mut data.allergen_ingredients_map;
+        let number_allergens = allergen_ingredients_map.len();
+        let mut found_allergens: Vec<(String, String)> = Vec::with_capacity(number_allergens);
+
+        let mut number_found = 0;
+        while number_found != number_allergens {
+            let current = allergen_ingredients_map
                 .iter()
-                .filter(|&ingredient| !allergen_ingredients_set.contains(ingredient))
-                .count()
-        })
-        .sum();
+                .find_map(|(allergen, ingredients)| {
+                    if ingredients.len() == 1 {
+                        Some((allergen.clone(), ingredients[0].clone()))
+                    } else {
+                        None
+                    }
+                });
+            // Remaining allergens cannot be narrowed down to one ingredient
+            let current = match current {
+                Some(current) => current,
+                None => return Answer::None,
+            };
+            allergen_ingredients_map.remove(&current.0);
+            allergen_ingredients_map
+                .iter_mut()
+                .for_each(|(_, ingredients)| {
+                    ingredients.retain(|ingredient| *ingredient != current.1);
+                });
+            found_allergens.push(current);
+            number_found += 1;
+        }
+        found_allergens.sort_by(|a, b| a.0.cmp(&b.0));
+        canonical_list(&found_allergens).into()
+    }
+
+    fn combined(buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        let mut allergen_ingredients_map =
+            FxHashMap::<String, Vec<String>>::with_capacity_and_hasher(
+                CAPACITY,
+                Default::default(),
+            );
 
-    // List all allergen ingredients
-    let number_allergens = allergen_ingredients_map.len();
-    let mut found_allergens: Vec<(String, String)> = Vec::with_capacity(number_allergens);
-    let mut number_found = 0;
+        // Find possible allergens
+        let foods: Vec<Food> = buffer
+            .lines()
+            .map(|line| {
+                let food = line
+                    .parse::<Food>()
+                    .map_err(|error| error.within(buffer, line))?;
+                food.allergens.iter().for_each(|allergen| {
+                    if allergen_ingredients_map.contains_key(allergen) {
+                        let ingredients = allergen_ingredients_map.get_mut(allergen).unwrap();
+                        if ingredients.len() != 1 {
+                            ingredients.retain(|ingredient| food.ingredients.contains(ingredient));
+                        }
+                    } else {
+                        allergen_ingredients_map.insert(allergen.clone(), food.ingredients.clone());
+                    }
+                });
+                Ok(food)
+            })
+            .collect::<Result<_, Error>>()?;
 
-    while number_found != number_allergens {
-        let current = allergen_ingredients_map
+        // Set of ingredients with allergens
+        let mut allergen_ingredients_set =
+            FxHashSet::<String>::with_capacity_and_hasher(CAPACITY, Default::default());
+        allergen_ingredients_map.values().for_each(|ingredients| {
+            ingredients.iter().for_each(|ingredient| {
+                allergen_ingredients_set.insert(ingredient.clone());
+            })
+        });
+        // Count safe ingredients
+        let combined_1: usize = foods
             .iter()
-            .find_map(|(allergen, ingredients)| {
-                if ingredients.len() == 1 {
-                    Some((allergen.clone(), ingredients[0].clone()))
-                } else {
-                    None
-                }
+            .map(|food| {
+                food.ingredients
+                    .iter()
+                    .filter(|&ingredient| !allergen_ingredients_set.contains(ingredient))
+                    .count()
             })
-            .unwrap();
-        allergen_ingredients_map.remove(&current.0);
-        allergen_ingredients_map
-            .iter_mut()
-            .for_each(|(_, ingredients)| {
-                ingredients.retain(|ingredient| *ingredient != current.1);
-            });
-        found_allergens.append(&mut vec![current]);
-        number_found += 1;
-    }
-    found_allergens.sort_by(|a, b| a.0.cmp(&b.0));
-    let combined_2 = number_found;
-    let time_combined = start_combined.elapsed();
-    assert_eq!(count_1, combined_1);
-    assert_eq!(ordered_2, combined_2);
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        ordered_2 as i64,
-        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
-    )
-}
+            .sum();
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(21, "Allergen Assessment");
-    output::print_part(1, "🛒 Safe", &format!("{}", results.part_1));
-    output::print_part(2, "🛒 Product", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+        // List all allergen ingredients
+        let number_allergens = allergen_ingredients_map.len();
+        let mut found_allergens: Vec<(String, String)> = Vec::with_capacity(number_allergens);
+        let mut number_found = 0;
+
+        while number_found != number_allergens {
+            let current = allergen_ingredients_map
+                .iter()
+                .find_map(|(allergen, ingredients)| {
+                    if ingredients.len() == 1 {
+                        Some((allergen.clone(), ingredients[0].clone()))
+                    } else {
+                        None
+                    }
+                });
+            // Remaining allergens cannot be narrowed down to one ingredient
+            let current = match current {
+                Some(current) => current,
+                None => return Ok(Some((combined_1.into(), Answer::None))),
+            };
+            allergen_ingredients_map.remove(&current.0);
+            allergen_ingredients_map
+                .iter_mut()
+                .for_each(|(_, ingredients)| {
+                    ingredients.retain(|ingredient| *ingredient != current.1);
+                });
+            found_allergens.push(current);
+            number_found += 1;
+        }
+        found_allergens.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Some((
+            combined_1.into(),
+            canonical_list(&found_allergens).into(),
+        )))
+    }
 }
 
 // -----------------------------------------------------------------------------
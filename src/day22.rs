@@ -5,23 +5,80 @@
 //! hashed Player 2's deck to a single u64, and hashed the two values to create a single
 //! u64 representing the current game configuration.
 //! I updated the code to use a max number of rounds rather than checking for a repeated
-//! state. This is a cheat, but it works.
+//! state in the sub games. This is a cheat, but it works. The outer game is scored, so
+//! it still checks for repeats, and so does Part 1, which has no rule for them.
 
 use crate::prelude::*;
+use crate::registry::Day;
+use rustc_hash::FxHashSet;
 
 // Constant
 const DECK_SIZE: usize = 50;
 const MAX_ROUNDS: u32 = 2_500; // 50 * 50
 
+// -----------------------------------------------------------------------------
+// Parse deck
+// -----------------------------------------------------------------------------
+fn parse_deck<'a>(
+    buffer: &'a str,
+    sections: &mut impl Iterator<Item = &'a str>,
+    player: &str,
+    deck: &mut [u8; DECK_SIZE],
+    start: usize,
+) -> Result<usize, Error> {
+    let section = error::next(buffer, sections, player)?;
+    let mut lines = section.lines();
+    if lines.next() != Some(player) {
+        return Err(Error::parse(
+            buffer,
+            section,
+            format!("expected '{}'", player),
+        ));
+    }
+    let mut tail = 0;
+    for value in lines {
+        if start + tail == DECK_SIZE {
+            return Err(Error::parse(
+                buffer,
+                value,
+                format!("more than {} cards in total", DECK_SIZE),
+            ));
+        }
+        deck[tail] = error::number(buffer, value)?;
+        tail += 1;
+    }
+    if tail == 0 {
+        return Err(Error::truncated(section, "at least one card").within(buffer, section));
+    }
+    Ok(tail)
+}
+
+// Sub games are cut off after a number of rounds instead of checking for repeats, which
+// only holds up when every card is different; lines that are not cards are left to the
+// parser
+fn check_cards(buffer: &str) -> Result<(), Error> {
+    let mut cards = [false; 256];
+    for line in buffer.lines() {
+        if let Ok(card) = line.parse::<u8>() {
+            if std::mem::replace(&mut cards[card as usize], true) {
+                return Err(Error::parse(
+                    buffer,
+                    line,
+                    format!("duplicate card {}", card),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 // -----------------------------------------------------------------------------
 // Part 1
 // -----------------------------------------------------------------------------
 #[inline]
-fn score_game(head: usize, deck: &[u8; DECK_SIZE]) -> usize {
-    let offset = DECK_SIZE - head % DECK_SIZE;
-    deck.iter()
-        .enumerate()
-        .map(|(i, &card)| card as usize * (DECK_SIZE - (i + offset) % DECK_SIZE))
+fn score_game(head: usize, tail: usize, deck: &[u8; DECK_SIZE]) -> usize {
+    (head..tail)
+        .map(|i| deck[i % DECK_SIZE] as usize * (tail - i))
         .sum()
 }
 
@@ -31,6 +88,49 @@ enum Winner {
     Player2,
 }
 
+// Cards of both decks at some round, to spot a game that goes round in circles
+#[derive(Debug, Default)]
+struct Snapshot {
+    player_1_cards: usize,
+    cards: Vec<u8>,
+}
+
+impl Snapshot {
+    fn cards<'a>(
+        head: usize,
+        tail: usize,
+        deck: &'a [u8; DECK_SIZE],
+    ) -> impl Iterator<Item = u8> + 'a {
+        (head..tail).map(move |i| deck[i % DECK_SIZE])
+    }
+
+    fn take(
+        &mut self,
+        player_1: (usize, usize, &[u8; DECK_SIZE]),
+        player_2: (usize, usize, &[u8; DECK_SIZE]),
+    ) {
+        self.player_1_cards = player_1.1 - player_1.0;
+        self.cards.clear();
+        self.cards
+            .extend(Self::cards(player_1.0, player_1.1, player_1.2));
+        self.cards
+            .extend(Self::cards(player_2.0, player_2.1, player_2.2));
+    }
+
+    fn matches(
+        &self,
+        player_1: (usize, usize, &[u8; DECK_SIZE]),
+        player_2: (usize, usize, &[u8; DECK_SIZE]),
+    ) -> bool {
+        self.player_1_cards == player_1.1 - player_1.0
+            && Self::cards(player_1.0, player_1.1, player_1.2)
+                .chain(Self::cards(player_2.0, player_2.1, player_2.2))
+                .eq(self.cards.iter().copied())
+    }
+}
+
+// Winner and score; `None` if the game never ends, which is checked for with Brent's
+// cycle detection since a simple game has no rule against repeats
 #[inline]
 fn part_1(
     mut player_1_head: usize,
@@ -39,9 +139,23 @@ fn part_1(
     mut player_2_head: usize,
     mut player_2_tail: usize,
     mut player_2_deck: [u8; DECK_SIZE],
-) -> (Winner, usize) {
+) -> Option<(Winner, usize)> {
     // Play game
+    let mut snapshot = Snapshot::default();
+    let (mut power, mut length) = (1, 1);
     while player_1_head != player_1_tail && player_2_head != player_2_tail {
+        let player_1 = (player_1_head, player_1_tail, &player_1_deck);
+        let player_2 = (player_2_head, player_2_tail, &player_2_deck);
+        if snapshot.matches(player_1, player_2) {
+            return None;
+        }
+        if length == power {
+            snapshot.take(player_1, player_2);
+            power *= 2;
+            length = 0;
+        }
+        length += 1;
+
         let card_1 = player_1_deck[player_1_head % DECK_SIZE];
         let card_2 = player_2_deck[player_2_head % DECK_SIZE];
         player_1_head += 1;
@@ -60,9 +174,15 @@ fn part_1(
 
     // Report winner
     if player_2_head == player_2_tail {
-        (Winner::Player1, score_game(player_1_head, &player_1_deck))
+        Some((
+            Winner::Player1,
+            score_game(player_1_head, player_1_tail, &player_1_deck),
+        ))
     } else {
-        (Winner::Player2, score_game(player_2_head, &player_2_deck))
+        Some((
+            Winner::Player2,
+            score_game(player_2_head, player_2_tail, &player_2_deck),
+        ))
     }
 }
 
@@ -82,15 +202,38 @@ fn part_2(
     if !score {
         let player_1_max = (player_1_head..player_1_tail)
             .map(|i| player_1_deck[i % DECK_SIZE])
-            .fold(0, |acc, elem| std::cmp::max(acc, elem));
+            .fold(0, std::cmp::max);
         if (player_2_head..player_2_tail).all(|i| player_2_deck[i % DECK_SIZE] < player_1_max) {
             return (Winner::Player1, 0);
         }
     }
 
-    // Play game
+    // Play game; the outer game checks for repeats, as its final decks are scored
     let mut round = 0;
-    while round < MAX_ROUNDS && player_1_head != player_1_tail && player_2_head != player_2_tail {
+    let mut repeated = false;
+    let mut seen = FxHashSet::<Vec<u8>>::default();
+    while (score || round < MAX_ROUNDS)
+        && player_1_head != player_1_tail
+        && player_2_head != player_2_tail
+    {
+        if score {
+            let mut state = vec![(player_1_tail - player_1_head) as u8];
+            state.extend(Snapshot::cards(
+                player_1_head,
+                player_1_tail,
+                &player_1_deck,
+            ));
+            state.extend(Snapshot::cards(
+                player_2_head,
+                player_2_tail,
+                &player_2_deck,
+            ));
+            if !seen.insert(state) {
+                repeated = true;
+                break;
+            }
+        }
+
         // Draw cards
         let card_1 = player_1_deck[player_1_head % DECK_SIZE];
         let card_2 = player_2_deck[player_2_head % DECK_SIZE];
@@ -105,10 +248,10 @@ fn part_2(
             let (winner_, _) = part_2(
                 player_1_head,
                 player_1_head + card_1 as usize,
-                player_1_deck.clone(),
+                player_1_deck,
                 player_2_head,
                 player_2_head + card_2 as usize,
-                player_2_deck.clone(),
+                player_2_deck,
                 false,
             );
             winner = winner_; // Ugly hack because destructuring is unstable
@@ -132,11 +275,11 @@ fn part_2(
     }
 
     // Report winner
-    if player_2_head == player_2_tail || round == MAX_ROUNDS {
+    if player_2_head == player_2_tail || round == MAX_ROUNDS || repeated {
         (
             Winner::Player1,
             if score {
-                score_game(player_1_head, &player_1_deck)
+                score_game(player_1_head, player_1_tail, &player_1_deck)
             } else {
                 0
             },
@@ -145,7 +288,7 @@ fn part_2(
         (
             Winner::Player2,
             if score {
-                score_game(player_2_head, &player_2_deck)
+                score_game(player_2_head, player_2_tail, &player_2_deck)
             } else {
                 0
             },
@@ -154,90 +297,81 @@ fn part_2(
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day22.txt").unwrap();
-    let mut data = buffer.split("\n\n");
-
-    // Read to arrays
-    let mut player_1_deck: [u8; DECK_SIZE] = [0; DECK_SIZE];
-    let player_1_head = 0;
-    let mut player_1_tail = 0;
-    data.next().unwrap().lines().skip(1).for_each(|value| {
-        player_1_deck[player_1_tail] = value.parse().expect("failed to parse line");
-        player_1_tail += 1;
-    });
-
-    let mut player_2_deck: [u8; DECK_SIZE] = [0; DECK_SIZE];
-    let player_2_head = 0;
-    let mut player_2_tail = 0;
-    data.next().unwrap().lines().skip(1).for_each(|value| {
-        player_2_deck[player_2_tail] = value.parse().expect("failed to parse line");
-        player_2_tail += 1;
-    });
-
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Play simple game
-    let start_part_1 = Instant::now();
-    let (_, score_1) = part_1(
-        player_1_head,
-        player_1_tail,
-        player_1_deck.clone(),
-        player_2_head,
-        player_2_tail,
-        player_2_deck.clone(),
-    );
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Play recursive game
-    let start_part_2 = Instant::now();
-    let (_, score_2) = part_2(
-        player_1_head,
-        player_1_tail,
-        player_1_deck.clone(),
-        player_2_head,
-        player_2_tail,
-        player_2_deck.clone(),
-        true,
-    );
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        score_1 as i64,
-        score_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+/// Starting decks for both players
+#[derive(Debug)]
+pub struct Decks {
+    player_1_tail: usize,
+    player_1_deck: [u8; DECK_SIZE],
+    player_2_tail: usize,
+    player_2_deck: [u8; DECK_SIZE],
 }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(22, "Crab Combat");
-    output::print_part(1, "🦀 Score", &format!("{}", results.part_1));
-    output::print_part(2, "🦀 Score", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Day 22, Crab Combat
+pub struct Day22;
+
+impl Day for Day22 {
+    const NUMBER: usize = 22;
+    const NAME: &'static str = "Crab Combat";
+    const LABELS: [&'static str; 2] = ["🦀 Score", "🦀 Score"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = Decks;
+
+    fn check(buffer: &str) -> Result<(), Error> {
+        check_cards(buffer)
+    }
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        let mut data = buffer.split("\n\n");
+
+        // Read to arrays
+        let mut player_1_deck: [u8; DECK_SIZE] = [0; DECK_SIZE];
+        let player_1_tail = parse_deck(buffer, &mut data, "Player 1:", &mut player_1_deck, 0)?;
+
+        let mut player_2_deck: [u8; DECK_SIZE] = [0; DECK_SIZE];
+        let player_2_tail = parse_deck(
+            buffer,
+            &mut data,
+            "Player 2:",
+            &mut player_2_deck,
+            player_1_tail,
+        )?;
+
+        Ok(Decks {
+            player_1_tail,
+            player_1_deck,
+            player_2_tail,
+            player_2_deck,
+        })
+    }
+
+    fn part_1(decks: &mut Self::Data) -> Answer {
+        // Play simple game
+        part_1(
+            0,
+            decks.player_1_tail,
+            decks.player_1_deck,
+            0,
+            decks.player_2_tail,
+            decks.player_2_deck,
+        )
+        .map_or(Answer::None, |(_, score_1)| score_1.into())
+    }
+
+    fn part_2(decks: &mut Self::Data) -> Answer {
+        // Play recursive game
+        let (_, score_2) = part_2(
+            0,
+            decks.player_1_tail,
+            decks.player_1_deck,
+            0,
+            decks.player_2_tail,
+            decks.player_2_deck,
+            true,
+        );
+        score_2.into()
+    }
 }
 
 // -----------------------------------------------------------------------------
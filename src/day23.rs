@@ -4,6 +4,7 @@
 //! test suite.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // Constants
 const NUMBER_CUPS_SMALL: i32 = 9;
@@ -15,9 +16,9 @@ const NUMBER_ROUNDS_BIG: usize = 10_000_000;
 // Play the game
 // -----------------------------------------------------------------------------
 #[inline(always)]
-fn play_game(cups: &mut Vec<i32>, number_rounds: usize) {
+fn play_game(cups: &mut [i32], first_cup: i32, number_rounds: usize) {
     let number_cups = cups.len() as i32;
-    let mut current_cup: i32 = 0;
+    let mut current_cup = first_cup;
     (0..number_rounds).for_each(|_| {
         // Next 3 cups
         let next_1 = cups[current_cup as usize];
@@ -40,82 +41,88 @@ fn play_game(cups: &mut Vec<i32>, number_rounds: usize) {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day23.txt").unwrap();
-
-    // Read to vector
-    let mut cups = vec![0; NUMBER_CUPS_SMALL as usize];
-    let data: Vec<i32> = buffer
-        .trim()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as i32 - 1)
-        .collect();
-    (0..NUMBER_CUPS_SMALL as usize)
-        .for_each(|i| cups[data[i] as usize] = data[(i + 1) % NUMBER_CUPS_SMALL as usize]);
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Find 100th move
-    let start_part_1 = Instant::now();
-    play_game(&mut cups, NUMBER_ROUNDS_SMALL);
-
-    let mut current_index = cups[0];
-    let number_1 = (0..NUMBER_CUPS_SMALL - 1).fold(0, |acc, _| {
-        let new = (acc * 10) + (current_index + 1);
-        current_index = cups[current_index as usize];
-        new
-    });
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Find 10,000,000th move
-    let start_part_2 = Instant::now();
-    let mut more_cups = vec![0; NUMBER_CUPS_BIG as usize];
-    (0..NUMBER_CUPS_SMALL as usize - 1)
-        .for_each(|i| more_cups[data[i] as usize] = data[(i + 1) % NUMBER_CUPS_SMALL as usize]);
-    more_cups[data[NUMBER_CUPS_SMALL as usize - 1] as usize] = NUMBER_CUPS_SMALL;
-    (NUMBER_CUPS_SMALL as usize..NUMBER_CUPS_BIG as usize)
-        .for_each(|i| more_cups[i] = (i as i32 + 1) % NUMBER_CUPS_BIG);
-
-    play_game(&mut more_cups, NUMBER_ROUNDS_BIG);
-
-    let number_2 = (more_cups[0] as usize + 1) * (more_cups[more_cups[0] as usize] as usize + 1);
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        number_1 as i64,
-        number_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+/// Starting cup labels
+#[derive(Debug)]
+pub struct Cups {
+    labels: Vec<i32>,
+    cups: Vec<i32>,
 }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(23, "Crab Cups");
-    output::print_part(1, "🥛 Current", &format!("{}", results.part_1));
-    output::print_part(2, "🥛 Number", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Day 23, Crab Cups
+pub struct Day23;
+
+impl Day for Day23 {
+    const NUMBER: usize = 23;
+    const NAME: &'static str = "Crab Cups";
+    const LABELS: [&'static str; 2] = ["🥛 Labels", "🥛 Number"];
+    const BUDGET: Budget = Budget::millis(5, 5, 2_000);
+    type Data = Cups;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read to vector
+        let mut cups = vec![0; NUMBER_CUPS_SMALL as usize];
+        let line = buffer.trim();
+        let mut seen = [false; NUMBER_CUPS_SMALL as usize];
+        let labels: Vec<i32> = line
+            .char_indices()
+            .map(|(i, c)| match c.to_digit(10) {
+                Some(label) if label >= 1 && !seen[label as usize - 1] => {
+                    seen[label as usize - 1] = true;
+                    Ok(label as i32 - 1)
+                }
+                _ => Err(Error::parse(
+                    buffer,
+                    &line[i..],
+                    format!("expected each cup label 1-{} once", NUMBER_CUPS_SMALL),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        if labels.len() != NUMBER_CUPS_SMALL as usize {
+            return Err(Error::truncated(
+                buffer,
+                &format!("{} cup labels", NUMBER_CUPS_SMALL),
+            ));
+        }
+        (0..NUMBER_CUPS_SMALL as usize)
+            .for_each(|i| cups[labels[i] as usize] = labels[(i + 1) % NUMBER_CUPS_SMALL as usize]);
+        Ok(Cups { labels, cups })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Find 100th move
+        let cups = &mut data.cups;
+        play_game(cups, data.labels[0], NUMBER_ROUNDS_SMALL);
+
+        let mut current_index = cups[0];
+        (0..NUMBER_CUPS_SMALL - 1)
+            .map(|_| {
+                let label = (b'1' + current_index as u8) as char;
+                current_index = cups[current_index as usize];
+                label
+            })
+            .collect::<String>()
+            .into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Find 10,000,000th move
+        let labels = &data.labels;
+        let mut more_cups = vec![0; NUMBER_CUPS_BIG as usize];
+        (0..NUMBER_CUPS_SMALL as usize - 1).for_each(|i| {
+            more_cups[labels[i] as usize] = labels[(i + 1) % NUMBER_CUPS_SMALL as usize]
+        });
+        more_cups[labels[NUMBER_CUPS_SMALL as usize - 1] as usize] = NUMBER_CUPS_SMALL;
+        (NUMBER_CUPS_SMALL as usize..NUMBER_CUPS_BIG as usize - 1)
+            .for_each(|i| more_cups[i] = i as i32 + 1);
+        // The last cup wraps around to the first label, not to cup 1
+        more_cups[NUMBER_CUPS_BIG as usize - 1] = labels[0];
+
+        play_game(&mut more_cups, labels[0], NUMBER_ROUNDS_BIG);
+
+        ((more_cups[0] as usize + 1) * (more_cups[more_cups[0] as usize] as usize + 1)).into()
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -6,11 +6,13 @@
 //! and unsigned integers.
 
 use crate::prelude::*;
+use crate::registry::Day;
 
 // Constants
 const GENERATIONS: usize = 100;
 const GRID_SIZE: usize = 2 * GENERATIONS + 34;
 const OFFSET: usize = GRID_SIZE / 2;
+const MAX_DISTANCE: i16 = (OFFSET - GENERATIONS - 1) as i16;
 
 // -----------------------------------------------------------------------------
 // Hexagonal coordinates
@@ -39,35 +41,56 @@ struct Coordinate {
 //        \ /   \ /
 //
 impl std::str::FromStr for Coordinate {
-    type Err = std::num::ParseIntError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (mut east, mut north_east) = (0, 0);
+        let (mut east, mut north_east): (i16, i16) = (0, 0);
         let mut i = 0;
+        let bytes = s.as_bytes();
         while i < s.len() {
-            if s.as_bytes()[i] == b'e' {
-                // East
-                east += 1;
-                i += 1;
-            } else if s.as_bytes()[i] == b'w' {
-                // Anti-east (west)
-                east -= 1;
-                i += 1;
-            } else if s.as_bytes()[i] == b'n' {
-                // Northeast
-                north_east += 1;
-                if s.as_bytes()[i + 1] == b'w' {
-                    // Northeast + anti-east (northwest)
+            match (bytes[i], bytes.get(i + 1)) {
+                (b'e', _) => {
+                    // East
+                    east += 1;
+                    i += 1;
+                }
+                (b'w', _) => {
+                    // Anti-east (west)
                     east -= 1;
+                    i += 1;
                 }
-                i += 2;
-            } else {
-                // Anti-northeast (southwest)
-                north_east -= 1;
-                if s.as_bytes()[i + 1] == b'e' {
-                    // Anti-northeast + east (southeast)
-                    east += 1;
+                (b'n', Some(b'e')) | (b'n', Some(b'w')) => {
+                    // Northeast
+                    north_east += 1;
+                    if bytes[i + 1] == b'w' {
+                        // Northeast + anti-east (northwest)
+                        east -= 1;
+                    }
+                    i += 2;
+                }
+                (b's', Some(b'e')) | (b's', Some(b'w')) => {
+                    // Anti-northeast (southwest)
+                    north_east -= 1;
+                    if bytes[i + 1] == b'e' {
+                        // Anti-northeast + east (southeast)
+                        east += 1;
+                    }
+                    i += 2;
+                }
+                _ => {
+                    return Err(Error::parse(
+                        s,
+                        s.get(i..).unwrap_or(""),
+                        "expected one of e, se, sw, w, nw, ne",
+                    ))
                 }
-                i += 2;
+            }
+            // Leave room for the pattern to grow
+            if east.abs() > MAX_DISTANCE || north_east.abs() > MAX_DISTANCE {
+                return Err(Error::parse(
+                    s,
+                    s,
+                    "tile is too far from the reference tile",
+                ));
             }
         }
         Ok(Coordinate { east, north_east })
@@ -122,64 +145,47 @@ fn game_of_life(tiles: &mut [bool; GRID_SIZE * GRID_SIZE], generations: usize) {
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day24.txt").unwrap();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Count initial tiles
-    let start_part_1 = Instant::now();
-    let mut tiles = [false; GRID_SIZE * GRID_SIZE];
-    buffer.lines().for_each(|line| {
-        let coordinate = line
-            .parse::<Coordinate>()
-            .expect("failed to parse directions");
-        tiles[coordinate.to_index()] ^= true;
-    });
-    let count_1 = tiles.iter().filter(|&tile| *tile).count();
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // Run Game of Life
-    let start_part_2 = Instant::now();
-    game_of_life(&mut tiles, GENERATIONS);
-    let count_2 = tiles.iter().filter(|&tile| *tile).count();
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        count_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+/// Tiles to flip and the lobby floor
+#[derive(Debug)]
+pub struct Floor {
+    directions: Vec<Coordinate>,
+    tiles: Box<[bool; GRID_SIZE * GRID_SIZE]>,
 }
 
-// -----------------------------------------------------------------------------
-// Report
-// -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(24, "Lobby Layout");
-    output::print_part(1, "🏨 Count", &format!("{}", results.part_1));
-    output::print_part(2, "🏨 Count", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Day 24, Lobby Layout
+pub struct Day24;
+
+impl Day for Day24 {
+    const NUMBER: usize = 24;
+    const NAME: &'static str = "Lobby Layout";
+    const LABELS: [&'static str; 2] = ["🏨 Count", "🏨 Count"];
+    const BUDGET: Budget = Budget::millis(5, 5, 40);
+    type Data = Floor;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Directions are flipped in part 1
+        Ok(Floor {
+            directions: error::lines(buffer)?,
+            tiles: Box::new([false; GRID_SIZE * GRID_SIZE]),
+        })
+    }
+
+    fn part_1(data: &mut Self::Data) -> Answer {
+        // Count initial tiles
+        let tiles = &mut data.tiles;
+        data.directions.iter().for_each(|coordinate| {
+            tiles[coordinate.to_index()] ^= true;
+        });
+        tiles.iter().filter(|&tile| *tile).count().into()
+    }
+
+    fn part_2(data: &mut Self::Data) -> Answer {
+        // Run Game of Life
+        game_of_life(&mut data.tiles, GENERATIONS);
+        data.tiles.iter().filter(|&tile| *tile).count().into()
+    }
 }
 
 // -----------------------------------------------------------------------------
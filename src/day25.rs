@@ -5,19 +5,25 @@
 //! the secret key to derive the shared secret.
 
 use crate::prelude::*;
+use crate::registry::Day;
 use rustc_hash::FxHashMap;
 
 // Constant
 const BASE: u32 = 7;
-const P: u32 = 2020_12_27;
+const P: u32 = 20_201_227;
 const SQRT_P: u32 = 4_495;
 const BASE_INV_SQRT_P: u32 = 680_915;
 
 // -----------------------------------------------------------------------------
 // Discrete logarithm
 // -----------------------------------------------------------------------------
-// Baby step, giant step algorithm
-fn discrete_log(target: u32) -> u32 {
+/// Loop size that transforms the subject number 7 into target, with the baby step,
+/// giant step algorithm; `None` if no loop size does, as for targets outside 1..20201227
+pub fn discrete_log(target: u32) -> Option<u32> {
+    if target == 0 || target >= P {
+        return None;
+    }
+
     // Form table of a^j mod p
     let mut current = 1;
     let table: FxHashMap<u32, u32> = (0..SQRT_P)
@@ -32,86 +38,83 @@ fn discrete_log(target: u32) -> u32 {
     let mut current = target;
     for i in 0..SQRT_P {
         if table.contains_key(&current) {
-            return i * SQRT_P + table.get(&current).unwrap();
+            return Some(i * SQRT_P + table.get(&current).unwrap());
         } else {
             current = ((current as u64 * BASE_INV_SQRT_P as u64) % P as u64) as u32;
         }
     }
-    unreachable!()
+    None
 }
 
 // -----------------------------------------------------------------------------
-// Run
+// Transform
 // -----------------------------------------------------------------------------
-pub(crate) fn run() -> Results {
-    // -------------------------------------------------------------------------
-    // Setup
-    // -------------------------------------------------------------------------
-    // Open file
-    let start_setup = Instant::now();
-    let buffer: String = std::fs::read_to_string("data/day25.txt").unwrap();
-
-    // Read data
-    let mut keys = buffer
-        .lines()
-        .map(|line| line.parse::<u32>().expect("failed to parse key"));
-    let door_public_key = keys.next().unwrap();
-    let card_public_key = keys.next().unwrap();
-    let time_setup = start_setup.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 1
-    // -------------------------------------------------------------------------
-    // Discover shared secret
-    let start_part_1 = Instant::now();
-    let door_private_key = discrete_log(door_public_key);
-    let number_digits = (door_private_key as f32).log2().ceil() as usize;
-    let mut digits = door_private_key;
-    let mut square = card_public_key;
+/// Transform the subject number with the loop size, subject ^ loop_size mod 20201227
+pub fn transform(subject: u32, loop_size: u32) -> u32 {
     // Multiply result by powers of two found in binary representation of loop size
-    let secret_1 = (0..number_digits).fold(1, |acc, _| {
-        let new_result = if digits % 2 == 1 {
-            ((acc as u64 * square as u64) % P as u64) as u32
-        } else {
-            acc
-        };
+    let mut result = 1;
+    let mut square = subject;
+    let mut digits = loop_size;
+    while digits > 0 {
+        if digits % 2 == 1 {
+            result = ((result as u64 * square as u64) % P as u64) as u32;
+        }
         square = ((square as u64 * square as u64) % P as u64) as u32;
-        digits = digits >> 1;
-        new_result
-    });
-    let time_part_1 = start_part_1.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Part 2
-    // -------------------------------------------------------------------------
-    // No part 2
-    let start_part_2 = Instant::now();
-    let count_2 = 0;
-    let time_part_2 = start_part_2.elapsed();
-
-    // -------------------------------------------------------------------------
-    // Return
-    // -------------------------------------------------------------------------
-    Results::new(
-        secret_1 as i64,
-        count_2 as i64,
-        Timing::new(
-            time_setup,
-            time_part_1,
-            time_part_2,
-            std::time::Duration::new(0, 0),
-        ),
-    )
+        digits >>= 1;
+    }
+    result
 }
 
 // -----------------------------------------------------------------------------
-// Report
+// Day
 // -----------------------------------------------------------------------------
-pub(crate) fn report(results: &Results) {
-    output::print_day(25, "Combo Breaker");
-    output::print_part(1, "🔓 Secret", &format!("{}", results.part_1));
-    output::print_part(2, "🔓 HAPPY HOLIDAYS", &format!("{}", results.part_2));
-    output::print_timing(&results.times);
+/// Public keys of the card and the door
+#[derive(Debug)]
+pub struct PublicKeys {
+    door: u32,
+    card: u32,
+}
+
+/// Day 25, Combo Breaker
+pub struct Day25;
+
+impl Day for Day25 {
+    const NUMBER: usize = 25;
+    const NAME: &'static str = "Combo Breaker";
+    const LABELS: [&'static str; 2] = ["🔓 Secret", "🔓 HAPPY HOLIDAYS"];
+    const BUDGET: Budget = Budget::millis(5, 5, 5);
+    type Data = PublicKeys;
+
+    fn parse(buffer: &str) -> Result<Self::Data, Error> {
+        // Read data
+        let mut keys = buffer.lines().map(|line| match line.parse::<u32>() {
+            Ok(key) if key > 0 && key < P => Ok(key),
+            _ => Err(Error::parse(
+                buffer,
+                line,
+                format!("invalid public key '{}', expected 1-{}", line, P - 1),
+            )),
+        });
+        let door = keys
+            .next()
+            .ok_or_else(|| Error::truncated(buffer, "door public key"))??;
+        let card = keys
+            .next()
+            .ok_or_else(|| Error::truncated(buffer, "card public key"))??;
+        Ok(PublicKeys { door, card })
+    }
+
+    fn part_1(keys: &mut Self::Data) -> Answer {
+        // Discover shared secret
+        discrete_log(keys.door).map_or(Answer::None, |door_private_key| {
+            transform(keys.card, door_private_key).into()
+        })
+    }
+
+    fn part_2(_keys: &mut Self::Data) -> Answer {
+        // No part 2
+        Answer::None
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -0,0 +1,153 @@
+//! Errors:
+//! Parsers report problems with the input through a single error type instead of
+//! panicking. Most parsers work on slices of the original buffer, so the location of a
+//! bad token can be recovered from its position in the buffer rather than threading
+//! line counters through every day.
+
+// -----------------------------------------------------------------------------
+// Error enum
+// -----------------------------------------------------------------------------
+/// Problem reading or parsing the input for a day
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read
+    Io(std::io::Error),
+    /// The input is malformed, or does not meet the assumptions of the solution
+    Parse {
+        /// Line of the problem, starting at 1
+        line: usize,
+        /// Column of the problem, in characters, starting at 1
+        column: usize,
+        /// Description of the problem
+        message: String,
+    },
+    /// The single pass solver disagrees with the separate parts
+    Mismatch {
+        /// Part of the puzzle, 1 or 2
+        part: usize,
+        /// Answer from the separate part
+        separate: String,
+        /// Answer from the single pass
+        combined: String,
+    },
+}
+
+impl Error {
+    /// Error at a token inside of text
+    pub fn parse(text: &str, token: &str, message: impl Into<String>) -> Self {
+        let (line, column) = locate(text, token);
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Error at the end of text, for truncated input
+    pub fn truncated(text: &str, expected: &str) -> Self {
+        Error::parse(text, &text[text.len()..], format!("expected {}", expected))
+    }
+
+    /// Shift an error located in part to be relative to the enclosing text
+    pub fn within(self, text: &str, part: &str) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => {
+                let (part_line, part_column) = locate(text, part);
+                Error::Parse {
+                    line: part_line + line - 1,
+                    column: if line == 1 {
+                        part_column + column - 1
+                    } else {
+                        column
+                    },
+                    message,
+                }
+            }
+            error => error,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "failed to read input: {}", error),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Mismatch {
+                part,
+                separate,
+                combined,
+            } => write!(
+                f,
+                "part {} gave {}, but the combined solver gave {}",
+                part, separate, combined
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Locate token
+// -----------------------------------------------------------------------------
+// Line and column (1 based) of token in text; tokens not from text point at the end
+fn locate(text: &str, token: &str) -> (usize, usize) {
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|&offset| offset <= text.len())
+        .unwrap_or(text.len());
+    let before = text.get(..offset).unwrap_or(text);
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Parse helpers
+// -----------------------------------------------------------------------------
+/// Next token, or an error at the end of text
+pub fn next<'a>(
+    text: &'a str,
+    tokens: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> Result<&'a str, Error> {
+    tokens
+        .next()
+        .ok_or_else(|| Error::truncated(text, expected))
+}
+
+/// Number from a token, with the token location on failure
+pub fn number<T: std::str::FromStr>(text: &str, token: &str) -> Result<T, Error> {
+    token
+        .parse()
+        .map_err(|_| Error::parse(text, token, format!("invalid number '{}'", token)))
+}
+
+/// Parse each line of text
+pub fn lines<T: std::str::FromStr<Err = Error>>(text: &str) -> Result<Vec<T>, Error> {
+    text.lines()
+        .map(|line| {
+            line.parse()
+                .map_err(|error: Error| error.within(text, line))
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
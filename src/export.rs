@@ -0,0 +1,209 @@
+//! Export:
+//! Writes the results of a run as JSON or CSV, including every repetition.
+
+use crate::prelude::*;
+use crate::registry::Entry;
+use std::fmt::Write;
+
+// -----------------------------------------------------------------------------
+// Summary struct
+// -----------------------------------------------------------------------------
+/// Everything the driver knows about a day after all repetitions
+#[derive(Debug)]
+pub struct Summary<'a> {
+    /// Day that was run
+    pub day: &'a Entry,
+    /// Results of each repetition
+    pub samples: &'a [Results],
+    /// Average phase times over the repetitions that were kept
+    pub average: &'a Timing,
+    /// Statistics of the total time of each repetition
+    pub statistics: &'a Statistics,
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+/// Combined time, if the day has a combined solution
+fn combined(times: &Timing) -> Option<u128> {
+    times.combined.map(|combined| combined.as_nanos())
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(value) => value.to_string(),
+        Answer::Unsigned(value) => value.to_string(),
+        Answer::String(value) => json_string(value),
+        Answer::None => "null".to_string(),
+    }
+}
+
+// Timing fields, without the enclosing braces
+fn json_timing(times: &Timing) -> String {
+    format!(
+        "\"setup_ns\": {}, \"part_1_ns\": {}, \"part_2_ns\": {}, \"combined_ns\": {}",
+        times.setup.as_nanos(),
+        times.part_1.as_nanos(),
+        times.part_2.as_nanos(),
+        combined(times).map_or("null".to_string(), |time| time.to_string())
+    )
+}
+
+fn csv_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::None => String::new(),
+        _ => csv_field(&answer.to_string()),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// JSON
+// -----------------------------------------------------------------------------
+/// Results of all days as a JSON document
+pub fn json(summaries: &[Summary], warmup: u32, parallel: bool) -> String {
+    let mut output = String::new();
+    let mut line = |indent: usize, text: String| {
+        writeln!(output, "{}{}", " ".repeat(indent), text).unwrap();
+    };
+    let separator = |i: usize, length: usize| if i + 1 < length { "," } else { "" };
+    line(0, "{".to_string());
+    let mode = if parallel { "parallel" } else { "sequential" };
+    line(2, format!("\"mode\": \"{}\",", mode));
+    line(2, format!("\"warmup\": {},", warmup));
+    line(2, "\"days\": [".to_string());
+    for (i, summary) in summaries.iter().enumerate() {
+        let result = &summary.samples[0];
+        let statistics = summary.statistics;
+        line(4, "{".to_string());
+        line(6, format!("\"day\": {},", summary.day.number));
+        line(6, format!("\"name\": {},", json_string(summary.day.name)));
+        line(6, format!("\"part_1\": {},", json_answer(&result.part_1)));
+        line(6, format!("\"part_2\": {},", json_answer(&result.part_2)));
+        line(6, format!("\"repetitions\": {},", summary.samples.len()));
+        line(6, format!("\"outliers\": {},", statistics.rejected()));
+        line(
+            6,
+            format!("\"average\": {{{}}},", json_timing(summary.average)),
+        );
+        line(6, format!("\"mean_ns\": {},", statistics.mean.as_nanos()));
+        line(
+            6,
+            format!("\"std_dev_ns\": {},", statistics.std_dev.as_nanos()),
+        );
+        line(
+            6,
+            format!("\"median_ns\": {},", statistics.median.as_nanos()),
+        );
+        line(6, format!("\"p90_ns\": {},", statistics.p90.as_nanos()));
+        line(6, format!("\"min_ns\": {},", statistics.min.as_nanos()));
+        line(6, "\"samples\": [".to_string());
+        for (j, (sample, outlier)) in summary.samples.iter().zip(&statistics.outliers).enumerate() {
+            line(
+                8,
+                format!(
+                    "{{{}, \"outlier\": {}}}{}",
+                    json_timing(&sample.times),
+                    outlier,
+                    separator(j, summary.samples.len())
+                ),
+            );
+        }
+        line(6, "]".to_string());
+        line(4, format!("}}{}", separator(i, summaries.len())));
+    }
+    line(2, "],".to_string());
+    let total: std::time::Duration = summaries
+        .iter()
+        .map(|summary| summary.statistics.mean)
+        .sum();
+    line(2, format!("\"total_ns\": {}", total.as_nanos()));
+    line(0, "}".to_string());
+    output
+}
+
+// -----------------------------------------------------------------------------
+// CSV
+// -----------------------------------------------------------------------------
+/// One 'average' row per day, followed by a row for each repetition
+pub fn csv(summaries: &[Summary]) -> String {
+    let mut output = String::new();
+    writeln!(
+        output,
+        "day,name,sample,part_1,part_2,setup_ns,part_1_ns,part_2_ns,combined_ns,total_ns,\
+         std_dev_ns,median_ns,p90_ns,min_ns,outlier"
+    )
+    .unwrap();
+    for summary in summaries {
+        let result = &summary.samples[0];
+        let statistics = summary.statistics;
+        let row = |output: &mut String, sample: &str, times: &Timing, total: u128, extra: &str| {
+            writeln!(
+                output,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                summary.day.number,
+                csv_field(summary.day.name),
+                sample,
+                csv_answer(&result.part_1),
+                csv_answer(&result.part_2),
+                times.setup.as_nanos(),
+                times.part_1.as_nanos(),
+                times.part_2.as_nanos(),
+                combined(times).map_or(String::new(), |time| time.to_string()),
+                total,
+                extra
+            )
+            .unwrap();
+        };
+        row(
+            &mut output,
+            "average",
+            summary.average,
+            statistics.mean.as_nanos(),
+            &format!(
+                "{},{},{},{},",
+                statistics.std_dev.as_nanos(),
+                statistics.median.as_nanos(),
+                statistics.p90.as_nanos(),
+                statistics.min.as_nanos()
+            ),
+        );
+        for (j, (sample, outlier)) in summary.samples.iter().zip(&statistics.outliers).enumerate() {
+            let times = &sample.times;
+            let extra = format!(",,,,{}", outlier);
+            row(
+                &mut output,
+                &(j + 1).to_string(),
+                times,
+                times.total().as_nanos(),
+                &extra,
+            );
+        }
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
@@ -0,0 +1,293 @@
+//! Fuzz:
+//! Mutation fuzzing of the parsers, and optionally the solvers, of each day. A case starts
+//! from a generated input, has a few bytes or lines mutated, and is fed through the check
+//! and parser of the day in process; with `solve`, inputs that parse are also run through
+//! both parts and the combined solver. Each case has to finish, or come back as an
+//! `Error`, within the timeout. A panic, a hang or a combined solver that disagrees with
+//! the parts is a finding. Cases are seeded, so a finding can be replayed.
+
+use crate::{error::Error, generate, registry, Input};
+use generate::Rng;
+use std::sync::mpsc;
+use std::time::Duration;
+
+// Constants
+const BASE_SIZE: usize = 40;
+const MAX_MUTATIONS: usize = 4;
+const MAX_SPAN: usize = 8;
+const TOKENS: [&str; 22] = [
+    "",
+    "\n",
+    "\n\n",
+    " ",
+    "0",
+    "-",
+    "-1",
+    "+",
+    ":",
+    ",",
+    "#",
+    ".",
+    "x",
+    "a",
+    "(",
+    ")",
+    "|",
+    "\"",
+    "é",
+    "🎄",
+    "4294967296",
+    "99999999999999999999",
+];
+
+// -----------------------------------------------------------------------------
+// Finding struct
+// -----------------------------------------------------------------------------
+/// What went wrong with a fuzz case
+#[derive(Debug)]
+pub enum Failure {
+    /// The parser or solver panicked, with the panic message
+    Panic(String),
+    /// The parser or solver was still running after the timeout
+    Hang(Duration),
+    /// The combined solver disagreed with the separate parts
+    Mismatch(String),
+}
+
+/// Fuzz case the parser of a day did not handle
+#[derive(Debug)]
+pub struct Finding {
+    /// Day of December
+    pub day: usize,
+    /// Seed of the generated input the case started from
+    pub seed: u64,
+    /// Index of the case for that seed, see [`case`]
+    pub case: usize,
+    /// The mutated input
+    pub input: String,
+    /// Panic or hang
+    pub failure: Failure,
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Dec {:02}, seed {}, case {}: ",
+            self.day, self.seed, self.case
+        )?;
+        match &self.failure {
+            Failure::Panic(message) => write!(f, "panicked with '{}'", message),
+            Failure::Hang(timeout) => write!(f, "still running after {:?}", timeout),
+            Failure::Mismatch(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Counts of the fuzz cases for a day that were accepted and rejected
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Tally {
+    /// Cases that parsed, and were solved when solving
+    pub parsed: usize,
+    /// Cases that came back as an error
+    pub rejected: usize,
+}
+
+// -----------------------------------------------------------------------------
+// Mutate
+// -----------------------------------------------------------------------------
+// A few bytes or lines changed, so most of the structure is still there for the parser
+// to get partway into
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>) {
+    let position = rng.below(input.len() + 1);
+    let span = std::cmp::min(rng.range(1..=MAX_SPAN), input.len() - position);
+    match rng.below(10) {
+        // Replace a byte
+        0 if position < input.len() => input[position] = rng.range(0x20..=0x7e) as u8,
+        // Insert a token
+        1 => {
+            let token = rng.choose(&TOKENS).as_bytes();
+            input.splice(position..position, token.iter().copied());
+        }
+        // Delete a span
+        2 => drop(input.drain(position..position + span)),
+        // Duplicate a span
+        3 => {
+            let copy = input[position..position + span].to_vec();
+            input.splice(position..position, copy);
+        }
+        // Truncate
+        4 => input.truncate(position),
+        // Replace a number with a token
+        5 => {
+            let start = input[..position]
+                .iter()
+                .rposition(|c| !c.is_ascii_digit())
+                .map_or(0, |i| i + 1);
+            let end = input[position..]
+                .iter()
+                .position(|c| !c.is_ascii_digit())
+                .map_or(input.len(), |i| position + i);
+            let token = rng.choose(&TOKENS).as_bytes();
+            input.splice(start..end, token.iter().copied());
+        }
+        // Replace one or two words with the ones at the start of a line, which often name
+        // what the line defines, so references like bag colors or rule numbers point at
+        // another definition or back at their own line
+        9 => {
+            let starts: Vec<usize> = std::iter::once(0)
+                .chain(
+                    (0..input.len())
+                        .filter(|&i| input[i] == b'\n')
+                        .map(|i| i + 1),
+                )
+                .collect();
+            let count = rng.range(1..=2);
+            let source = words(input, *rng.choose(&starts), count);
+            let copy = input[source].to_vec();
+            input.splice(words(input, position, count), copy);
+        }
+        // Line edits
+        kind => {
+            let mut lines: Vec<Vec<u8>> =
+                input.split(|&c| c == b'\n').map(<[u8]>::to_vec).collect();
+            let i = rng.below(lines.len());
+            let j = rng.below(lines.len());
+            match kind {
+                6 => drop(lines.remove(i)),
+                7 => lines.insert(i, lines[j].clone()),
+                _ => lines.swap(i, j),
+            }
+            *input = lines.join(&b'\n');
+        }
+    }
+}
+
+// Run of letters and digits around a position, and up to `count - 1` more after it that
+// are each separated by a space
+fn words(input: &[u8], position: usize, count: usize) -> std::ops::Range<usize> {
+    let end_of_word = |start: usize| {
+        input[start..]
+            .iter()
+            .position(|c| !c.is_ascii_alphanumeric())
+            .map_or(input.len(), |i| start + i)
+    };
+    let start = input[..position]
+        .iter()
+        .rposition(|c| !c.is_ascii_alphanumeric())
+        .map_or(0, |i| i + 1);
+    let mut end = end_of_word(position);
+    for _ in 1..count {
+        if input.get(end) != Some(&b' ') {
+            break;
+        }
+        end = end_of_word(end + 1);
+    }
+    start..end
+}
+
+// Generated input the cases for a seed start from
+fn base(day: usize, seed: u64) -> Option<String> {
+    let size = std::cmp::min(*generate::SIZES.get(day.wrapping_sub(1))?, BASE_SIZE);
+    generate::input(day, seed, size)
+}
+
+fn mutated(base: &str, seed: u64, index: usize) -> String {
+    let mut input = base.as_bytes().to_vec();
+    let mut rng = Rng::new((seed << 32) ^ index as u64);
+    for _ in 0..rng.range(1..=MAX_MUTATIONS) {
+        mutate(&mut rng, &mut input);
+    }
+    // Mutations can split a character, like any other corrupted file
+    Input::normalize(String::from_utf8_lossy(&input).into_owned())
+}
+
+/// Fuzz case for a seed and index, the same each time; `None` for an unknown day
+pub fn case(day: usize, seed: u64, index: usize) -> Option<String> {
+    Some(mutated(&base(day, seed)?, seed, index))
+}
+
+// -----------------------------------------------------------------------------
+// Fuzz
+// -----------------------------------------------------------------------------
+enum Event {
+    Start(u64, usize),
+    Failure(Failure),
+    Done(Tally),
+}
+
+/// Parse `cases` mutated inputs for each seed, and with `solve` run both parts and the
+/// combined solver on the ones that parse; the tally of the cases, or the first one that
+/// panicked, ran past the timeout or got different answers from the combined solver. A
+/// case that hangs is left running on its own thread, so the caller should not carry on
+/// for long after a hang.
+pub fn fuzz(
+    day: usize,
+    seeds: std::ops::Range<u64>,
+    cases: usize,
+    timeout: Duration,
+    solve: bool,
+) -> Result<Tally, Box<Finding>> {
+    let entry = registry::get(day).expect("day not registered");
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut tally = Tally::default();
+        for seed in seeds {
+            let base = base(day, seed).unwrap();
+            for index in 0..cases {
+                let input = mutated(&base, seed, index);
+                if sender.send(Event::Start(seed, index)).is_err() {
+                    return;
+                }
+                let outcome = std::panic::catch_unwind(|| match solve {
+                    true => (entry.run)(&Input::Buffer(input.clone())).map(drop),
+                    false => (entry.parse)(&input),
+                });
+                match outcome {
+                    Ok(Ok(())) => tally.parsed += 1,
+                    Ok(Err(error @ Error::Mismatch { .. })) => {
+                        let _ = sender.send(Event::Failure(Failure::Mismatch(error.to_string())));
+                        return;
+                    }
+                    Ok(Err(_)) => tally.rejected += 1,
+                    Err(payload) => {
+                        let message = match payload.downcast_ref::<&str>() {
+                            Some(message) => message.to_string(),
+                            None => payload
+                                .downcast_ref::<String>()
+                                .cloned()
+                                .unwrap_or_default(),
+                        };
+                        let _ = sender.send(Event::Failure(Failure::Panic(message)));
+                        return;
+                    }
+                }
+            }
+        }
+        let _ = sender.send(Event::Done(tally));
+    });
+
+    // Each case has to finish within the timeout
+    let mut current = (0, 0);
+    let failure = loop {
+        match receiver.recv_timeout(timeout) {
+            Ok(Event::Start(seed, index)) => current = (seed, index),
+            Ok(Event::Done(tally)) => return Ok(tally),
+            Ok(Event::Failure(failure)) => break failure,
+            Err(mpsc::RecvTimeoutError::Timeout) => break Failure::Hang(timeout),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                break Failure::Panic("fuzz thread stopped".to_string())
+            }
+        }
+    };
+    let (seed, index) = current;
+    Err(Box::new(Finding {
+        day,
+        seed,
+        case: index,
+        input: case(day, seed, index).unwrap(),
+        failure,
+    }))
+}
+
+// -----------------------------------------------------------------------------
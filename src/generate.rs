@@ -0,0 +1,1491 @@
+//! Generate:
+//! Random puzzle inputs from a seed. They have the same structure as the puzzle inputs
+//! and keep the guarantees the puzzles make, like exactly one corrupted instruction on day
+//! 8 or a square of tiles with unique edges on day 20. The size scales the input, usually
+//! the number of lines; a few days clamp it to what the puzzle or the parser allows, and
+//! days 23 and 25, which have a fixed input shape, ignore it.
+
+use crate::registry::NUMBER_DAYS;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+// Constants
+/// Size of my own puzzle input for each day, the default size for [`input`]
+pub const SIZES: [usize; NUMBER_DAYS] = [
+    200, 1000, 323, 290, 814, 490, 594, 656, 1000, 99, 94, 771, 9, 100, 6, 240, 8, 373, 470, 12,
+    38, 50, 9, 394, 2,
+];
+const GENERATORS: [fn(&mut Rng, usize) -> String; NUMBER_DAYS] = [
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+];
+
+// -----------------------------------------------------------------------------
+// Random number generator
+// -----------------------------------------------------------------------------
+/// Small seeded random number generator, SplitMix64
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Generator for a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next random 64 bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random value below a positive bound
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Random value in a non-empty inclusive range
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    /// True with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < probability
+    }
+
+    /// Random element of a non-empty slice
+    pub fn choose<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.below(values.len())]
+    }
+
+    /// Shuffle a slice in place
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Input
+// -----------------------------------------------------------------------------
+/// Random input for a day, the same for the same seed and size; `None` for an unknown day
+pub fn input(day: usize, seed: u64, size: usize) -> Option<String> {
+    let generate = GENERATORS.get(day.wrapping_sub(1))?;
+    Some(generate(&mut Rng::new(seed), std::cmp::max(size, 1)))
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+fn lines<T: std::fmt::Display>(values: impl IntoIterator<Item = T>) -> String {
+    let mut output = String::new();
+    for value in values {
+        writeln!(output, "{}", value).unwrap();
+    }
+    output
+}
+
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.below(26) as u8) as char
+}
+
+// Grid of 'on' and 'off' characters
+fn grid(rng: &mut Rng, rows: usize, columns: usize, density: f64, on: char, off: char) -> String {
+    let mut output = String::new();
+    for _ in 0..rows {
+        for _ in 0..columns {
+            output.push(if rng.chance(density) { on } else { off });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// Day 1
+// -----------------------------------------------------------------------------
+// Exactly one pair and one triple sum to 2020. Only the four small values are at most
+// 1010, and the large values avoid the few numbers that would complete another sum.
+fn day01(rng: &mut Rng, size: usize) -> String {
+    const YEAR: usize = 2020;
+    let size = size.clamp(5, 900);
+    loop {
+        let pair = rng.range(1..=1009);
+        let first = rng.range(2..=1009);
+        let second = rng.range(1011 - first..=1009);
+        let small = [pair, first, second, YEAR - first - second];
+        let mut forbidden = [false; YEAR + 1];
+        for &a in small.iter() {
+            forbidden[YEAR - a] = true;
+            for &b in small.iter().filter(|&&b| a + b < YEAR) {
+                forbidden[YEAR - a - b] = true;
+            }
+        }
+        let mut values: Vec<usize> = (1011..=YEAR).filter(|&value| !forbidden[value]).collect();
+        rng.shuffle(&mut values);
+        values.truncate(size - 5);
+        values.extend_from_slice(&small);
+        values.push(YEAR - pair);
+        if count_sums(&values, YEAR) == (1, 1) {
+            rng.shuffle(&mut values);
+            return lines(values);
+        }
+    }
+}
+
+// Pairs and triples that sum to the total, or (0, 0) if there are repeated values
+fn count_sums(values: &[usize], total: usize) -> (usize, usize) {
+    let mut mask = vec![false; total + 1];
+    for &value in values {
+        if mask[value] {
+            return (0, 0);
+        }
+        mask[value] = true;
+    }
+    let pairs = (1..=total / 2)
+        .filter(|&i| mask[i] && mask[total - i])
+        .count();
+    let mut triples = 0;
+    for i in (1..=total / 3).filter(|&i| mask[i]) {
+        for j in (i..=(total - i) / 2).filter(|&j| mask[j]) {
+            if mask[total - i - j] {
+                triples += 1;
+            }
+        }
+    }
+    (pairs, triples)
+}
+
+// -----------------------------------------------------------------------------
+// Day 2
+// -----------------------------------------------------------------------------
+fn day02(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let lower = rng.range(1..=15);
+        let upper = rng.range(lower + 1..=20);
+        let required = letter(rng);
+        let password: String = (0..rng.range(upper..=upper + 4))
+            .map(|_| {
+                if rng.chance(0.4) {
+                    required
+                } else {
+                    letter(rng)
+                }
+            })
+            .collect();
+        writeln!(output, "{}-{} {}: {}", lower, upper, required, password).unwrap();
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// Day 3
+// -----------------------------------------------------------------------------
+fn day03(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, 31, 0.2, '#', '.')
+}
+
+// -----------------------------------------------------------------------------
+// Day 4
+// -----------------------------------------------------------------------------
+// Passports with missing fields and out of range values
+fn day04(rng: &mut Rng, size: usize) -> String {
+    const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+    const OTHER_COLORS: [&str; 5] = ["gmt", "utc", "xry", "zzz", "dne"];
+    let mut passports = vec![];
+    for _ in 0..size {
+        let mut fields = vec![];
+        let mut year = |rng: &mut Rng, name: &str, valid: RangeInclusive<usize>| {
+            let (start, end) = (*valid.start(), *valid.end());
+            if rng.chance(0.9) {
+                let value = match rng.chance(0.8) {
+                    true => rng.range(valid),
+                    false => rng.range(start - 20..=end + 20),
+                };
+                fields.push(format!("{}:{}", name, value));
+            }
+        };
+        year(rng, "byr", 1920..=2002);
+        year(rng, "iyr", 2010..=2020);
+        year(rng, "eyr", 2020..=2030);
+        let valid = rng.chance(0.8);
+        let values = [
+            match (valid, rng.chance(0.5)) {
+                (true, true) => format!("{}cm", rng.range(150..=193)),
+                (true, false) => format!("{}in", rng.range(59..=76)),
+                (false, true) => format!("{}cm", rng.range(100..=220)),
+                (false, false) => rng.range(50..=200).to_string(),
+            },
+            match valid {
+                true => format!("#{:06x}", rng.below(1 << 24)),
+                false => format!("{:06x}", rng.below(1 << 24)),
+            },
+            match valid {
+                true => rng.choose(&EYE_COLORS).to_string(),
+                false => rng.choose(&OTHER_COLORS).to_string(),
+            },
+            match valid {
+                true => format!("{:09}", rng.below(1_000_000_000)),
+                false => format!("{:010}", rng.below(1_000_000_000)),
+            },
+        ];
+        for (name, value) in ["hgt", "hcl", "ecl", "pid"].iter().zip(values.iter()) {
+            if rng.chance(0.9) {
+                fields.push(format!("{}:{}", name, value));
+            }
+        }
+        if fields.is_empty() || rng.chance(0.7) {
+            fields.push(format!("cid:{}", rng.range(100..=350)));
+        }
+        rng.shuffle(&mut fields);
+        let mut passport = fields[0].clone();
+        for field in &fields[1..] {
+            passport.push(if rng.chance(0.6) { ' ' } else { '\n' });
+            passport.push_str(field);
+        }
+        passports.push(passport);
+    }
+    passports.join("\n\n") + "\n"
+}
+
+// -----------------------------------------------------------------------------
+// Day 5
+// -----------------------------------------------------------------------------
+// A contiguous block of seats with one missing from the middle
+fn day05(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(3, 1000);
+    let first = rng.range(1..=1023 - size);
+    let missing = rng.range(first + 1..=first + size - 1);
+    let mut seats: Vec<usize> = (first..=first + size)
+        .filter(|&seat| seat != missing)
+        .collect();
+    rng.shuffle(&mut seats);
+    lines(seats.iter().map(|seat| {
+        (0..10)
+            .map(|bit| match (bit < 7, seat >> (9 - bit) & 1 == 1) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            })
+            .collect::<String>()
+    }))
+}
+
+// -----------------------------------------------------------------------------
+// Day 6
+// -----------------------------------------------------------------------------
+fn day06(rng: &mut Rng, size: usize) -> String {
+    let mut groups = vec![];
+    for _ in 0..size {
+        let mut pool: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut pool);
+        pool.truncate(rng.range(1..=20));
+        let people: Vec<String> = (0..rng.range(1..=5))
+            .map(|_| {
+                let mut answers: String = pool.iter().filter(|_| rng.chance(0.7)).collect();
+                if answers.is_empty() {
+                    answers.push(*rng.choose(&pool));
+                }
+                answers
+            })
+            .collect();
+        groups.push(people.join("\n"));
+    }
+    groups.join("\n\n") + "\n"
+}
+
+// -----------------------------------------------------------------------------
+// Day 7
+// -----------------------------------------------------------------------------
+const ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+const COLORS: [&str; 33] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "tomato",
+    "turquoise",
+    "violet",
+    "white",
+    "yellow",
+];
+
+// Bags are given levels and only hold bags from deeper levels, so the rules have no
+// cycles and the count for part 2 stays reasonable
+fn day07(rng: &mut Rng, size: usize) -> String {
+    const LEVELS: usize = 6;
+    let mut names: Vec<String> = ADJECTIVES
+        .iter()
+        .flat_map(|adjective| {
+            COLORS
+                .iter()
+                .map(move |color| format!("{} {}", adjective, color))
+        })
+        .filter(|name| name != "shiny gold")
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(size.clamp(2, names.len() + 1) - 1);
+    names.push("shiny gold".to_string());
+    let gold = names.len() - 1;
+    let mut levels: Vec<usize> = names.iter().map(|_| rng.range(0..=LEVELS)).collect();
+    levels[gold] = rng.range(1..=3);
+    let mut rules = vec![];
+    for (i, name) in names.iter().enumerate() {
+        let mut inside: Vec<usize> = (0..names.len())
+            .filter(|&j| levels[j] > levels[i])
+            .collect();
+        let number = if inside.is_empty() || (i != gold && rng.chance(0.1)) {
+            0
+        } else {
+            rng.range(1..=std::cmp::min(4, inside.len()))
+        };
+        rng.shuffle(&mut inside);
+        let contents: Vec<String> = inside[..number]
+            .iter()
+            .map(|&j| {
+                let count = rng.range(1..=5);
+                let plural = if count == 1 { "" } else { "s" };
+                format!("{} {} bag{}", count, names[j], plural)
+            })
+            .collect();
+        match contents.is_empty() {
+            true => rules.push(format!("{} bags contain no other bags.", name)),
+            false => rules.push(format!("{} bags contain {}.", name, contents.join(", "))),
+        }
+    }
+    rng.shuffle(&mut rules);
+    lines(rules)
+}
+
+// -----------------------------------------------------------------------------
+// Day 8
+// -----------------------------------------------------------------------------
+// Boot code with exactly one corrupted instruction, laid out in blocks that end with a
+// jump. The blocks of the boot path loop back on themselves and the unused blocks jump
+// into that loop. The way out is a chain of blocks at the end of the code, reached by
+// falling through from the block in front of it, or by a corrupted no-op that points
+// there. Generated programs are checked by trying every change.
+fn day08(rng: &mut Rng, size: usize) -> String {
+    let size = std::cmp::max(size, 20);
+    loop {
+        let program = boot_code(rng, size);
+        if fixes(&program) == 1 {
+            return lines(
+                program
+                    .iter()
+                    .map(|(operation, value)| format!("{} {:+}", operation, value)),
+            );
+        }
+    }
+}
+
+fn boot_code(rng: &mut Rng, size: usize) -> Vec<(&'static str, i64)> {
+    // Blocks
+    let mut starts = vec![];
+    let mut total = 0;
+    while total < size {
+        starts.push(total);
+        total = std::cmp::min(size, total + rng.range(1..=5));
+    }
+    starts.push(size);
+    let number_blocks = starts.len() - 1;
+    let end = |block: usize| starts[block + 1] - 1;
+    let exits = std::cmp::max(1, number_blocks / 5);
+    let guard = number_blocks - exits - 1;
+    let exit = starts[guard + 1];
+
+    // Boot path and unused blocks
+    let mut path = vec![0];
+    let mut unused = vec![];
+    for block in 1..guard {
+        match rng.chance(0.5) {
+            true => path.push(block),
+            false => unused.push(block),
+        }
+    }
+    rng.shuffle(&mut path[1..]);
+    let hosts: Vec<usize> = path
+        .iter()
+        .copied()
+        .filter(|&block| end(block) > starts[block])
+        .collect();
+    let corrupted_nop = !hosts.is_empty() && rng.chance(0.5);
+    match corrupted_nop {
+        true => unused.push(guard),
+        false => path.insert(rng.range(1..=path.len()), guard),
+    }
+
+    // Accumulators and no-ops, with no-ops outside the way out pointing before it
+    let mut program = vec![("acc", 0); size];
+    for (block, bounds) in starts.windows(2).enumerate() {
+        let body = if block + 1 == number_blocks {
+            bounds[0]..size
+        } else {
+            bounds[0]..bounds[1] - 1
+        };
+        let limit = if block > guard { size } else { exit };
+        for i in body {
+            program[i] = match rng.chance(0.7) {
+                true => ("acc", rng.range(0..=99) as i64 - 49),
+                false => ("nop", rng.below(limit) as i64 - i as i64),
+            };
+        }
+    }
+
+    // Jumps
+    let mut jump = |from: usize, to: usize| {
+        let i = end(from);
+        program[i] = ("jmp", starts[to] as i64 - i as i64);
+    };
+    for pair in path.windows(2) {
+        jump(pair[0], pair[1]);
+    }
+    jump(path[path.len() - 1], *rng.choose(&path));
+    for &block in &unused {
+        jump(block, *rng.choose(&path));
+    }
+    let mut way_out: Vec<usize> = (guard + 1..number_blocks).collect();
+    let length = way_out.len();
+    if length > 2 {
+        rng.shuffle(&mut way_out[1..length - 1]);
+    }
+    for pair in way_out.windows(2) {
+        jump(pair[0], pair[1]);
+    }
+    if corrupted_nop {
+        let block = *rng.choose(&hosts);
+        let i = rng.range(starts[block]..=end(block) - 1);
+        program[i] = ("nop", exit as i64 - i as i64);
+    }
+    program
+}
+
+// Number of single instruction changes that let the boot code finish
+fn fixes(program: &[(&str, i64)]) -> usize {
+    let finishes = |changed: usize| {
+        let mut executed = vec![false; program.len()];
+        let mut current = 0;
+        while current < program.len() as i64 && !executed[current as usize] {
+            let i = current as usize;
+            executed[i] = true;
+            let jump = (program[i].0 == "jmp") ^ (i == changed);
+            current += if jump { program[i].1 } else { 1 };
+        }
+        current == program.len() as i64
+    };
+    (0..program.len())
+        .filter(|&i| program[i].0 != "acc" && finishes(i))
+        .count()
+}
+
+// -----------------------------------------------------------------------------
+// Day 9
+// -----------------------------------------------------------------------------
+// Each number is the sum of two of the smallest numbers in the window, as picking from
+// the whole window makes them overflow after a few hundred lines. The invalid number is
+// the sum of a run of earlier numbers, and no other run adds up to it.
+fn day09(rng: &mut Rng, size: usize) -> String {
+    const WINDOW: usize = 25;
+    let size = size.clamp(WINDOW + 10, 1200);
+    'retry: loop {
+        let mut values: Vec<i64> = (1..=50).collect();
+        rng.shuffle(&mut values);
+        values.truncate(WINDOW);
+        let invalid = rng.range(size / 2..=size - 1);
+        while values.len() < size {
+            let i = values.len();
+            let mut window = values[i - WINDOW..i].to_vec();
+            if i == invalid {
+                let length = rng.range(2..=10);
+                let start = rng.range(i.saturating_sub(60)..=i - length);
+                let target = values[start..start + length].iter().sum();
+                if window
+                    .iter()
+                    .enumerate()
+                    .any(|(j, &a)| window[j + 1..].contains(&(target - a)))
+                {
+                    continue 'retry;
+                }
+                values.push(target);
+            } else {
+                window.sort_unstable();
+                window.dedup();
+                let smallest = &window[..std::cmp::min(4, window.len())];
+                let a = rng.below(smallest.len());
+                let b = (a + rng.range(1..=smallest.len() - 1)) % smallest.len();
+                values.push(smallest[a] + smallest[b]);
+            }
+        }
+        if count_runs(&values, values[invalid]) == 1 {
+            return lines(values);
+        }
+    }
+}
+
+// Contiguous runs of at least two positive values that sum to the target
+fn count_runs(values: &[i64], target: i64) -> usize {
+    let (mut start, mut sum, mut count) = (0, 0, 0);
+    for end in 0..values.len() {
+        sum += values[end];
+        while sum > target {
+            sum -= values[start];
+            start += 1;
+        }
+        if sum == target && end > start {
+            count += 1;
+        }
+    }
+    count
+}
+
+// -----------------------------------------------------------------------------
+// Day 10
+// -----------------------------------------------------------------------------
+// Steps of one and three jolts, with runs of at most four single steps as in the puzzle.
+// Runs that would overflow the number of arrangements are cut short.
+fn day10(rng: &mut Rng, size: usize) -> String {
+    const ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+    let mut arrangements: u64 = 1;
+    let mut joltage = 0;
+    let mut adapters = vec![];
+    while adapters.len() < size {
+        let mut run = rng.range(0..=4);
+        match arrangements.checked_mul(ARRANGEMENTS[run]) {
+            Some(product) if product <= i64::MAX as u64 => arrangements = product,
+            _ => run = 1,
+        }
+        for step in std::iter::repeat_n(1, run).chain(std::iter::once(3)) {
+            joltage += step;
+            adapters.push(joltage);
+        }
+    }
+    adapters.truncate(size);
+    rng.shuffle(&mut adapters);
+    lines(adapters)
+}
+
+// -----------------------------------------------------------------------------
+// Day 11
+// -----------------------------------------------------------------------------
+// Some seat maps flip back and forth forever, but the puzzle promises they settle, so
+// seats that are still changing after a while are turned into floor a few at a time. The
+// parser holds 16,384 seats, including a border of floor.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let side = std::cmp::min(size, 126);
+    let map = grid(rng, side, side, 0.85, 'L', '.');
+    let mut map: Vec<Vec<u8>> = map.lines().map(|line| line.bytes().collect()).collect();
+    loop {
+        let mut unsettled = unsettled_seats(&map, 4, false);
+        unsettled.extend(unsettled_seats(&map, 5, true));
+        if unsettled.is_empty() {
+            return lines(
+                map.iter()
+                    .map(|row| String::from_utf8_lossy(row).into_owned()),
+            );
+        }
+        rng.shuffle(&mut unsettled);
+        for &(row, column) in &unsettled[..unsettled.len().div_ceil(8)] {
+            map[row][column] = b'.';
+        }
+    }
+}
+
+// Seats that still change after many rounds, with the neighbors in sight for part 2
+fn unsettled_seats(map: &[Vec<u8>], crowded: usize, in_sight: bool) -> Vec<(usize, usize)> {
+    const ROUNDS: usize = 200;
+    let (rows, columns) = (map.len() as i32, map[0].len() as i32);
+    let inside = |row: i32, column: i32| (0..rows).contains(&row) && (0..columns).contains(&column);
+    let chairs: Vec<(usize, usize)> = (0..map.len())
+        .flat_map(|row| (0..map[0].len()).map(move |column| (row, column)))
+        .filter(|&(row, column)| map[row][column] == b'L')
+        .collect();
+    let neighbors: Vec<Vec<(usize, usize)>> = chairs
+        .iter()
+        .map(|&(row, column)| {
+            let directions = (-1..=1).flat_map(|d| (-1..=1).map(move |e| (d, e)));
+            directions
+                .filter(|&direction| direction != (0, 0))
+                .filter_map(|(d_row, d_column)| {
+                    let (mut r, mut c) = (row as i32 + d_row, column as i32 + d_column);
+                    while in_sight && inside(r, c) && map[r as usize][c as usize] == b'.' {
+                        r += d_row;
+                        c += d_column;
+                    }
+                    match inside(r, c) {
+                        true => Some((r as usize, c as usize)),
+                        false => None,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let mut occupied = vec![vec![false; map[0].len()]; map.len()];
+    let mut changes = vec![];
+    for _ in 0..ROUNDS {
+        changes = chairs
+            .iter()
+            .zip(&neighbors)
+            .filter(|&(&(row, column), neighbors)| {
+                let count = neighbors.iter().filter(|&&(r, c)| occupied[r][c]).count();
+                match occupied[row][column] {
+                    true => count >= crowded,
+                    false => count == 0,
+                }
+            })
+            .map(|(&seat, _)| seat)
+            .collect();
+        if changes.is_empty() {
+            break;
+        }
+        for &(row, column) in &changes {
+            occupied[row][column] = !occupied[row][column];
+        }
+    }
+    changes
+}
+
+// -----------------------------------------------------------------------------
+// Day 12
+// -----------------------------------------------------------------------------
+// Navigation that keeps both ships and the waypoint within the 16 bit positions the
+// solver uses; moves that would stray too far are drawn again
+#[derive(Debug, Clone, Copy)]
+struct Ferry {
+    ship: (i32, i32),
+    heading: (i32, i32),
+    ship_2: (i32, i32),
+    waypoint: (i32, i32),
+}
+
+impl Ferry {
+    fn navigate(mut self, action: char, value: i32) -> Self {
+        let left = |(east, north): (i32, i32)| (-north, east);
+        let (east, north) = match action {
+            'N' => (0, value),
+            'S' => (0, -value),
+            'E' => (value, 0),
+            'W' => (-value, 0),
+            _ => (0, 0),
+        };
+        self.ship = (self.ship.0 + east, self.ship.1 + north);
+        self.waypoint = (self.waypoint.0 + east, self.waypoint.1 + north);
+        match action {
+            'L' | 'R' => {
+                let turns = if action == 'L' {
+                    value / 90
+                } else {
+                    4 - value / 90
+                };
+                for _ in 0..turns {
+                    self.heading = left(self.heading);
+                    self.waypoint = left(self.waypoint);
+                }
+            }
+            'F' => {
+                self.ship.0 += value * self.heading.0;
+                self.ship.1 += value * self.heading.1;
+                self.ship_2.0 += value * self.waypoint.0;
+                self.ship_2.1 += value * self.waypoint.1;
+            }
+            _ => (),
+        }
+        self
+    }
+
+    fn in_range(&self) -> bool {
+        [self.ship, self.ship_2]
+            .iter()
+            .all(|(east, north)| east.abs() <= 10_000 && north.abs() <= 10_000)
+            && self.waypoint.0.abs() <= 300
+            && self.waypoint.1.abs() <= 300
+    }
+}
+
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut ferry = Ferry {
+        ship: (0, 0),
+        heading: (1, 0),
+        ship_2: (0, 0),
+        waypoint: (10, 1),
+    };
+    let mut output = String::new();
+    for _ in 0..size {
+        let mut instruction = ('L', 180);
+        for _ in 0..8 {
+            let candidate = match rng.below(10) {
+                0..=2 => ('F', rng.range(1..=100) as i32),
+                3..=6 => (*rng.choose(&['N', 'S', 'E', 'W']), rng.range(1..=5) as i32),
+                _ => (*rng.choose(&['L', 'R']), *rng.choose(&[90, 90, 180, 270])),
+            };
+            if ferry.navigate(candidate.0, candidate.1).in_range() {
+                instruction = candidate;
+                break;
+            }
+        }
+        ferry = ferry.navigate(instruction.0, instruction.1);
+        writeln!(output, "{}{}", instruction.0, instruction.1).unwrap();
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// Day 13
+// -----------------------------------------------------------------------------
+// Buses with distinct prime IDs, as few as it takes to keep the products in the
+// Chinese Remainder Theorem within 64 bits, and a single earliest bus
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let size = std::cmp::max(size, 2);
+    let mut primes: Vec<u64> = (11..1000_u64)
+        .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect();
+    loop {
+        rng.shuffle(&mut primes);
+        let mut buses: Vec<u64> = vec![];
+        let mut product: u64 = 1;
+        for &prime in &primes {
+            let largest = buses.iter().fold(prime, |acc, &bus| acc.max(bus));
+            match product
+                .checked_mul(prime)
+                .and_then(|product| product.checked_mul(largest))
+            {
+                Some(bound) if bound < 1 << 58 && buses.len() < size => {
+                    product *= prime;
+                    buses.push(prime);
+                }
+                _ => (),
+            }
+        }
+        let earliest = rng.range(100_000..=1_000_000) as u64;
+        let mut waits: Vec<u64> = buses.iter().map(|bus| bus - earliest % bus).collect();
+        waits.sort_unstable();
+        if buses.iter().any(|bus| earliest.is_multiple_of(*bus)) || waits[0] == waits[1] {
+            continue;
+        }
+        let mut entries = vec![];
+        for (i, bus) in buses.iter().enumerate() {
+            if i > 0 {
+                entries.extend(std::iter::repeat_n("x".to_string(), rng.range(0..=30)));
+            }
+            entries.push(bus.to_string());
+        }
+        return format!("{}\n{}\n", earliest, entries.join(","));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Day 14
+// -----------------------------------------------------------------------------
+fn day14(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let floating = rng.range(2..=9);
+        let mut mask: Vec<char> = (0..36)
+            .map(|i| match i < floating {
+                true => 'X',
+                false if rng.chance(0.5) => '1',
+                false => '0',
+            })
+            .collect();
+        rng.shuffle(&mut mask);
+        writeln!(output, "mask = {}", mask.iter().collect::<String>()).unwrap();
+        for _ in 0..rng.range(1..=6) {
+            let (address, value) = (rng.below(1 << 16), rng.below(1 << 30));
+            writeln!(output, "mem[{}] = {}", address, value).unwrap();
+        }
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// Day 15
+// -----------------------------------------------------------------------------
+fn day15(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, 2020);
+    let mut values: Vec<usize> = (0..(3 * size).clamp(20, 2020)).collect();
+    rng.shuffle(&mut values);
+    let values: Vec<String> = values[..size]
+        .iter()
+        .map(|value| value.to_string())
+        .collect();
+    format!("{}\n", values.join(","))
+}
+
+// -----------------------------------------------------------------------------
+// Day 16
+// -----------------------------------------------------------------------------
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+// Each field has a gap in its ranges that no other gap overlaps. The fields are matched
+// in a random order, and the position of each field holds the gap values of every field
+// matched after it, so the positions can be resolved one at a time. The parser holds 256
+// nearby tickets.
+fn day16(rng: &mut Rng, size: usize) -> String {
+    let number_fields = FIELDS.len();
+    let size = size.clamp(number_fields + 4, 256);
+    let gaps: Vec<(usize, usize)> = (0..number_fields)
+        .map(|field| {
+            let start = 100 + 40 * field + rng.below(20);
+            (start, start + rng.below(20))
+        })
+        .collect();
+    let mut output = String::new();
+    for (name, &(start, end)) in FIELDS.iter().zip(&gaps) {
+        let (lower, upper) = (rng.range(25..=50), rng.range(950..=974));
+        writeln!(
+            output,
+            "{}: {}-{} or {}-{}",
+            name,
+            lower,
+            start - 1,
+            end + 1,
+            upper
+        )
+        .unwrap();
+    }
+    let open = |rng: &mut Rng| loop {
+        let value = rng.range(51..=949);
+        if gaps
+            .iter()
+            .all(|&(start, end)| value < start || value > end)
+        {
+            return value;
+        }
+    };
+
+    // Tickets
+    let mut order: Vec<usize> = (0..number_fields).collect();
+    let mut positions: Vec<usize> = (0..number_fields).collect();
+    rng.shuffle(&mut order);
+    rng.shuffle(&mut positions);
+    let number_valid = size - std::cmp::min(size / 5, size - number_fields);
+    let mut tickets: Vec<Vec<usize>> = (0..size)
+        .map(|_| (0..number_fields).map(|_| open(rng)).collect())
+        .collect();
+    for rank in 0..number_fields {
+        let mut holders: Vec<usize> = (0..number_valid).collect();
+        rng.shuffle(&mut holders);
+        for (later, &ticket) in (rank + 1..number_fields).zip(&holders) {
+            let (start, end) = gaps[order[later]];
+            tickets[ticket][positions[rank]] = rng.range(start..=end);
+        }
+    }
+    for ticket in &mut tickets[number_valid..] {
+        ticket[rng.below(number_fields)] = match rng.chance(0.5) {
+            true => rng.range(0..=24),
+            false => rng.range(975..=999),
+        };
+    }
+    rng.shuffle(&mut tickets);
+    let join = |ticket: &[usize]| {
+        let values: Vec<String> = ticket.iter().map(|value| value.to_string()).collect();
+        values.join(",")
+    };
+    let mine: Vec<usize> = (0..number_fields).map(|_| open(rng)).collect();
+    writeln!(output, "\nyour ticket:\n{}\n\nnearby tickets:", join(&mine)).unwrap();
+    output + &lines(tickets.iter().map(|ticket| join(ticket)))
+}
+
+// -----------------------------------------------------------------------------
+// Day 17
+// -----------------------------------------------------------------------------
+// The parser leaves room for the state to grow for six cycles in every direction, which
+// fits a starting slice of up to 14 by 14
+fn day17(rng: &mut Rng, size: usize) -> String {
+    let side = std::cmp::min(size, 14);
+    grid(rng, side, side, 0.45, '#', '.')
+}
+
+// -----------------------------------------------------------------------------
+// Day 18
+// -----------------------------------------------------------------------------
+// Expressions are built as trees, so both evaluation orders can be checked for
+// overflow before the expression is written out
+#[derive(Debug)]
+enum Operand {
+    Digit(u64),
+    Group(Expression),
+}
+
+#[derive(Debug)]
+struct Expression {
+    operands: Vec<Operand>,
+    additions: Vec<bool>,
+}
+
+impl Expression {
+    fn random(rng: &mut Rng, depth: usize) -> Self {
+        let count = rng.range(2..=6 - depth);
+        Self {
+            operands: (0..count)
+                .map(|_| match depth < 2 && rng.chance(0.3) {
+                    true => Operand::Group(Expression::random(rng, depth + 1)),
+                    false => Operand::Digit(rng.range(1..=9) as u64),
+                })
+                .collect(),
+            additions: (1..count).map(|_| rng.chance(0.5)).collect(),
+        }
+    }
+
+    // Left to right, or with additions first
+    fn evaluate(&self, additions_first: bool) -> Option<u64> {
+        let values = self
+            .operands
+            .iter()
+            .map(|operand| match operand {
+                Operand::Digit(digit) => Some(*digit),
+                Operand::Group(group) => group.evaluate(additions_first),
+            })
+            .collect::<Option<Vec<u64>>>()?;
+        let (mut product, mut sum) = (1_u64, values[0]);
+        for (&addition, &value) in self.additions.iter().zip(&values[1..]) {
+            match (addition, additions_first) {
+                (true, _) => sum = sum.checked_add(value)?,
+                (false, true) => {
+                    product = product.checked_mul(sum)?;
+                    sum = value;
+                }
+                (false, false) => sum = sum.checked_mul(value)?,
+            }
+        }
+        product.checked_mul(sum)
+    }
+
+    fn write(&self, output: &mut String) {
+        for (i, operand) in self.operands.iter().enumerate() {
+            if i > 0 {
+                output.push_str(if self.additions[i - 1] { " + " } else { " * " });
+            }
+            match operand {
+                Operand::Digit(digit) => write!(output, "{}", digit).unwrap(),
+                Operand::Group(group) => {
+                    output.push('(');
+                    group.write(output);
+                    output.push(')');
+                }
+            }
+        }
+    }
+}
+
+fn day18(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: u64 = 1_000_000_000_000;
+    let mut output = String::new();
+    for _ in 0..size {
+        let expression = loop {
+            let expression = Expression::random(rng, 0);
+            if [false, true].iter().all(|&order| {
+                expression
+                    .evaluate(order)
+                    .is_some_and(|value| value <= LIMIT)
+            }) {
+                break expression;
+            }
+        };
+        expression.write(&mut output);
+        output.push('\n');
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// Day 19
+// -----------------------------------------------------------------------------
+// Rules 42 and 31 split the strings of one length between them, built up a character at
+// a time from pairs of rules that do the same for shorter strings. Each rule then has a
+// single way to match, which is what the solver relies on.
+#[derive(Debug, Clone)]
+enum Production {
+    Terminal(char),
+    Sequences(Vec<Vec<usize>>),
+}
+
+fn expand(rng: &mut Rng, grammar: &[Option<Production>], rule: usize, output: &mut String) {
+    match &grammar[rule] {
+        Some(Production::Terminal(c)) => output.push(*c),
+        Some(Production::Sequences(sequences)) => {
+            for &sub_rule in rng.choose(sequences) {
+                expand(rng, grammar, sub_rule, output);
+            }
+        }
+        None => unreachable!("rule {} is not defined", rule),
+    }
+}
+
+fn day19(rng: &mut Rng, size: usize) -> String {
+    const NUMBER_RULES: usize = 135;
+    const FIXED: [usize; 5] = [0, 8, 11, 42, 31];
+    let length = rng.range(5..=8);
+    let mut numbers: Vec<usize> = (0..NUMBER_RULES)
+        .filter(|number| !FIXED.contains(number))
+        .collect();
+    rng.shuffle(&mut numbers);
+    let mut grammar: Vec<Option<Production>> = vec![None; NUMBER_RULES];
+    let (a, b) = (numbers.pop().unwrap(), numbers.pop().unwrap());
+    grammar[a] = Some(Production::Terminal('a'));
+    grammar[b] = Some(Production::Terminal('b'));
+    let mut level = vec![(a, b)];
+    for current in 2..=length {
+        let count = if current == length { 1 } else { 2 };
+        let mut next = vec![];
+        for _ in 0..count {
+            let pair = match current == length {
+                true => (42, 31),
+                false => (numbers.pop().unwrap(), numbers.pop().unwrap()),
+            };
+            let prefix = rng.chance(0.5);
+            let sequence = |terminal: usize, rule: usize| match prefix {
+                true => vec![terminal, rule],
+                false => vec![rule, terminal],
+            };
+            let mut halves = [(a, *rng.choose(&level)), (b, *rng.choose(&level))];
+            for (_, half) in halves.iter_mut() {
+                if rng.chance(0.5) {
+                    *half = (half.1, half.0);
+                }
+            }
+            rng.shuffle(&mut halves);
+            let first = halves
+                .iter()
+                .map(|&(c, half)| sequence(c, half.0))
+                .collect();
+            let second = halves
+                .iter()
+                .map(|&(c, half)| sequence(c, half.1))
+                .collect();
+            grammar[pair.0] = Some(Production::Sequences(first));
+            grammar[pair.1] = Some(Production::Sequences(second));
+            next.push(pair);
+        }
+        level = next;
+    }
+    grammar[0] = Some(Production::Sequences(vec![vec![8, 11]]));
+    grammar[8] = Some(Production::Sequences(vec![vec![42]]));
+    grammar[11] = Some(Production::Sequences(vec![vec![42, 31]]));
+
+    // Rules
+    let mut rules: Vec<String> = grammar
+        .iter()
+        .enumerate()
+        .filter_map(|(number, production)| match production.as_ref()? {
+            Production::Terminal(c) => Some(format!("{}: \"{}\"", number, c)),
+            Production::Sequences(sequences) => {
+                let sequences: Vec<String> = sequences
+                    .iter()
+                    .map(|sequence| {
+                        let rules: Vec<String> = sequence.iter().map(|r| r.to_string()).collect();
+                        rules.join(" ")
+                    })
+                    .collect();
+                Some(format!("{}: {}", number, sequences.join(" | ")))
+            }
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+
+    // Messages that match rule 0, that only match the looping rules, or neither
+    let mut messages = vec![];
+    for _ in 0..size {
+        let mut message = String::new();
+        let chunks = match rng.below(10) {
+            0..=3 => vec![0],
+            4..=5 => {
+                let count_31 = rng.range(1..=3);
+                let count_42 = rng.range(count_31 + 1..=count_31 + 3);
+                let mut chunks = vec![42; count_42];
+                chunks.extend(std::iter::repeat_n(31, count_31));
+                chunks
+            }
+            6..=8 => (0..rng.range(2..=6))
+                .map(|_| *rng.choose(&[42, 31]))
+                .collect(),
+            _ => vec![],
+        };
+        for chunk in chunks {
+            expand(rng, &grammar, chunk, &mut message);
+        }
+        if message.is_empty() {
+            message = (0..rng.range(1..=4 * length))
+                .map(|_| *rng.choose(&['a', 'b']))
+                .collect();
+        }
+        messages.push(message);
+    }
+    format!("{}\n{}", rules.join("\n") + "\n", lines(messages))
+}
+
+// -----------------------------------------------------------------------------
+// Day 20
+// -----------------------------------------------------------------------------
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+// Turn a square grid; the first four orientations are rotations and the rest are flipped
+fn orient(grid: &[Vec<bool>], orientation: usize) -> Vec<Vec<bool>> {
+    let size = grid.len();
+    let mut grid = grid.to_vec();
+    if orientation >= 4 {
+        grid.iter_mut().for_each(|row| row.reverse());
+    }
+    for _ in 0..orientation % 4 {
+        grid = (0..size)
+            .map(|i| (0..size).map(|j| grid[size - 1 - j][i]).collect())
+            .collect();
+    }
+    grid
+}
+
+// Edge with the given corners that does not match any other edge, either way round
+fn unique_edge(rng: &mut Rng, used: &mut [bool], first: bool, last: bool) -> Option<Vec<bool>> {
+    let value =
+        |bits: &mut dyn Iterator<Item = &bool>| bits.fold(0, |acc, &bit| (acc << 1) | bit as usize);
+    for _ in 0..100 {
+        let mut edge = vec![first; 10];
+        edge[1..9].iter_mut().for_each(|bit| *bit = rng.chance(0.5));
+        edge[9] = last;
+        let (forward, backward) = (value(&mut edge.iter()), value(&mut edge.iter().rev()));
+        if forward != backward && !used[forward] && !used[backward] {
+            used[forward] = true;
+            used[backward] = true;
+            return Some(edge);
+        }
+    }
+    None
+}
+
+// The image is cut into tiles, with a shared random edge between neighboring tiles, and
+// each tile is turned at random
+fn day20(rng: &mut Rng, size: usize) -> String {
+    const TILE: usize = 10;
+    let side = size.clamp(3, 12);
+    let pixels = side * (TILE - 2);
+
+    // Image with a few sea monsters
+    let mut image: Vec<Vec<bool>> = (0..pixels)
+        .map(|_| (0..pixels).map(|_| rng.chance(0.3)).collect())
+        .collect();
+    let mut taken = vec![vec![false; pixels]; pixels];
+    for _ in 0..pixels * pixels / 300 + 1 {
+        let (row, column) = (rng.below(pixels - 2), rng.below(pixels - 19));
+        if (row..row + 3).any(|i| taken[i][column..column + 20].iter().any(|&t| t)) {
+            continue;
+        }
+        for (i, line) in SEA_MONSTER.iter().enumerate() {
+            for (j, c) in line.bytes().enumerate() {
+                taken[row + i][column + j] = true;
+                if c == b'#' {
+                    image[row + i][column + j] = true;
+                }
+            }
+        }
+    }
+
+    // Edges, with each corner shared by the four tiles around it
+    let (horizontal, vertical) = loop {
+        let corners: Vec<Vec<bool>> = (0..=side)
+            .map(|_| (0..=side).map(|_| rng.chance(0.5)).collect())
+            .collect();
+        let mut used = vec![false; 1 << TILE];
+        let mut horizontal = vec![];
+        let mut vertical = vec![];
+        for r in 0..=side {
+            for c in 0..=side {
+                if c < side {
+                    horizontal.push(unique_edge(
+                        rng,
+                        &mut used,
+                        corners[r][c],
+                        corners[r][c + 1],
+                    ));
+                }
+                if r < side {
+                    vertical.push(unique_edge(
+                        rng,
+                        &mut used,
+                        corners[r][c],
+                        corners[r + 1][c],
+                    ));
+                }
+            }
+        }
+        let horizontal: Option<Vec<Vec<bool>>> = horizontal.into_iter().collect();
+        let vertical: Option<Vec<Vec<bool>>> = vertical.into_iter().collect();
+        if let (Some(horizontal), Some(vertical)) = (horizontal, vertical) {
+            break (horizontal, vertical);
+        }
+    };
+
+    // Tiles
+    let mut ids: Vec<usize> = (1000..10_000).collect();
+    rng.shuffle(&mut ids);
+    let mut tiles = vec![];
+    for r in 0..side {
+        for c in 0..side {
+            let mut tile = vec![vec![false; TILE]; TILE];
+            let (top, bottom) = (&horizontal[r * side + c], &horizontal[(r + 1) * side + c]);
+            let (left, right) = (
+                &vertical[r * (side + 1) + c],
+                &vertical[r * (side + 1) + c + 1],
+            );
+            for i in 0..TILE {
+                tile[0][i] = top[i];
+                tile[TILE - 1][i] = bottom[i];
+                tile[i][0] = left[i];
+                tile[i][TILE - 1] = right[i];
+            }
+            for i in 1..TILE - 1 {
+                for j in 1..TILE - 1 {
+                    tile[i][j] = image[r * (TILE - 2) + i - 1][c * (TILE - 2) + j - 1];
+                }
+            }
+            let mut text = format!("Tile {}:", ids[tiles.len()]);
+            for row in orient(&tile, rng.below(8)) {
+                text.push('\n');
+                text.extend(row.iter().map(|&pixel| if pixel { '#' } else { '.' }));
+            }
+            tiles.push(text);
+        }
+    }
+    rng.shuffle(&mut tiles);
+    tiles.join("\n\n") + "\n"
+}
+
+// -----------------------------------------------------------------------------
+// Day 21
+// -----------------------------------------------------------------------------
+const ALLERGENS: [&str; 8] = [
+    "dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "soy", "wheat",
+];
+
+// Each allergen has one ingredient, which is in every food that lists the allergen. Foods
+// are added past the size until the allergens can be narrowed down to one ingredient each.
+fn day21(rng: &mut Rng, size: usize) -> String {
+    const SAFE: usize = 100;
+    let number_allergens = ALLERGENS.len();
+    let mut names: Vec<String> = vec![];
+    while names.len() < SAFE + number_allergens {
+        let name: String = (0..rng.range(3..=8)).map(|_| letter(rng)).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let mut foods: Vec<(Vec<usize>, Vec<usize>)> = vec![];
+    while foods.len() < size || !allergens_resolved(&foods, number_allergens) {
+        let mut ingredients: Vec<usize> = (0..SAFE).filter(|_| rng.chance(0.25)).collect();
+        let mut allergens: Vec<usize> = (0..number_allergens).filter(|_| rng.chance(0.5)).collect();
+        if allergens.is_empty() {
+            allergens.push(rng.below(number_allergens));
+        }
+        ingredients.extend(allergens.iter().map(|allergen| SAFE + allergen));
+        let listed: Vec<usize> = allergens
+            .iter()
+            .copied()
+            .filter(|_| rng.chance(0.6))
+            .collect();
+        let listed = if listed.is_empty() {
+            vec![*rng.choose(&allergens)]
+        } else {
+            listed
+        };
+        rng.shuffle(&mut ingredients);
+        foods.push((ingredients, listed));
+    }
+    lines(foods.iter().map(|(ingredients, allergens)| {
+        let ingredients: Vec<&str> = ingredients.iter().map(|&i| names[i].as_str()).collect();
+        let allergens: Vec<&str> = allergens.iter().map(|&a| ALLERGENS[a]).collect();
+        format!(
+            "{} (contains {})",
+            ingredients.join(" "),
+            allergens.join(", ")
+        )
+    }))
+}
+
+// Whether every allergen is listed and narrows down to a single ingredient
+fn allergens_resolved(foods: &[(Vec<usize>, Vec<usize>)], number_allergens: usize) -> bool {
+    let mut candidates: Vec<Option<Vec<usize>>> = vec![None; number_allergens];
+    for (ingredients, allergens) in foods {
+        for &allergen in allergens {
+            let candidate = candidates[allergen].get_or_insert_with(|| ingredients.clone());
+            candidate.retain(|ingredient| ingredients.contains(ingredient));
+        }
+    }
+    let mut candidates: Vec<Vec<usize>> = match candidates.into_iter().collect() {
+        Some(candidates) => candidates,
+        None => return false,
+    };
+    for _ in 0..number_allergens {
+        let found = match candidates.iter().find(|candidate| candidate.len() == 1) {
+            Some(candidate) => candidate[0],
+            None => return false,
+        };
+        candidates
+            .iter_mut()
+            .for_each(|candidate| candidate.retain(|&ingredient| ingredient != found));
+    }
+    true
+}
+
+// -----------------------------------------------------------------------------
+// Day 22
+// -----------------------------------------------------------------------------
+// Combat can go on forever for some deals, so those are dealt again. The parser holds 50
+// cards in all.
+fn day22(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, 50).div_ceil(2) * 2;
+    let mut cards: Vec<usize> = (1..=size).collect();
+    loop {
+        rng.shuffle(&mut cards);
+        let (player_1, player_2) = cards.split_at(size / 2);
+        if combat_ends(player_1, player_2) {
+            return format!(
+                "Player 1:\n{}\nPlayer 2:\n{}",
+                lines(player_1),
+                lines(player_2)
+            );
+        }
+    }
+}
+
+fn combat_ends(player_1: &[usize], player_2: &[usize]) -> bool {
+    let mut player_1: std::collections::VecDeque<usize> = player_1.iter().copied().collect();
+    let mut player_2: std::collections::VecDeque<usize> = player_2.iter().copied().collect();
+    for _ in 0..100_000 {
+        let (card_1, card_2) = match (player_1.pop_front(), player_2.pop_front()) {
+            (Some(card_1), Some(card_2)) => (card_1, card_2),
+            _ => return true,
+        };
+        match card_1 > card_2 {
+            true => player_1.extend([card_1, card_2].iter()),
+            false => player_2.extend([card_2, card_1].iter()),
+        }
+    }
+    false
+}
+
+// -----------------------------------------------------------------------------
+// Day 23
+// -----------------------------------------------------------------------------
+fn day23(rng: &mut Rng, _size: usize) -> String {
+    let mut cups: Vec<char> = ('1'..='9').collect();
+    rng.shuffle(&mut cups);
+    cups.into_iter().collect::<String>() + "\n"
+}
+
+// -----------------------------------------------------------------------------
+// Day 24
+// -----------------------------------------------------------------------------
+// Paths stay close to the reference tile, so the pattern has room to grow
+fn day24(rng: &mut Rng, size: usize) -> String {
+    const STEPS: [(&str, i32, i32); 6] = [
+        ("e", 1, 0),
+        ("w", -1, 0),
+        ("ne", 0, 1),
+        ("nw", -1, 1),
+        ("se", 1, -1),
+        ("sw", 0, -1),
+    ];
+    const REACH: i32 = 12;
+    let mut output = String::new();
+    for _ in 0..size {
+        let (mut east, mut north_east) = (0, 0);
+        for _ in 0..rng.range(10..=25) {
+            let &(step, d_east, d_north_east) = loop {
+                let step = rng.choose(&STEPS);
+                if (east + step.1).abs() <= REACH && (north_east + step.2).abs() <= REACH {
+                    break step;
+                }
+            };
+            east += d_east;
+            north_east += d_north_east;
+            output.push_str(step);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// Day 25
+// -----------------------------------------------------------------------------
+fn day25(rng: &mut Rng, _size: usize) -> String {
+    const P: u64 = 20_201_227;
+    let public_key = |rng: &mut Rng| {
+        let (mut key, mut base, mut exponent) = (1, 7, rng.range(1..=P as usize - 2));
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                key = key * base % P;
+            }
+            base = base * base % P;
+            exponent >>= 1;
+        }
+        key
+    };
+    loop {
+        let (door, card) = (public_key(rng), public_key(rng));
+        if door != card {
+            return format!("{}\n{}\n", door, card);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
@@ -0,0 +1,1298 @@
+//! Advent of Code 2020:
+//! My solutions to [Advent of Code 2020](https://adventofcode.com/2020). Each day lives in
+//! its own module and implements the [`registry::Day`] trait, so the parsing and the two
+//! parts can be called directly, for example
+//!
+//! ```no_run
+//! use advent2020::{day13::Day13, registry::Day};
+//!
+//! let buffer = std::fs::read_to_string("data/day13.txt").unwrap();
+//! let mut schedule = Day13::parse(&buffer).unwrap();
+//! println!("{}", Day13::part_2(&mut schedule));
+//! ```
+//!
+//! The [`registry`] runs and times a day by number, which is what the `advent2020` binary
+//! uses. Some of the more reusable pieces, such as the k-sum search in [`day01`], the
+//! record schemas in [`day04`], the Chinese Remainder Theorem solver in [`day13`] or the
+//! discrete logarithm in [`day25`], are also exported on their own.
+
+#![warn(missing_docs)]
+
+// -----------------------------------------------------------------------------
+// Modules
+// -----------------------------------------------------------------------------
+pub mod baseline;
+pub mod budget;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod error;
+pub mod export;
+pub mod fuzz;
+pub mod generate;
+pub mod memory;
+pub mod output;
+#[cfg(feature = "reference")]
+pub mod reference;
+pub mod registry;
+pub mod report;
+pub mod spans;
+pub mod statistics;
+
+// -----------------------------------------------------------------------------
+// Answer enum
+// -----------------------------------------------------------------------------
+/// Answer to one part of a puzzle
+#[derive(Debug, Clone)]
+pub enum Answer {
+    /// Signed integer answer
+    Integer(i64),
+    /// Unsigned integer answer
+    Unsigned(u64),
+    /// Text answer, such as a code or a list
+    String(String),
+    /// No answer, for the missing part 2 on day 25 or when no solution exists
+    None,
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::String(value) => write!(f, "{}", value),
+            Answer::None => write!(f, "N/A"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )+
+    };
+}
+answer_from!(Integer, i64, i16, i32, i64);
+answer_from!(Unsigned, u64, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::String(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::String(value.to_string())
+    }
+}
+
+// Integer answers compare by value, regardless of signedness
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Integer(value), _) => other == value,
+            (Answer::Unsigned(value), Answer::Unsigned(other)) => value == other,
+            (Answer::Unsigned(_), Answer::Integer(other)) => self == other,
+            (Answer::String(value), Answer::String(other)) => value == other,
+            (Answer::None, Answer::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        match self {
+            Answer::Integer(value) => value == other,
+            Answer::Unsigned(value) => *other >= 0 && *value == *other as u64,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::String(value) => value == other,
+            _ => false,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Results struct
+// -----------------------------------------------------------------------------
+/// Answers and timing from a single run of a day
+#[derive(Debug)]
+pub struct Results {
+    /// Answer to part 1
+    pub part_1: Answer,
+    /// Answer to part 2
+    pub part_2: Answer,
+    /// Time spent in each phase
+    pub times: Timing,
+    /// Allocator activity in each phase, with the `memory` feature
+    pub memory: Option<memory::Usage>,
+    /// Time spent in the named steps of the solvers, with the `spans` feature
+    pub spans: Vec<spans::SpanTime>,
+}
+
+impl Results {
+    /// Collect the results of a run
+    pub fn new(part_1: Answer, part_2: Answer, times: Timing) -> Self {
+        Self {
+            part_1,
+            part_2,
+            times,
+            memory: None,
+            spans: vec![],
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Timing struct
+// -----------------------------------------------------------------------------
+/// Time spent in each phase of a day
+#[derive(Debug)]
+pub struct Timing {
+    /// Reading and parsing the input
+    pub setup: std::time::Duration,
+    /// Solving part 1
+    pub part_1: std::time::Duration,
+    /// Solving part 2
+    pub part_2: std::time::Duration,
+    /// Solving both parts in a single pass, including parsing, if the day supports it
+    pub combined: Option<std::time::Duration>,
+}
+
+impl Timing {
+    /// Collect the phase times of a run
+    pub fn new(
+        setup: std::time::Duration,
+        part_1: std::time::Duration,
+        part_2: std::time::Duration,
+        combined: Option<std::time::Duration>,
+    ) -> Self {
+        Self {
+            setup,
+            part_1,
+            part_2,
+            combined,
+        }
+    }
+
+    /// Average over repetitions; combined only if every repetition has it
+    pub fn average(times: &[&Timing]) -> Self {
+        let count = times.len() as u32;
+        let sum = |phase: fn(&Timing) -> std::time::Duration| {
+            times
+                .iter()
+                .map(|&time| phase(time))
+                .sum::<std::time::Duration>()
+                / count
+        };
+        let combined: Option<Vec<std::time::Duration>> =
+            times.iter().map(|time| time.combined).collect();
+        Self::new(
+            sum(|time| time.setup),
+            sum(|time| time.part_1),
+            sum(|time| time.part_2),
+            combined.map(|combined| combined.iter().sum::<std::time::Duration>() / count),
+        )
+    }
+
+    /// Best of the separate parts and the combined solution
+    pub fn total(&self) -> std::time::Duration {
+        let separate = self.setup + self.part_1 + self.part_2;
+        self.combined
+            .map_or(separate, |combined| std::cmp::min(combined, separate))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Input enum
+// -----------------------------------------------------------------------------
+/// Source of the puzzle input for a day
+#[derive(Debug)]
+pub enum Input {
+    /// Read the input from a file
+    Path(std::path::PathBuf),
+    /// Input already in memory
+    Buffer(String),
+}
+
+impl Input {
+    /// Default input file for a day, `data/dayNN.txt`
+    pub fn day(day: usize) -> Self {
+        Input::Path(format!("data/day{:02}.txt", day).into())
+    }
+
+    /// Read the whole input, normalized with [`Input::normalize`]
+    pub fn read(&self) -> std::io::Result<String> {
+        match self {
+            Input::Path(path) => std::fs::read_to_string(path).map(Input::normalize),
+            Input::Buffer(buffer) => Ok(Input::normalize(buffer.clone())),
+        }
+    }
+
+    /// Unix line endings, no trailing whitespace on any line, and a single newline at
+    /// the end, which is what the parsers expect
+    pub fn normalize(buffer: String) -> String {
+        let trimmed = buffer.trim_end();
+        let normal = !trimmed.contains('\r')
+            && trimmed
+                .lines()
+                .all(|line| line.trim_end().len() == line.len())
+            && buffer.len() == trimmed.len() + 1;
+        if normal {
+            return buffer;
+        }
+        let mut normalized = String::with_capacity(buffer.len());
+        for line in trimmed.lines() {
+            normalized.push_str(line.trim_end());
+            normalized.push('\n');
+        }
+        normalized
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Prelude
+// -----------------------------------------------------------------------------
+pub(crate) mod prelude {
+    pub(crate) use crate::budget::Budget;
+    pub(crate) use crate::error::{self, Error};
+    pub(crate) use crate::statistics::Statistics;
+    pub(crate) use crate::{output, spans, Answer, Input, Results, Timing};
+    pub(crate) use colored::*;
+    pub(crate) use std::time::Instant;
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use registry::Day;
+    macro_rules! test_day {
+        ($day:expr, $part_1:expr, $part_2:expr) => {
+            let entry = registry::get($day).unwrap();
+            let results = (entry.run)(&Input::day($day)).unwrap();
+            assert_eq!(results.part_1, $part_1);
+            assert_eq!(results.part_2, $part_2);
+            let overruns = entry.budget.overruns(&results.times);
+            assert!(overruns.is_empty(), "Dec {:02}: {:?}", $day, overruns);
+        };
+    }
+
+    // Run a day on an in-memory worked example
+    fn example(day: usize, buffer: &str) -> Results {
+        (registry::get(day).unwrap().run)(&Input::Buffer(buffer.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_library() {
+        assert_eq!(
+            day13::chinese_remainder(&[(0, 17), (13 - 2, 13), (19 - 3, 19)]),
+            Some(3417)
+        );
+        assert_eq!(day13::chinese_remainder(&[(1, 4), (3, 6)]), None);
+        assert_eq!(
+            day13::chinese_remainder(&[(0, 1 << 40), (1, (1 << 40) + 1), (2, (1 << 40) - 1)]),
+            None
+        );
+        assert_eq!(day25::discrete_log(5_764_801), Some(8));
+        assert_eq!(day25::discrete_log(0), None);
+        assert_eq!(day25::discrete_log(20_201_227), None);
+        assert_eq!(day25::transform(17_807_724, 8), 14_897_079);
+        let rules = day19::parse_rules("0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: \"b\"").unwrap();
+        assert!(day19::matches("aab", &rules) && day19::matches("aba", &rules));
+        assert!(!day19::matches("abb", &rules) && !day19::matches("aa", &rules));
+        let mut schedule = day13::Day13::parse("939\n7,13,x,x,59,x,31,19\n").unwrap();
+        assert_eq!(day13::Day13::part_1(&mut schedule), 295);
+        assert_eq!(day13::Day13::part_2(&mut schedule), 1_068_781);
+    }
+
+    #[test]
+    fn test_errors() {
+        let run = |day: usize, buffer: &str| {
+            (registry::get(day).unwrap().run)(&Input::Buffer(buffer.to_string()))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            run(2, "1-3 a: abcde\n1-x b: cdefg\n"),
+            "line 2, column 3: invalid number 'x'"
+        );
+        assert_eq!(
+            run(12, "F10\nN3\nL45\n"),
+            "line 3, column 2: unsupported rotation 45"
+        );
+        assert_eq!(
+            run(10, "1\n99999999999\n"),
+            "line 2, column 1: joltage 99999999999 is out of reach of 2 adapters"
+        );
+        assert!(run(13, "939\n").starts_with("line 2, column 1: expected"));
+        assert_eq!(
+            run(13, "939\n4,x,6\n"),
+            "line 2, column 5: bus IDs 4 and 6 share a factor of 2"
+        );
+        assert_eq!(
+            run(13, "939\n7,7\n"),
+            "line 2, column 3: bus IDs 7 and 7 share a factor of 7"
+        );
+        assert_eq!(
+            run(
+                7,
+                "shiny gold bags contain 1 dark red bag.\n\
+                 dark red bags contain 2 shiny gold bags.\n"
+            ),
+            "line 2, column 25: bag 'shiny gold' contains itself"
+        );
+        assert_eq!(
+            run(22, "Player 1:\n3\n1\n\nPlayer 2:\n2\n1\n"),
+            "line 7, column 1: duplicate card 1"
+        );
+        assert_eq!(
+            run(22, "Player 1:\n01\n2\n\nPlayer 2:\n1\n3\n"),
+            "line 6, column 1: duplicate card 1"
+        );
+        assert_eq!(
+            run(18, "1 + 2\n12 * 3\n"),
+            "line 2, column 1: numbers must be single digits"
+        );
+        assert_eq!(
+            run(20, "Tile 1:\n#\n\nTile 1:\n#\n"),
+            "line 4, column 6: duplicate tile id 1"
+        );
+        assert!(run(20, "Tile 1:\n#\n\nTile 2:\n#\n").ends_with("do not make a square image"));
+        assert!((registry::get(1).unwrap().run)(&Input::Path("missing.txt".into())).is_err());
+
+        // A combined solver that disagrees with the parts is an error, not a panic
+        struct Disagree;
+        impl registry::Day for Disagree {
+            const NUMBER: usize = 1;
+            const NAME: &'static str = "Disagree";
+            const LABELS: [&'static str; 2] = ["", ""];
+            const BUDGET: budget::Budget = budget::Budget::millis(5, 5, 5);
+            type Data = ();
+            fn parse(_buffer: &str) -> Result<Self::Data, error::Error> {
+                Ok(())
+            }
+            fn part_1(_data: &mut Self::Data) -> Answer {
+                1.into()
+            }
+            fn part_2(_data: &mut Self::Data) -> Answer {
+                2.into()
+            }
+            fn combined(_buffer: &str) -> Result<Option<(Answer, Answer)>, error::Error> {
+                Ok(Some((1.into(), 3.into())))
+            }
+        }
+        assert_eq!(
+            registry::run::<Disagree>(&Input::Buffer(String::new()))
+                .unwrap_err()
+                .to_string(),
+            "part 2 gave 2, but the combined solver gave 3"
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let normal = "a\n\nb c\n".to_string();
+        assert_eq!(Input::normalize(normal.clone()), normal);
+        assert_eq!(
+            Input::normalize("a \r\n\r\nb c\t\r\n\r\n".to_string()),
+            normal
+        );
+        assert_eq!(Input::normalize("a\n\nb c".to_string()), normal);
+        let results = example(6, "abc\r\n\r\na\r\nb\r\nc  \r\n");
+        assert_eq!((results.part_1, results.part_2), (6.into(), 3.into()));
+    }
+
+    #[test]
+    fn test_generate() {
+        for day in 1..=registry::NUMBER_DAYS {
+            let size = generate::SIZES[day - 1];
+            let input = generate::input(day, 2020, size).unwrap();
+            assert_eq!(generate::input(day, 2020, size), Some(input.clone()));
+            let results = example(day, &input);
+            assert_ne!(results.part_1, Answer::None, "Dec {:02}", day);
+            if day < registry::NUMBER_DAYS {
+                assert_ne!(results.part_2, Answer::None, "Dec {:02}", day);
+            }
+        }
+        // Sizes past the limits of the parsers are clamped
+        for day in [11, 16, 17, 22] {
+            let input = generate::input(day, 2020, 100_000).unwrap();
+            let results = example(day, &input);
+            assert_ne!(results.part_1, Answer::None, "Dec {:02}", day);
+        }
+        assert_ne!(generate::input(9, 1, 100), generate::input(9, 2, 100));
+        assert_eq!(generate::input(26, 1, 100), None);
+    }
+
+    #[test]
+    fn test_fuzz() {
+        let timeout = std::time::Duration::from_secs(5);
+        for day in 1..=registry::NUMBER_DAYS {
+            match fuzz::fuzz(day, 0..2, 250, timeout, false) {
+                Ok(tally) => assert_eq!(tally.parsed + tally.rejected, 500),
+                Err(finding) => panic!("{}\n{}", finding, finding.input),
+            }
+        }
+        // Solving too, but days 15 and 23 take a fixed number of turns, too many for a test
+        let timeout = std::time::Duration::from_secs(30);
+        for day in (1..=registry::NUMBER_DAYS).filter(|&day| day != 15 && day != 23) {
+            if let Err(finding) = fuzz::fuzz(day, 0..1, 100, timeout, true) {
+                panic!("{}\n{}", finding, finding.input);
+            }
+        }
+        assert_eq!(fuzz::case(4, 1, 7), fuzz::case(4, 1, 7));
+        assert_eq!(fuzz::case(26, 1, 7), None);
+    }
+
+    #[cfg(feature = "reference")]
+    #[test]
+    fn test_reference() {
+        for day in 1..=registry::NUMBER_DAYS {
+            let input = std::fs::read_to_string(format!("data/day{:02}.txt", day)).unwrap();
+            let results = example(day, &input);
+            let answers = reference::solve(day, &input).unwrap();
+            assert_eq!((results.part_1, results.part_2), answers, "Dec {:02}", day);
+            let seeds = if day == 15 || day == 23 { 0..1 } else { 0..5 };
+            if let Err(divergence) = reference::compare(day, seeds, generate::SIZES[day - 1]) {
+                panic!("{}", divergence);
+            }
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let samples = [10.0, 11.0, 12.0, 11.0, 10.0, 100.0, 13.0, 11.0];
+        let statistics = statistics::Statistics::new(&samples);
+        assert_eq!(statistics.rejected(), 1);
+        assert!(statistics.outliers[5]);
+        assert_eq!(statistics.mean.as_nanos(), 11);
+        assert_eq!(statistics.median.as_nanos(), 11);
+        assert_eq!(statistics.p90.as_nanos(), 13);
+        assert_eq!(statistics.min.as_nanos(), 10);
+        assert!(statistics.relative_error() < 0.05);
+        let single = statistics::Statistics::new(&[5.0]);
+        assert_eq!(single.std_dev.as_nanos(), 0);
+        assert!(single.relative_error().is_infinite());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let saved = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let comparison = baseline::compare(&saved, &[3.0, 4.0, 5.0, 6.0, 7.0], 0.05);
+        assert!((comparison.p_value - 0.0805).abs() < 1e-4);
+        assert!(!comparison.regression);
+        let comparison = baseline::compare(&saved, &[7.0, 8.0, 9.0, 10.0, 11.0], 0.05);
+        assert!(comparison.regression);
+        let comparison = baseline::compare(&saved, &[7.0, 8.0, 9.0, 10.0, 11.0], 5.0);
+        assert!(comparison.significant() && !comparison.regression);
+
+        let path = std::env::temp_dir().join("advent2020_test_baseline.txt");
+        baseline::save(&path, &[(3, saved.to_vec())]).unwrap();
+        let loaded = baseline::load(&path).unwrap();
+        assert_eq!(loaded.samples(3), Some(&saved[..]));
+        assert_eq!(loaded.samples(4), None);
+        std::fs::write(&path, "03 1 2\n30 1 2\n").unwrap();
+        let error = baseline::load(&path).unwrap_err().to_string();
+        assert_eq!(error, "line 2, column 1: invalid day 30");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_budget() {
+        let millis = std::time::Duration::from_millis;
+        let budget = budget::Budget::millis(1, 2, 3);
+        let overruns = budget.overruns(&Timing::new(millis(1), millis(3), millis(3), None));
+        assert_eq!(overruns.len(), 1);
+        assert_eq!(
+            overruns[0].to_string(),
+            "part 1 took 3ms, over the budget of 2ms"
+        );
+    }
+
+    #[test]
+    fn test_memory() {
+        let allocations = memory::Allocations {
+            count: 1,
+            bytes: 2_048,
+            peak: 100,
+        };
+        assert_eq!(
+            allocations.to_string(),
+            "1 allocation, 2.0 KiB allocated, 100 B peak"
+        );
+        let results = example(1, "1721\n979\n366\n299\n675\n1456\n");
+        assert_eq!(results.memory.is_some(), memory::ENABLED);
+        if let Some(usage) = results.memory {
+            assert!(usage.setup.count > 0);
+        }
+    }
+
+    #[test]
+    fn test_spans() {
+        let span = |path: &[&'static str], micros| spans::SpanTime {
+            path: path.to_vec(),
+            time: std::time::Duration::from_micros(micros),
+            count: 1,
+        };
+        let first = [span(&["a"], 10), span(&["a", "b"], 4)];
+        let second = [span(&["a"], 20), span(&["c"], 2)];
+        let average = spans::average(&[&first, &second]);
+        let names: Vec<&str> = average.iter().map(|span| span.name()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert_eq!(average[0].time.as_micros(), 15);
+        assert_eq!(average[1].depth(), 1);
+        let results = example(20, include_str!("../data/examples/day20.txt"));
+        let names: Vec<&str> = results.spans.iter().map(|span| span.name()).collect();
+        if spans::ENABLED {
+            assert_eq!(
+                names,
+                vec![
+                    "tile parsing",
+                    "edge matching",
+                    "assembly",
+                    "monster search"
+                ]
+            );
+        } else {
+            assert!(names.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_export() {
+        let day = registry::get(21).unwrap();
+        let samples: Vec<Results> = (0..2)
+            .map(|_| (day.run)(&Input::day(21)).unwrap())
+            .collect();
+        let summaries = [export::Summary {
+            day,
+            samples: &samples,
+            average: &samples[0].times,
+            statistics: &statistics::Statistics::new(&[1_000.0, 2_000.0]),
+        }];
+        let json = export::json(&summaries, 1, false);
+        assert!(json.contains("\"mode\": \"sequential\","));
+        assert!(json.contains("\"part_1\": 2230,"));
+        assert!(json.contains("\"part_2\": \"qqskn,ccvnlbp,tcm,jnqcd,qjqb,xjqd,xhzr,cjxv\""));
+        assert!(json.contains("\"median_ns\": 1500,"));
+        assert!(json.contains("\"outlier\": false}"));
+        let csv = export::csv(&summaries);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].starts_with("21,Allergen Assessment,average,2230,\"qqskn,"));
+        assert!(rows[1].ends_with(",1500,707,1500,2000,1000,"));
+        assert!(rows[3].ends_with(",,,,false"));
+        assert!(rows[3].starts_with("21,Allergen Assessment,2,"));
+    }
+
+    #[test]
+    fn test_report() {
+        assert!(report::notes(13)[0].starts_with("This is a fast one"));
+        let samples = [example(12, "F10\nN3\nF7\nR90\nF11\n")];
+        let summaries = [export::Summary {
+            day: registry::get(12).unwrap(),
+            samples: &samples,
+            average: &samples[0].times,
+            statistics: &statistics::Statistics::new(&[1_000.0, 2_000.0]),
+        }];
+        let markdown = report::markdown(&summaries, 1, false);
+        assert!(markdown.contains("| 12 | Rain Risk | 25 | 286 |"));
+        assert!(markdown.contains("<text x=\"695\" y=\"15\">1.5µs</text>"));
+        assert!(markdown.contains("### Day 12: Rain Risk\n\nAnother straightforward problem"));
+        let html = report::html(&summaries, 1, true);
+        assert!(html.contains("<p>Mode: parallel, warmup: 1</p>"));
+        assert!(html.contains("<td>12</td><td>Rain Risk</td><td>25</td><td>286</td>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_01() {
+        let results = example(1, "1721\n979\n366\n299\n675\n1456\n");
+        assert_eq!(results.part_1, 514_579);
+        assert_eq!(results.part_2, 241_861_950);
+
+        test_day!(1, 326_211, 131_347_190);
+
+        // Any k and target, with duplicates, negative and large values
+        let sums = day01::KSum::new(&[1721, 979, 366, 299, 675, 1456]);
+        assert_eq!(sums.first(2, 2020), Some(vec![0, 3]));
+        assert_eq!(sums.all(3, 2020), vec![vec![1, 2, 4]]);
+        assert_eq!(sums.first(4, 2020), None);
+        assert_eq!(day01::KSum::new(&[1010; 3]).all(2, 2020).len(), 3);
+        let values = [-5, 10_000_000_000, 7, -9_999_999_993, 3];
+        assert_eq!(day01::KSum::new(&values).all(3, 10), vec![vec![1, 3, 4]]);
+        let mut rng = generate::Rng::new(1);
+        let values: Vec<i64> = (0..30).map(|_| rng.range(0..=40) as i64 - 20).collect();
+        let sums = day01::KSum::new(&values);
+        let mut expected = vec![];
+        for i in 0..values.len() {
+            for j in i + 1..values.len() {
+                for k in j + 1..values.len() {
+                    if values[i] + values[j] + values[k] == 7 {
+                        expected.push(vec![i, j, k]);
+                    }
+                }
+            }
+        }
+        let mut found = sums.all(3, 7);
+        found.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_02() {
+        let results = example(2, "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n");
+        assert_eq!(results.part_1, 2);
+        assert_eq!(results.part_2, 1);
+        // Bounds past the end of the password only fail the positions policy
+        let results = example(2, "1-9 a: aaa\n");
+        assert_eq!(results.part_1, 1);
+        assert_eq!(results.part_2, 0);
+
+        test_day!(2, 538, 489);
+
+        // Policies from descriptions, with the clause that failed
+        use day02::PasswordPolicy;
+        let description = "1-3 a & at 1,3 b & 2-9 digit & 1-2 [!?] & no 1234 & min 8";
+        let policy = day02::parse_policy(description).unwrap();
+        assert_eq!(policy.to_string(), description);
+        assert!(policy.allows("bax42!yz?"));
+        let violation = policy.check("bax1234!x").unwrap_err();
+        assert_eq!(violation.clause, "no 1234");
+        assert_eq!(violation.to_string(), "'no 1234': contains 1234 at 4");
+        assert_eq!(
+            policy.check("bbb42!xyz").unwrap_err().to_string(),
+            "'1-3 a': found 0 of a, expected 1-3"
+        );
+        assert_eq!(
+            day02::parse_policy("1-3 a & 2-x b")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 11: invalid number 'x'"
+        );
+        assert!(day02::parse_policy("3-1 a").is_err());
+        assert_eq!(
+            day02::parse_policy("min 2 & at 0,3 a")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 12: positions start at 1"
+        );
+
+        // Forbidden texts with an `&` or a leading quote are quoted
+        let description = r#"no "a & b" & min 3 & no "\"&\\""#;
+        let policy = day02::parse_policy(description).unwrap();
+        assert_eq!(policy.to_string(), description);
+        assert_eq!(
+            policy.check("xa & by").unwrap_err().to_string(),
+            "'no \"a & b\"': contains a & b at 2"
+        );
+        assert!(policy.check(r#"x"&\y"#).is_err());
+        assert!(policy.allows(r#"x"&y"#));
+        assert_eq!(
+            day02::parse_policy(r#"no "a & min 2"#)
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 4: expected a closing quote"
+        );
+        assert!(day02::parse_policy("1-3 [] & min 2").is_err());
+        let audits =
+            day02::audit("1-3 a: abcde\nat 1,3 b: cdefg\nmin 4 & no cc: ccccccccc\nat 2,5 b: ab\n")
+                .unwrap()
+                .iter()
+                .map(|audit| audit.to_string())
+                .collect::<Vec<_>>();
+        assert_eq!(
+            audits,
+            [
+                "line 1: abcde is valid",
+                "line 2: cdefg breaks 'at 1,3 b': found b at neither position",
+                "line 3: ccccccccc breaks 'no cc': contains cc at 1",
+                "line 4: ab breaks 'at 2,5 b': position 5 is past the end of length 2",
+            ]
+        );
+        assert_eq!(
+            day02::audit("min 4: abcde\nmin x: a\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 5: invalid number 'x'"
+        );
+    }
+
+    #[test]
+    fn test_03() {
+        let results = example(
+            3,
+            "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n..#.##.....\n\
+             .#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n.#..#...#.#\n",
+        );
+        assert_eq!(results.part_1, 7);
+        assert_eq!(results.part_2, 336);
+
+        test_day!(3, 176, 5_872_458_240);
+
+        // Slope lists, search and rendering on the toroidal grid
+        use day03::{Slope, TreeGrid, Wrap};
+        let grid: TreeGrid = "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n\
+                              ..#.##.....\n.#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n\
+                              .#..#...#.#\n"
+            .parse()
+            .unwrap();
+        assert_eq!(grid.count_slopes(&day03::SLOPES), vec![7, 2, 3, 4, 2]);
+        let search = grid.search(1..=7, 1..=2).unwrap();
+        assert_eq!(search.fewest, (Slope { right: 5, down: 2 }, 0));
+        assert_eq!(search.most, (Slope { right: 3, down: 1 }, 7));
+        assert_eq!(
+            grid.search(1..=7, std::ops::RangeInclusive::new(2, 1)),
+            None
+        );
+        assert!(grid
+            .render(day03::SLOPES[0])
+            .starts_with("O.##.......\n#..O#...#..\n.#....X..#.\n"));
+        let slope = Slope { right: 1, down: 2 };
+        assert_eq!(grid.clone().with_wrap(Wrap::Both).count_trees(slope), 3);
+        assert_eq!(grid.with_wrap(Wrap::Both).path(slope).count(), 11);
+    }
+
+    #[test]
+    fn test_04() {
+        let results = example(
+            4,
+            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\n\
+             iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\nhcl:#cfa07d byr:1929\n\n\
+             hcl:#ae17e1 iyr:2013\neyr:2024\necl:brn pid:760753108 byr:1931\nhgt:179cm\n\n\
+             hcl:#cfa07d eyr:2025 pid:166559648\niyr:2011 ecl:brn hgt:59in\n",
+        );
+        assert_eq!(results.part_1, 2);
+        assert_eq!(results.part_2, 2);
+        let invalid = example(
+            4,
+            "eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926\n\n\
+             iyr:2019\nhcl:#602927 eyr:1967 hgt:170cm\necl:grn pid:012533040 byr:1946\n\n\
+             hcl:dab227 iyr:2012\necl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277\n\n\
+             hgt:59cm ecl:zzz\neyr:2038 hcl:74454a iyr:2023\npid:3556412378 byr:2007\n",
+        );
+        assert_eq!(invalid.part_1, 4);
+        assert_eq!(invalid.part_2, 0);
+        let valid = example(
+            4,
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f\n\n\
+             eyr:2029 ecl:blu cid:129 byr:1989\niyr:2014 pid:896056539 hcl:#a97842 hgt:165cm\n\n\
+             hcl:#888785\nhgt:164cm byr:2001 iyr:2015 cid:88\npid:545766238 ecl:hzl\neyr:2022\n\n\
+             iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719\n",
+        );
+        assert_eq!(valid.part_2, 4);
+
+        test_day!(4, 182, 109);
+
+        // Schemas as data, with every failing field and why
+        use day04::{Problem, Rule, Schema};
+        let passport = Schema::passport();
+        assert_eq!(passport.to_string(), day04::PASSPORT);
+        assert_eq!(
+            passport.field("hgt").unwrap().rule,
+            Rule::Units(vec![
+                ("cm".to_string(), 150..=193),
+                ("in".to_string(), 59..=76)
+            ])
+        );
+        let report = passport
+            .validate(
+                "hgt:59cm ecl:zzz\neyr:2038 hcl:74454a iyr:2023 foo:1\npid:3556412378 ecl:amb",
+            )
+            .unwrap();
+        assert!(!report.is_complete() && !report.is_valid());
+        assert_eq!(
+            report.to_string(),
+            "hgt: invalid '59cm', 59cm is outside 150-193cm; \
+             ecl: invalid 'zzz', expected one of amb blu brn gry grn hzl oth; \
+             eyr: invalid '2038', 2038 is outside 2020-2030; \
+             hcl: invalid '74454a', expected '#' and 6 hex digits; \
+             iyr: invalid '2023', 2023 is outside 2010-2020; \
+             foo: unknown field; \
+             pid: invalid '3556412378', expected 9 digits; \
+             ecl: repeated; \
+             byr: missing"
+        );
+        let report = passport.validate("hgt:170 byr:19x0").unwrap();
+        assert_eq!(
+            report.diagnostics[0].to_string(),
+            "hgt: invalid '170', expected a unit of cm or in"
+        );
+        assert_eq!(
+            report.diagnostics[1].problem,
+            Problem::Invalid {
+                value: "19x0".to_string(),
+                reason: "expected a number, found '19x0'".to_string()
+            }
+        );
+        assert_eq!(report.diagnostics.len(), 7);
+        assert_eq!(
+            passport.validate("byr:1920 hgt").unwrap_err().to_string(),
+            "line 1, column 10: expected 'key:value' field, found 'hgt'"
+        );
+
+        // Other record formats, from a file
+        let path = std::env::temp_dir().join("advent2020_test_schema.txt");
+        std::fs::write(&path, "# Orders\nid: 6 digits\n\nqty: 1-99\nnote?: any\n").unwrap();
+        let orders = Schema::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(orders.fields().len(), 3);
+        assert!(orders.validate("id:000042 qty:3").unwrap().is_valid());
+        assert_eq!(
+            orders.validate("note:rush qty:100").unwrap().to_string(),
+            "qty: invalid '100', 100 is outside 1-99; id: missing"
+        );
+        let readings: Schema = "depth: 1-20 m | 150-2000 cm\ntemp: -40--10\n"
+            .parse()
+            .unwrap();
+        assert_eq!(readings.field("temp").unwrap().rule, Rule::Range(-40..=-10));
+        assert_eq!(
+            readings.to_string(),
+            "depth: 1-20 m | 150-2000 cm\ntemp: -40--10\n"
+        );
+        assert!(readings
+            .validate("depth:150cm temp:-12")
+            .unwrap()
+            .is_valid());
+        assert!(readings.validate("depth:15m temp:-40").unwrap().is_valid());
+        assert_eq!(
+            readings
+                .validate("depth:100cm temp:-5")
+                .unwrap()
+                .to_string(),
+            "depth: invalid '100cm', 100cm is outside 150-2000cm; \
+             temp: invalid '-5', -5 is outside -40--10"
+        );
+        for (schema, error) in [
+            (
+                "id: 6 digits\nqty 1-99\n",
+                "line 2, column 1: expected 'KEY: RULE'",
+            ),
+            (
+                "id: 6 digits\nid?: any\n",
+                "line 2, column 1: duplicate field 'id'",
+            ),
+            ("qty: 9-1\n", "line 1, column 6: invalid range '9-1'"),
+            (
+                "hgt: 1-2 cm | 3-x in\n",
+                "line 1, column 17: invalid number 'x'",
+            ),
+            ("hcl: colour\n", "line 1, column 6: unknown rule 'colour'"),
+        ] {
+            assert_eq!(schema.parse::<Schema>().unwrap_err().to_string(), error);
+        }
+    }
+
+    #[test]
+    fn test_05() {
+        for (pass, id) in [
+            ("FBFBBFFRLR", 357),
+            ("BFFFBBFRRR", 567),
+            ("FFFBBBFRRR", 119),
+        ] {
+            assert_eq!(day05::Day05::parse(pass).unwrap(), vec![id]);
+        }
+        let mut seats = day05::Day05::parse("BFFFBBFRRR\nBBFFBBFRLL\nFFFBBBFRRR\n").unwrap();
+        assert_eq!(day05::Day05::part_1(&mut seats), 820);
+        let results = example(5, "FFFFFFFLLR\nFFFFFFFLRL\nFFFFFFFRLL\n");
+        assert_eq!(results.part_1, 4);
+        assert_eq!(results.part_2, 3);
+
+        test_day!(5, 892, 625);
+
+        // Codec for other plane sizes, seat map and every gap
+        use day05::{Codec, Seat, SeatMap, PLANE};
+        assert_eq!(PLANE.encode(357).unwrap(), "FBFBBFFRLR");
+        assert_eq!(PLANE.encode(1024), None);
+        assert!(
+            (0..PLANE.seats()).all(|id| PLANE.decode(&PLANE.encode(id).unwrap()).unwrap() == id)
+        );
+        assert_eq!(PLANE.seat(357), (44, 5));
+        let small = Codec::new(2, 2);
+        assert_eq!((small.rows(), small.columns(), small.bits()), (4, 4, 4));
+        assert_eq!(small.encode(6).unwrap(), "FBRL");
+        assert_eq!(small.decode("BFLR").unwrap(), 9);
+        assert_eq!(
+            small.decode("BLLR").unwrap_err().to_string(),
+            "line 1, column 2: unexpected character 'L', expected F or B"
+        );
+        assert_eq!(
+            PLANE.decode("FBFBBFFRLé").unwrap_err().to_string(),
+            "line 1, column 10: unexpected character 'é', expected L or R"
+        );
+        assert!(small.decode("BFL").is_err());
+        let map = SeatMap::new(small, &[2, 3, 5, 8, 9, 11]);
+        assert_eq!(map.span(), Some(2..=11));
+        assert_eq!(map.gaps(), vec![4..=4, 6..=7, 10..=10]);
+        assert_eq!(
+            [map.seat(1), map.seat(4), map.seat(5)],
+            [Seat::Missing, Seat::Empty, Seat::Occupied]
+        );
+        assert_eq!(map.render(), "..##\nL#LL\n##L#\n....\n");
+        assert_eq!(SeatMap::new(small, &[]).gaps(), vec![]);
+        assert_eq!(
+            day05::Day05::parse("FBFBBFFRLR\nFBFBBFFRLX\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 10: unexpected character 'X', expected L or R"
+        );
+    }
+
+    #[test]
+    fn test_06() {
+        let results = example(6, "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n");
+        assert_eq!(results.part_1, 11);
+        assert_eq!(results.part_2, 6);
+
+        test_day!(6, 6_249, 3_103);
+    }
+
+    #[test]
+    fn test_07() {
+        let results = example(
+            7,
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+             dark orange bags contain 3 bright white bags, 4 muted yellow bags.\n\
+             bright white bags contain 1 shiny gold bag.\n\
+             muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.\n\
+             shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.\n\
+             dark olive bags contain 3 faded blue bags, 4 dotted black bags.\n\
+             vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.\n\
+             faded blue bags contain no other bags.\n\
+             dotted black bags contain no other bags.\n",
+        );
+        assert_eq!(results.part_1, 4);
+        assert_eq!(results.part_2, 32);
+        let results = example(
+            7,
+            "shiny gold bags contain 2 dark red bags.\n\
+             dark red bags contain 2 dark orange bags.\n\
+             dark orange bags contain 2 dark yellow bags.\n\
+             dark yellow bags contain 2 dark green bags.\n\
+             dark green bags contain 2 dark blue bags.\n\
+             dark blue bags contain 2 dark violet bags.\n\
+             dark violet bags contain no other bags.\n",
+        );
+        assert_eq!(results.part_2, 126);
+        let results = example(
+            7,
+            "shiny gold bags contain 99999999999 dark red bags.\n\
+             dark red bags contain 99999999999 faded blue bags.\n",
+        );
+        assert_eq!(results.part_2, Answer::None);
+
+        test_day!(7, 332, 10_875);
+    }
+
+    #[test]
+    fn test_08() {
+        let results = example(
+            8,
+            "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n",
+        );
+        assert_eq!(results.part_1, 5);
+        assert_eq!(results.part_2, 8);
+
+        test_day!(8, 1_594, 758);
+
+        // A patch that runs into a loop of its own
+        let results = example(8, "jmp +2\nacc +5\njmp -2\njmp +0\n");
+        assert_eq!(results.part_1, 0);
+        assert_eq!(results.part_2, Answer::None);
+    }
+
+    #[test]
+    fn test_09() {
+        let values = [
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
+            576,
+        ];
+        assert_eq!(day09::first_invalid(&values, 5), Some(127));
+        assert_eq!(day09::encryption_weakness(&values, 127), Some(62));
+
+        test_day!(9, 756_008_079, 93_727_241);
+    }
+
+    #[test]
+    fn test_10() {
+        let results = example(10, "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n");
+        assert_eq!(results.part_1, 35);
+        assert_eq!(results.part_2, 8);
+        let results = example(
+            10,
+            "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n\
+             25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n",
+        );
+        assert_eq!(results.part_1, 220);
+        assert_eq!(results.part_2, 19_208);
+
+        test_day!(10, 2_244, 3_947_645_370_368);
+    }
+
+    #[test]
+    fn test_11() {
+        let results = example(
+            11,
+            "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\n\
+             L.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL\n",
+        );
+        assert_eq!(results.part_1, 37);
+        assert_eq!(results.part_2, 26);
+
+        test_day!(11, 2_263, 2_002);
+
+        // Seats that flip back and forth forever
+        let results = example(11, ".LL.\nLLLL\nLLLL\n.LL.\n");
+        assert_eq!(results.part_1, Answer::None);
+        assert_eq!(results.part_2, 8);
+    }
+
+    #[test]
+    fn test_12() {
+        let results = example(12, "F10\nN3\nF7\nR90\nF11\n");
+        assert_eq!(results.part_1, 25);
+        assert_eq!(results.part_2, 286);
+
+        test_day!(12, 879, 18_107);
+    }
+
+    #[test]
+    fn test_13() {
+        let results = example(13, "939\n7,13,x,x,59,x,31,19\n");
+        assert_eq!(results.part_1, 295);
+        assert_eq!(results.part_2, 1_068_781);
+        for (buses, time) in [
+            ("17,x,13,19", 3_417),
+            ("67,7,59,61", 754_018),
+            ("67,x,7,59,61", 779_210),
+            ("67,7,x,59,61", 1_261_476),
+            ("1789,37,47,1889", 1_202_161_486),
+        ] {
+            assert_eq!(example(13, &format!("939\n{}\n", buses)).part_2, time);
+        }
+
+        test_day!(13, 2_045, 402_251_700_208_309);
+    }
+
+    #[test]
+    fn test_14() {
+        // The first example has too many floating bits for part 2
+        let mut program = day14::Day14::parse(
+            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmem[7] = 101\nmem[8] = 0\n",
+        )
+        .unwrap();
+        assert_eq!(day14::Day14::part_1(&mut program), 165);
+        let results = example(
+            14,
+            "mask = 000000000000000000000000000000X1001X\nmem[42] = 100\n\
+             mask = 00000000000000000000000000000000X0XX\nmem[26] = 1\n",
+        );
+        assert_eq!(results.part_2, 208);
+
+        test_day!(14, 15_172_047_086_292, 4_197_941_339_968);
+    }
+
+    #[test]
+    fn test_15() {
+        // Part 2 of the examples takes too long for a unit test
+        for (starters, number) in [
+            ("0,3,6", 436),
+            ("1,3,2", 1),
+            ("2,1,3", 10),
+            ("1,2,3", 27),
+            ("2,3,1", 78),
+            ("3,2,1", 438),
+            ("3,1,2", 1_836),
+        ] {
+            let mut values = day15::Day15::parse(starters).unwrap();
+            assert_eq!(day15::Day15::part_1(&mut values), number);
+        }
+
+        test_day!(15, 763, 1_876_406);
+    }
+
+    #[test]
+    fn test_16() {
+        let results = example(
+            16,
+            "class: 1-3 or 5-7\nrow: 6-11 or 33-44\nseat: 13-40 or 45-50\n\n\
+             your ticket:\n7,1,14\n\nnearby tickets:\n7,3,47\n40,4,50\n55,2,20\n38,6,12\n",
+        );
+        assert_eq!(results.part_1, 71);
+        // Second example, with departure fields so the product is meaningful
+        let results = example(
+            16,
+            "departure class: 0-1 or 4-19\nrow: 0-5 or 8-19\ndeparture seat: 0-13 or 16-19\n\n\
+             your ticket:\n11,12,13\n\nnearby tickets:\n3,9,18\n15,1,5\n5,14,9\n",
+        );
+        assert_eq!(results.part_1, 0);
+        assert_eq!(results.part_2, 12 * 13);
+
+        test_day!(16, 26_988, 426_362_917_709);
+    }
+
+    #[test]
+    fn test_17() {
+        let results = example(17, ".#.\n..#\n###\n");
+        assert_eq!(results.part_1, 112);
+        assert_eq!(results.part_2, 848);
+
+        test_day!(17, 315, 1_520);
+    }
+
+    #[test]
+    fn test_18() {
+        for (expression, part_1, part_2) in [
+            ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+            ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+            ("2 * 3 + (4 * 5)", 26, 46),
+            ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1_445),
+            ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12_240, 669_060),
+            (
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                13_632,
+                23_340,
+            ),
+        ] {
+            let results = example(18, expression);
+            assert_eq!(results.part_1, part_1);
+            assert_eq!(results.part_2, part_2);
+        }
+
+        test_day!(18, 6_811_433_855_019, 129_770_152_447_927);
+    }
+
+    #[test]
+    fn test_19() {
+        let results = example(
+            19,
+            "0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"\n\n\
+             ababbb\nbababa\nabbbab\naaabbb\naaaabbb\n",
+        );
+        assert_eq!(results.part_1, 2);
+        let results = example(19, include_str!("../data/examples/day19.txt"));
+        assert_eq!(results.part_1, 3);
+        assert_eq!(results.part_2, 12);
+
+        test_day!(19, 239, 405);
+    }
+
+    #[test]
+    fn test_20() {
+        let results = example(20, include_str!("../data/examples/day20.txt"));
+        assert_eq!(results.part_1, 20_899_048_083_289);
+        assert_eq!(results.part_2, 273);
+
+        test_day!(20, 23_386_616_781_851, 2_376);
+    }
+
+    #[test]
+    fn test_21() {
+        let results = example(
+            21,
+            "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\n\
+             trh fvjkl sbzzf mxmxvkd (contains dairy)\n\
+             sqjhc fvjkl (contains soy)\n\
+             sqjhc mxmxvkd sbzzf (contains fish)\n",
+        );
+        assert_eq!(results.part_1, 5);
+        assert_eq!(results.part_2, "mxmxvkd,sqjhc,fvjkl");
+
+        test_day!(21, 2_230, "qqskn,ccvnlbp,tcm,jnqcd,qjqb,xjqd,xhzr,cjxv");
+    }
+
+    #[test]
+    fn test_22() {
+        let results = example(
+            22,
+            "Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n",
+        );
+        assert_eq!(results.part_1, 306);
+        assert_eq!(results.part_2, 291);
+
+        test_day!(22, 30_780, 36_621);
+
+        // A simple game that never ends
+        let results = example(22, "Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14\n");
+        assert_eq!(results.part_1, Answer::None);
+        assert_eq!(results.part_2, 105);
+    }
+
+    #[test]
+    fn test_23() {
+        // Part 2 of the example takes as long as the real input
+        let mut cups = day23::Day23::parse("389125467").unwrap();
+        assert_eq!(day23::Day23::part_1(&mut cups), "67384529");
+
+        test_day!(23, "59374826", 66_878_091_588);
+    }
+
+    #[test]
+    fn test_24() {
+        let results = example(
+            24,
+            "sesenwnenenewseeswwswswwnenewsewsw\nneeenesenwnwwswnenewnwwsewnenwseswesw\n\
+             seswneswswsenwwnwse\nnwnwneseeswswnenewneswwnewseswneseene\n\
+             swweswneswnenwsewnwneneseenw\neesenwseswswnenwswnwnwsewwnwsene\n\
+             sewnenenenesenwsewnenwwwse\nwenwwweseeeweswwwnwwe\n\
+             wsweesenenewnwwnwsenewsenwwsesesenwne\nneeswseenwwswnwswswnw\n\
+             nenwswwsewswnenenewsenwsenwnesesenew\nenewnwewneswsewnwswenweswnenwsenwsw\n\
+             sweneswneswneneenwnewenewwneswswnese\nswwesenesewenwneswnwwneseswwne\n\
+             enesenwswwswneneswsenwnewswseenwsese\nwnwnesenesenenwwnenwsewesewsesesew\n\
+             nenewswnwewswnenesenwnesewesw\neneswnwswnwsenenwnwnwwseeswneewsenese\n\
+             neswnwewnwnwseenwseesewsenwsweewe\nwseweeenwnesenwwwswnew\n",
+        );
+        assert_eq!(results.part_1, 10);
+        assert_eq!(results.part_2, 2_208);
+
+        test_day!(24, 332, 3_900);
+    }
+
+    #[test]
+    fn test_25() {
+        let results = example(25, "5764801\n17807724\n");
+        assert_eq!(results.part_1, 14_897_079);
+
+        test_day!(25, 18_608_573, Answer::None);
+    }
+}
+
+// -----------------------------------------------------------------------------
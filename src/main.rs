@@ -1,428 +1,404 @@
-#[macro_use]
-extern crate lazy_static;
-
 // -----------------------------------------------------------------------------
 // Modules
 // -----------------------------------------------------------------------------
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod output;
-
-// -----------------------------------------------------------------------------
-// Results struct
-// -----------------------------------------------------------------------------
-#[derive(Debug)]
-pub(crate) struct Results {
-    part_1: i64,
-    part_2: i64,
-    times: Timing,
-}
-
-impl Results {
-    fn new(part_1: i64, part_2: i64, times: Timing) -> Self {
-        Self {
-            part_1,
-            part_2,
-            times,
-        }
-    }
-}
-
-// -----------------------------------------------------------------------------
-// Timing struct
-// -----------------------------------------------------------------------------
-#[derive(Debug)]
-pub(crate) struct Timing {
-    setup: std::time::Duration,
-    part_1: std::time::Duration,
-    part_2: std::time::Duration,
-    combined: std::time::Duration,
-}
-
-impl Timing {
-    fn new(
-        setup: std::time::Duration,
-        part_1: std::time::Duration,
-        part_2: std::time::Duration,
-        combined: std::time::Duration,
-    ) -> Self {
-        Self {
-            setup,
-            part_1,
-            part_2,
-            combined,
-        }
-    }
-}
+mod cli;
+mod watch;
 
-// -----------------------------------------------------------------------------
-// Prelude
-// -----------------------------------------------------------------------------
-const REPETITIONS: u32 = 5;
-pub(crate) mod prelude {
-    pub(crate) use crate::REPETITIONS;
-    pub(crate) use crate::{output, Results, Timing};
-    pub(crate) use colored::*;
-    pub(crate) use std::time::Instant;
-}
+use advent2020::{
+    baseline, budget, error::Error, export, fuzz, generate, output, registry, report, spans,
+    statistics, Results, Timing,
+};
+use rayon::prelude::*;
 
 // -----------------------------------------------------------------------------
 // Main Driver
 // -----------------------------------------------------------------------------
 fn main() {
     // Setup
-    const DAYS: usize = 25;
-    let runs = [
-        day01::run,
-        day02::run,
-        day03::run,
-        day04::run,
-        day05::run,
-        day06::run,
-        day07::run,
-        day08::run,
-        day09::run,
-        day10::run,
-        day11::run,
-        day12::run,
-        day13::run,
-        day14::run,
-        day15::run,
-        day16::run,
-        day17::run,
-        day18::run,
-        day19::run,
-        day20::run,
-        day21::run,
-        day22::run,
-        day23::run,
-        day24::run,
-        day25::run,
-    ];
-    let reports = [
-        day01::report,
-        day02::report,
-        day03::report,
-        day04::report,
-        day05::report,
-        day06::report,
-        day07::report,
-        day08::report,
-        day09::report,
-        day10::report,
-        day11::report,
-        day12::report,
-        day13::report,
-        day14::report,
-        day15::report,
-        day16::report,
-        day17::report,
-        day18::report,
-        day19::report,
-        day20::report,
-        day21::report,
-        day22::report,
-        day23::report,
-        day24::report,
-        day25::report,
-    ];
-
-    // Each day
-    output::print_header();
-    let mut day_results: [Vec<Results>; DAYS] = [
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-    ];
-    for _ in 0..REPETITIONS {
-        for (i, day) in runs.iter().enumerate() {
-            day_results[i].push(day());
+    let options = match cli::parse_command(std::env::args().skip(1)) {
+        Ok(cli::Command::Run(options)) => options,
+        Ok(cli::Command::Generate { day, seed, size }) => {
+            let size = size.unwrap_or(generate::SIZES[day - 1]);
+            print!("{}", generate::input(day, seed, size).unwrap());
+            return;
         }
-    }
-    let average_times: Vec<Timing> = day_results
+        Ok(cli::Command::Compare { days, seeds, size }) => compare(&days, seeds, size),
+        Ok(cli::Command::Fuzz {
+            days,
+            seeds,
+            cases,
+            timeout,
+            solve,
+        }) => fuzz(&days, seeds, cases, timeout, solve),
+        Ok(cli::Command::Watch { options, interval }) => {
+            set_style(&options);
+            watch::watch(&options, interval)
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(if message == cli::USAGE { 0 } else { 2 });
+        }
+    };
+    let days: Vec<&registry::Entry> = options
+        .days
         .iter()
-        .map(|day| {
-            day.iter().fold(
-                Timing::new(
-                    std::time::Duration::new(0, 0),
-                    std::time::Duration::new(0, 0),
-                    std::time::Duration::new(0, 0),
-                    std::time::Duration::new(0, 0),
-                ),
-                |acc, result| {
-                    Timing::new(
-                        acc.setup + result.times.setup / REPETITIONS,
-                        acc.part_1 + result.times.part_1 / REPETITIONS,
-                        acc.part_2 + result.times.part_2 / REPETITIONS,
-                        acc.combined + result.times.combined / REPETITIONS,
-                    )
-                },
-            )
-        })
+        .map(|&day| registry::get(day).expect("day not registered"))
         .collect();
-    for i in 0..DAYS {
-        let result = day_results[i].first().unwrap();
-        let timing = &average_times[i];
-        reports[i](&Results::new(
-            result.part_1,
-            result.part_2,
-            Timing::new(timing.setup, timing.part_1, timing.part_2, timing.combined),
-        ));
-    }
+    let saved = options.baseline.as_ref().map(|path| {
+        baseline::load(path).unwrap_or_else(|error| {
+            eprintln!("error: baseline {}: {}", path.display(), error);
+            std::process::exit(1);
+        })
+    });
 
-    // Day comparison
-    output::print_header();
-    let time_averages = average_times
-        .iter()
-        .map(|day| {
-            if day.combined.as_nanos() > 1 {
-                std::cmp::min(day.combined, day.setup + day.part_1 + day.part_2)
-            } else {
-                day.setup + day.part_1 + day.part_2
-            }
+    // Each day
+    let text = options.format == cli::Format::Text;
+    if text {
+        set_style(&options);
+        output::print_header();
+    }
+    let measure = |day: &registry::Entry| {
+        measure(day, &options).unwrap_or_else(|error| {
+            eprintln!("error: Dec {:02} {}: {}", day.number, day.name, error);
+            std::process::exit(1);
         })
-        .collect::<Vec<_>>();
-    let time_std_devs: Vec<f64> = time_averages
+    };
+    // Days are independent, but running them side by side skews the timing
+    let (day_results, day_statistics): (Vec<Vec<Results>>, Vec<statistics::Statistics>) =
+        if options.parallel {
+            days.par_iter().map(|day| measure(day)).unzip()
+        } else {
+            days.iter().map(|day| measure(day)).unzip()
+        };
+    let average_times: Vec<Timing> = day_results
         .iter()
-        .zip(day_results.iter())
-        .map(|(averages, day)| {
-            (day.iter().fold(0.0, |acc, repetition| {
-                let current = if repetition.times.combined.as_nanos() > 1 {
-                    repetition.times.combined.as_nanos()
-                } else {
-                    repetition.times.setup.as_nanos()
-                        + repetition.times.part_1.as_nanos()
-                        + repetition.times.part_2.as_nanos()
-                };
-                acc + ((averages.as_nanos() as f64 - current as f64) / 1000.0).powf(2.0)
-                    / ((REPETITIONS - 1) as f64)
-            }))
-            .powf(0.5)
-        })
+        .zip(&day_statistics)
+        .map(|(day, statistics)| average(day, statistics))
         .collect();
-    output::print_days_timing(&time_averages, &time_std_devs);
-    output::print_header();
-}
-
-// -----------------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------------
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const MAX_TIME: u128 = 250;
-    macro_rules! test_day {
-        ($results:expr, $part_1:expr, $part_2:expr) => {
-            assert_eq!($results.part_1, $part_1);
-            assert_eq!($results.part_2, $part_2);
-            assert!(
-                ($results.times.setup + $results.times.part_1 + $results.times.part_2).as_millis()
-                    < MAX_TIME
-            );
-        };
-    }
-
-    #[test]
-    fn test_01() {
-        let results = day01::run();
-        test_day!(results, 326_211, 131_347_190);
-    }
-
-    #[test]
-    fn test_02() {
-        let results = day02::run();
-        test_day!(results, 538, 489);
-    }
-
-    #[test]
-    fn test_03() {
-        let results = day03::run();
-        test_day!(results, 176, 5_872_458_240);
-    }
-
-    #[test]
-    fn test_04() {
-        let results = day04::run();
-        test_day!(results, 182, 109);
-    }
-
-    #[test]
-    fn test_05() {
-        let results = day05::run();
-        test_day!(results, 892, 625);
-    }
-
-    #[test]
-    fn test_06() {
-        let results = day06::run();
-        test_day!(results, 6_249, 3_103);
-    }
-
-    #[test]
-    fn test_07() {
-        let results = day07::run();
-        test_day!(results, 332, 10_875);
-    }
-
-    #[test]
-    fn test_08() {
-        let results = day08::run();
-        test_day!(results, 1_594, 758);
-    }
-
-    #[test]
-    fn test_09() {
-        let results = day09::run();
-        test_day!(results, 756_008_079, 93_727_241);
-    }
-
-    #[test]
-    fn test_10() {
-        let results = day10::run();
-        test_day!(results, 2_244, 3_947_645_370_368);
-    }
 
-    #[test]
-    fn test_11() {
-        let results = day11::run();
-        test_day!(results, 2_263, 2_002);
+    // Baseline
+    let comparisons: Vec<Option<baseline::Comparison>> = match &saved {
+        Some(saved) => days
+            .iter()
+            .zip(&day_statistics)
+            .map(|(day, statistics)| {
+                saved.samples(day.number).map(|saved| {
+                    baseline::compare(saved, &statistics.samples, options.threshold / 100.0)
+                })
+            })
+            .collect(),
+        None => vec![],
+    };
+    if let Some(path) = &options.save_baseline {
+        let samples: Vec<(usize, Vec<f64>)> = days
+            .iter()
+            .map(|day| day.number)
+            .zip(
+                day_statistics
+                    .iter()
+                    .map(|statistics| statistics.samples.clone()),
+            )
+            .collect();
+        if let Err(error) = baseline::save(path, &samples) {
+            eprintln!("error: baseline {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
     }
 
-    #[test]
-    fn test_12() {
-        let results = day12::run();
-        test_day!(results, 879, 18_107);
-    }
+    // Budgets
+    let overruns: Vec<Vec<budget::Overrun>> = if options.check_budgets {
+        days.iter()
+            .zip(&average_times)
+            .map(|(day, times)| day.budget.overruns(times))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    // Output
+    match options.format {
+        cli::Format::Text => {
+            for (i, day) in days.iter().enumerate() {
+                (day.report)(
+                    &summary(&day_results[i], &day_statistics[i]),
+                    &day_statistics[i],
+                );
+            }
 
-    #[test]
-    fn test_13() {
-        let results = day13::run();
-        test_day!(results, 2_045, 402_251_700_208_309);
+            // Day comparison
+            output::print_header();
+            output::print_days_timing(&days, &day_statistics, options.warmup, options.parallel);
+            if saved.is_some() {
+                output::print_header();
+                output::print_comparison(&days, &comparisons, options.threshold);
+            }
+            if options.check_budgets {
+                output::print_header();
+                output::print_budgets(&days, &overruns);
+            }
+            output::print_header();
+        }
+        format => {
+            let summaries: Vec<export::Summary> = (0..days.len())
+                .map(|i| export::Summary {
+                    day: days[i],
+                    samples: &day_results[i],
+                    average: &average_times[i],
+                    statistics: &day_statistics[i],
+                })
+                .collect();
+            match format {
+                cli::Format::Json => print!(
+                    "{}",
+                    export::json(&summaries, options.warmup, options.parallel)
+                ),
+                cli::Format::Markdown => print!(
+                    "{}",
+                    report::markdown(&summaries, options.warmup, options.parallel)
+                ),
+                cli::Format::Html => print!(
+                    "{}",
+                    report::html(&summaries, options.warmup, options.parallel)
+                ),
+                _ => print!("{}", export::csv(&summaries)),
+            }
+            for (day, overruns) in days.iter().zip(&overruns) {
+                for overrun in overruns {
+                    eprintln!("error: Dec {:02} {}: {}", day.number, day.name, overrun);
+                }
+            }
+        }
     }
 
-    #[test]
-    fn test_14() {
-        let results = day14::run();
-        test_day!(results, 15_172_047_086_292, 4_197_941_339_968);
+    // Regressions
+    let regressions = comparisons
+        .iter()
+        .flatten()
+        .filter(|comparison| comparison.regression)
+        .count();
+    if regressions > 0 {
+        eprintln!("error: {} regression(s) against the baseline", regressions);
+        std::process::exit(3);
     }
 
-    #[test]
-    fn test_15() {
-        let results = day15::run();
-        assert_eq!(results.part_1, 763);
-        assert_eq!(results.part_2, 1_876_406);
+    // Budget overruns
+    if overruns.iter().any(|overruns| !overruns.is_empty()) {
+        eprintln!("error: time budget exceeded");
+        std::process::exit(4);
     }
+}
 
-    #[test]
-    fn test_16() {
-        let results = day16::run();
-        test_day!(results, 26_988, 426_362_917_709);
-    }
+// -----------------------------------------------------------------------------
+// Style
+// -----------------------------------------------------------------------------
+// Console style from the terminal and the options
+fn set_style(options: &cli::Options) {
+    let mut style = output::Style::detect();
+    style.color &= !options.no_color;
+    style.ascii |= options.ascii;
+    style.width = options.width.unwrap_or(style.width);
+    output::set_style(style);
+}
 
-    #[test]
-    fn test_17() {
-        let results = day17::run();
-        test_day!(results, 315, 1_520);
+// -----------------------------------------------------------------------------
+// Measure
+// -----------------------------------------------------------------------------
+// Warmup runs, then repetitions until the mean is accurate enough, if requested
+fn measure(
+    day: &registry::Entry,
+    options: &cli::Options,
+) -> Result<(Vec<Results>, statistics::Statistics), Error> {
+    let input = options.input(day.number);
+    for _ in 0..options.warmup {
+        (day.run)(input)?;
     }
-
-    #[test]
-    fn test_18() {
-        let results = day18::run();
-        test_day!(results, 6_811_433_855_019, 129_770_152_447_927);
+    let mut results: Vec<Results> = vec![];
+    let mut samples: Vec<f64> = vec![];
+    loop {
+        let result = (day.run)(input)?;
+        samples.push(result.times.total().as_nanos() as f64);
+        results.push(result);
+        let count = results.len() as u32;
+        if count < options.repetitions {
+            continue;
+        }
+        match options.target_error {
+            Some(target) if count < options.max_repetitions => {
+                if statistics::Statistics::new(&samples).relative_error() <= target / 100.0 {
+                    break;
+                }
+            }
+            _ => break,
+        }
     }
+    Ok((results, statistics::Statistics::new(&samples)))
+}
 
-    #[test]
-    fn test_19() {
-        let results = day19::run();
-        assert_eq!(results.part_1, 239);
-        assert_eq!(results.part_2, 405);
-    }
+// Average phase times of the repetitions that are not outliers
+fn average(results: &[Results], statistics: &statistics::Statistics) -> Timing {
+    let kept: Vec<&Timing> = results
+        .iter()
+        .zip(&statistics.outliers)
+        .filter(|(_, &outlier)| !outlier)
+        .map(|(result, _)| &result.times)
+        .collect();
+    Timing::average(&kept)
+}
 
-    #[test]
-    fn test_20() {
-        let results = day20::run();
-        assert_eq!(results.part_1, 23_386_616_781_851);
-        assert_eq!(results.part_2, 2_376);
-    }
+// Results to report for the repetitions, with the average times and spans
+fn summary(results: &[Results], statistics: &statistics::Statistics) -> Results {
+    let result = results.first().unwrap();
+    let mut summary = Results::new(
+        result.part_1.clone(),
+        result.part_2.clone(),
+        average(results, statistics),
+    );
+    // Allocations are the same for each repetition, so report the first
+    summary.memory = result.memory.clone();
+    let kept_spans: Vec<&[spans::SpanTime]> = results
+        .iter()
+        .zip(&statistics.outliers)
+        .filter(|(_, &outlier)| !outlier)
+        .map(|(result, _)| &result.spans[..])
+        .collect();
+    summary.spans = spans::average(&kept_spans);
+    summary
+}
 
-    #[test]
-    fn test_21() {
-        let results = day21::run();
-        assert_eq!(results.part_1, 2_230);
-        assert_eq!(results.part_2, 8);
+// -----------------------------------------------------------------------------
+// Compare
+// -----------------------------------------------------------------------------
+#[cfg(feature = "reference")]
+fn compare(days: &[usize], seeds: u64, size: Option<usize>) -> ! {
+    for &day in days {
+        let size = size.unwrap_or(generate::SIZES[day - 1]);
+        match advent2020::reference::compare(day, 0..seeds, size) {
+            Ok(agreed) => println!("Dec {:02}: {} inputs agree", day, agreed),
+            Err(divergence) => {
+                eprintln!("{}", divergence);
+                std::process::exit(5);
+            }
+        }
     }
+    std::process::exit(0);
+}
 
-    #[test]
-    fn test_22() {
-        let results = day22::run();
-        assert_eq!(results.part_1, 30_780);
-        assert_eq!(results.part_2, 36_621);
-    }
+#[cfg(not(feature = "reference"))]
+fn compare(_days: &[usize], _seeds: u64, _size: Option<usize>) -> ! {
+    eprintln!("compare requires the reference feature, see --help");
+    std::process::exit(2);
+}
 
-    #[test]
-    fn test_23() {
-        let results = day23::run();
-        assert_eq!(results.part_1, 59_374_826);
-        assert_eq!(results.part_2, 66_878_091_588);
+// -----------------------------------------------------------------------------
+// Fuzz
+// -----------------------------------------------------------------------------
+fn fuzz(days: &[usize], seeds: u64, cases: usize, timeout: std::time::Duration, solve: bool) -> ! {
+    let accepted = if solve { "solved" } else { "parsed" };
+    for &day in days {
+        match fuzz::fuzz(day, 0..seeds, cases, timeout, solve) {
+            Ok(tally) => println!(
+                "Dec {:02}: {} inputs {}, {} rejected",
+                day, tally.parsed, accepted, tally.rejected
+            ),
+            Err(finding) => {
+                eprintln!("{}", finding);
+                let path = format!("fuzz-day{:02}.txt", day);
+                match std::fs::write(&path, &finding.input) {
+                    Ok(()) => {
+                        eprintln!("reproduce with: advent2020 --day {} --input {}", day, path)
+                    }
+                    Err(error) => eprintln!("error: {}: {}", path, error),
+                }
+                std::process::exit(6);
+            }
+        }
     }
+    std::process::exit(0);
+}
 
-    #[test]
-    fn test_24() {
-        let results = day24::run();
-        assert_eq!(results.part_1, 332);
-        assert_eq!(results.part_2, 3_900);
-    }
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent2020::Input;
 
     #[test]
-    fn test_25() {
-        let results = day25::run();
-        assert_eq!(results.part_1, 18_608_573);
-        assert_eq!(results.part_2, 0);
+    fn test_cli() {
+        let args = |s: &str| s.split(' ').map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let options = cli::parse_args(args("--days 3-5 -r 2 -i 4=input.txt").into_iter()).unwrap();
+        assert_eq!(options.days, vec![3, 4, 5]);
+        assert_eq!(options.repetitions, 2);
+        assert!(matches!(options.input(4), Input::Path(path) if path.ends_with("input.txt")));
+        let options = cli::parse_args(args("-d 4 -i data/run=1/input.txt").into_iter()).unwrap();
+        assert!(
+            matches!(options.input(4), Input::Path(path) if path.to_str() == Some("data/run=1/input.txt"))
+        );
+        let options = cli::parse_args(args("-d 4 -i 4=run=1.txt").into_iter()).unwrap();
+        assert!(
+            matches!(options.input(4), Input::Path(path) if path.to_str() == Some("run=1.txt"))
+        );
+        assert!(!options.check_budgets);
+        assert!(
+            cli::parse_args(args("--check-budgets").into_iter())
+                .unwrap()
+                .check_budgets
+        );
+        assert!(!options.parallel);
+        assert_eq!(options.width, None);
+        let options = cli::parse_args(args("--ascii --no-color --width 60").into_iter()).unwrap();
+        assert!(options.ascii && options.no_color);
+        assert_eq!(options.width, Some(60));
+        assert!(cli::parse_args(args("--width 10").into_iter()).is_err());
+        assert_eq!(
+            cli::parse_args(args("-p").into_iter()).map(|options| options.parallel),
+            if advent2020::memory::ENABLED {
+                Err("--parallel cannot be used with the memory feature".to_string())
+            } else {
+                Ok(true)
+            }
+        );
+        assert!(cli::parse_args(args("--day 26").into_iter()).is_err());
+        assert!(cli::parse_args(args("--days 1-2 --input input.txt").into_iter()).is_err());
+        let command = cli::parse_command(args("generate --day 9 --seed 7").into_iter()).unwrap();
+        assert!(matches!(
+            command,
+            cli::Command::Generate {
+                day: 9,
+                seed: 7,
+                size: None
+            }
+        ));
+        let command = cli::parse_command(args("generate -d 3 --size 40").into_iter()).unwrap();
+        assert!(matches!(
+            command,
+            cli::Command::Generate { size: Some(40), .. }
+        ));
+        assert!(cli::parse_command(args("generate --seed 1").into_iter()).is_err());
+        let command = cli::parse_command(args("compare --days 3-4 --seeds 5").into_iter()).unwrap();
+        assert!(matches!(
+            command,
+            cli::Command::Compare { ref days, seeds: 5, size: None } if days == &[3, 4]
+        ));
+        let command = cli::parse_command(args("fuzz -d 10 --cases 50").into_iter()).unwrap();
+        assert!(matches!(
+            command,
+            cli::Command::Fuzz { ref days, seeds: 1, cases: 50, solve: false, .. } if days == &[10]
+        ));
+        assert!(cli::parse_command(args("fuzz --timeout 0").into_iter()).is_err());
+        let command = cli::parse_command(args("watch -d 10 --interval 50 -r 2").into_iter());
+        assert!(matches!(
+            command,
+            Ok(cli::Command::Watch { ref options, interval })
+                if options.days == [10] && options.repetitions == 2 && interval.as_millis() == 50
+        ));
+        assert!(cli::parse_command(args("watch --days 1-2").into_iter()).is_err());
+        assert!(cli::parse_command(args("watch -d 10 -f json").into_iter()).is_err());
+        assert!(matches!(
+            cli::parse_command(args("-d 3").into_iter()),
+            Ok(cli::Command::Run(_))
+        ));
     }
 }
 
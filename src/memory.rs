@@ -0,0 +1,170 @@
+//! Memory:
+//! With the `memory` feature, a global allocator wraps the system allocator and counts the
+//! allocations, the bytes allocated and the peak live bytes of each phase of a day. The
+//! counters are global, so the numbers are only meaningful when the days run one at a
+//! time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Counters
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the counting allocator is installed
+pub const ENABLED: bool = cfg!(feature = "memory");
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// -----------------------------------------------------------------------------
+// Counting allocator
+// -----------------------------------------------------------------------------
+/// System allocator that keeps count of allocations and live bytes
+#[derive(Debug)]
+pub struct CountingAllocator;
+
+fn grow(allocated: usize, growth: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(allocated as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(growth as u64, Ordering::Relaxed) + growth as u64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size(), layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size(), layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // Count a reallocation as a new allocation of the full size
+            if new_size >= layout.size() {
+                grow(new_size, new_size - layout.size());
+            } else {
+                grow(new_size, 0);
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Allocations struct
+// -----------------------------------------------------------------------------
+/// Allocator activity during one phase of a day
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Allocations {
+    /// Number of allocations, including reallocations
+    pub count: u64,
+    /// Total bytes allocated
+    pub bytes: u64,
+    /// Peak live bytes, above what was live when the phase started
+    pub peak: u64,
+}
+
+impl std::fmt::Display for Allocations {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocation{}, {} allocated, {} peak",
+            self.count,
+            if self.count == 1 { "" } else { "s" },
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+// Bytes in binary units
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Counter struct
+// -----------------------------------------------------------------------------
+/// Allocator counters at the start of a phase
+#[derive(Debug)]
+pub struct Counter {
+    allocations: u64,
+    allocated: u64,
+    live: u64,
+}
+
+impl Counter {
+    /// Start counting a phase; this resets the peak to the current live bytes
+    pub fn start() -> Self {
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated: ALLOCATED.load(Ordering::Relaxed),
+            live,
+        }
+    }
+
+    /// Allocator activity since the start of the phase
+    pub fn stop(&self) -> Allocations {
+        Allocations {
+            count: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: ALLOCATED.load(Ordering::Relaxed) - self.allocated,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.live),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Usage struct
+// -----------------------------------------------------------------------------
+/// Allocator activity in each phase of a day
+#[derive(Debug, Clone, PartialEq)]
+pub struct Usage {
+    /// Reading and parsing the input
+    pub setup: Allocations,
+    /// Solving part 1
+    pub part_1: Allocations,
+    /// Solving part 2
+    pub part_2: Allocations,
+    /// Solving both parts together, if the day supports it
+    pub combined: Option<Allocations>,
+}
+
+// -----------------------------------------------------------------------------
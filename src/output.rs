@@ -1,56 +1,162 @@
 //! Output:
-//! This module collects some of my `println!` boilerplate between the days.
+//! Printing helpers shared between the days and the driver. The style can be switched to
+//! plain text without colors or emoji, and the bars follow the width of the terminal.
 
+use crate::memory;
 use crate::prelude::*;
+use crate::registry::Entry;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
 
 // Constants
-const NUMBER_DASHES: usize = 80;
+const DEFAULT_WIDTH: usize = 80;
+const MIN_WIDTH: usize = 40;
+
+// Current style
+static ASCII: AtomicBool = AtomicBool::new(false);
+static WIDTH: AtomicUsize = AtomicUsize::new(DEFAULT_WIDTH);
+
+// -----------------------------------------------------------------------------
+// Style struct
+// -----------------------------------------------------------------------------
+/// How the console output looks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    /// Use colors and bold text
+    pub color: bool,
+    /// Only print ASCII characters, so no emoji or 'µs'
+    pub ascii: bool,
+    /// Width of the bars and banners, in columns
+    pub width: usize,
+}
+
+impl Style {
+    /// Style for the current stdout; no color if `NO_COLOR` is set or stdout is not a
+    /// terminal, and the width of the terminal if it can be found
+    pub fn detect() -> Self {
+        use std::io::IsTerminal;
+        let terminal = std::io::stdout().is_terminal();
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self {
+            color: terminal && !no_color,
+            ascii: false,
+            width: if terminal {
+                terminal_width().unwrap_or(DEFAULT_WIDTH)
+            } else {
+                DEFAULT_WIDTH
+            },
+        }
+    }
+}
+
+/// Use a style for all following output
+pub fn set_style(style: Style) {
+    colored::control::set_override(style.color);
+    ASCII.store(style.ascii, Ordering::Relaxed);
+    WIDTH.store(std::cmp::max(MIN_WIDTH, style.width), Ordering::Relaxed);
+}
+
+// Width from $COLUMNS, or from asking stty about the controlling terminal
+fn terminal_width() -> Option<usize> {
+    if let Some(columns) = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()) {
+        return Some(columns);
+    }
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    let output = std::process::Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    // Output is 'rows columns'
+    String::from_utf8(output.stdout)
+        .ok()?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+fn width() -> usize {
+    WIDTH.load(Ordering::Relaxed)
+}
+
+// Text with non-ASCII characters replaced, in ASCII mode
+fn text(s: String) -> String {
+    if ASCII.load(Ordering::Relaxed) {
+        s.replace('µ', "u")
+            .chars()
+            .filter(|c| c.is_ascii())
+            .collect::<String>()
+            .trim_start()
+            .to_string()
+    } else {
+        s
+    }
+}
+
+fn time(duration: Duration) -> String {
+    text(format!("{:?}", duration))
+}
 
 // -----------------------------------------------------------------------------
 // Print header
 // -----------------------------------------------------------------------------
-pub(crate) fn print_header() {
-    println!("{}", "-".repeat(NUMBER_DASHES).green().bold());
+/// Print the banner
+pub fn print_header() {
+    let width = width();
+    println!("{}", "-".repeat(width).green().bold());
     println!(
         "{} {} {}",
-        "-".repeat(NUMBER_DASHES / 2 - 10).red().bold(),
+        "-".repeat(width / 2 - 10).red().bold(),
         "Advent of Code 2020".bold(),
-        "-".repeat(NUMBER_DASHES / 2 - 11).red().bold()
+        "-".repeat(width - width / 2 - 11).red().bold()
     );
-    println!("{}", "-".repeat(NUMBER_DASHES).green().bold());
+    println!("{}", "-".repeat(width).green().bold());
 }
 
 // -----------------------------------------------------------------------------
 // Output day information
 // -----------------------------------------------------------------------------
-pub(crate) fn print_day(day: usize, name: &str) {
+/// Print the title of a day
+pub fn print_day(day: usize, name: &str) {
     println!("- {}", format!("Day {:02} --- {} ---", day, name).bold());
 }
 
 // -----------------------------------------------------------------------------
 // Output part summary
 // -----------------------------------------------------------------------------
-pub(crate) fn print_part(part: usize, output: &str, output_value: &str) {
+/// Print the answer to a part, with its label
+pub fn print_part(part: usize, output: &str, output_value: &Answer) {
     let part_string = if part == 1 {
         "Part 1".red().bold()
     } else {
         "Part 2".green().bold()
     };
     println!("    {}:", part_string);
-    println!("      {}: {}", output, output_value);
+    let output = text(output.to_string());
+    match output_value {
+        Answer::None => println!("      {}", output),
+        _ => println!("      {}: {}", output, output_value),
+    }
 }
 
 // -----------------------------------------------------------------------------
 // Output timing summary
 // -----------------------------------------------------------------------------
-pub(crate) fn print_timing(times: &Timing) {
+/// Print the phase times, allocations and statistics for a day
+pub fn print_timing(times: &Timing, memory: Option<&memory::Usage>, statistics: &Statistics) {
     println!("    {}:", "Timing".purple().bold());
+    let width = width();
     let times_total = times.setup + times.part_1 + times.part_2;
     let part_1_percent = times.part_1.as_nanos() as f64 / times_total.as_nanos() as f64;
-    let mut part_1_portion = std::cmp::max(1, (NUMBER_DASHES as f64 * part_1_percent) as usize);
+    let mut part_1_portion = std::cmp::max(1, (width as f64 * part_1_percent) as usize);
     let part_2_percent = times.part_2.as_nanos() as f64 / times_total.as_nanos() as f64;
-    let mut part_2_portion = std::cmp::max(1, (NUMBER_DASHES as f64 * part_2_percent) as usize);
-    let mut setup_portion = NUMBER_DASHES - part_1_portion - part_2_portion;
+    let mut part_2_portion = std::cmp::max(1, (width as f64 * part_2_percent) as usize);
+    let mut setup_portion = width - part_1_portion - part_2_portion;
     if setup_portion == 0 {
         setup_portion = 1;
         if part_1_portion > part_2_portion {
@@ -60,36 +166,55 @@ pub(crate) fn print_timing(times: &Timing) {
         }
     }
     println!(
-        "      {}: {:?} ({:02.1}%)",
+        "      {}: {} ({:02.1}%)",
         "Setup".blue(),
-        times.setup,
+        time(times.setup),
         100.0 * (1.0 - part_1_percent - part_2_percent),
     );
     println!(
-        "      {}: {:?} ({:02.1}%)",
+        "      {}: {} ({:02.1}%)",
         "Part 1".red(),
-        times.part_1,
+        time(times.part_1),
         100.0 * part_1_percent,
     );
     println!(
-        "      {}: {:?} ({:02.1}%)",
+        "      {}: {} ({:02.1}%)",
         "Part 2".green(),
-        times.part_2,
+        time(times.part_2),
         100.0 * part_2_percent,
     );
-    println!("      Total: {:?}", times_total,);
+    println!("      Total: {}", time(times_total));
     println!(
         "      Combined Time: {}",
-        if times.combined.as_nanos() > 1 {
-            format!(
-                "{:?} ({:2.1}%)",
-                times.combined,
-                times.combined.as_nanos() as f64 / times_total.as_nanos() as f64 * 100.0
-            )
-        } else {
-            "N/A".to_string()
+        match times.combined {
+            Some(combined) => format!(
+                "{} ({:2.1}%)",
+                time(combined),
+                combined.as_nanos() as f64 / times_total.as_nanos() as f64 * 100.0
+            ),
+            None => "N/A".to_string(),
         }
     );
+    println!(
+        "      Median: {}, P90: {}, Min: {}",
+        time(statistics.median),
+        time(statistics.p90),
+        time(statistics.min)
+    );
+    println!(
+        "      Repetitions: {} ({} rejected)",
+        statistics.samples.len() + statistics.rejected(),
+        statistics.rejected()
+    );
+    if let Some(memory) = memory {
+        println!("    {}:", "Memory".purple().bold());
+        println!("      {}: {}", "Setup".blue(), memory.setup);
+        println!("      {}: {}", "Part 1".red(), memory.part_1);
+        println!("      {}: {}", "Part 2".green(), memory.part_2);
+        if let Some(combined) = memory.combined {
+            println!("      Combined: {}", combined);
+        }
+    }
     println!(
         "{}{}{}",
         "-".repeat(setup_portion).blue().bold(),
@@ -98,34 +223,102 @@ pub(crate) fn print_timing(times: &Timing) {
     );
 }
 
+// -----------------------------------------------------------------------------
+// Output timing change
+// -----------------------------------------------------------------------------
+/// Print the change in each phase time since an earlier run of the same day
+pub fn print_delta(previous: &Timing, current: &Timing) {
+    println!("    {}:", "Change".purple().bold());
+    let phases = [
+        ("Setup".blue(), previous.setup, current.setup),
+        ("Part 1".red(), previous.part_1, current.part_1),
+        ("Part 2".green(), previous.part_2, current.part_2),
+        ("Total".normal(), previous.total(), current.total()),
+    ];
+    for (name, before, after) in phases.iter() {
+        let change = 100.0 * (after.as_nanos() as f64 / before.as_nanos() as f64 - 1.0);
+        let change = format!("{:+.1}%", change);
+        println!(
+            "      {}: {} -> {} ({})",
+            name,
+            time(*before),
+            time(*after),
+            if after < before {
+                change.green().bold()
+            } else if after > before {
+                change.red().bold()
+            } else {
+                change.normal()
+            }
+        );
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Output span breakdown
+// -----------------------------------------------------------------------------
+/// Print the time spent in the named steps of a day, nested by span
+pub fn print_spans(spans: &[crate::spans::SpanTime]) {
+    if spans.is_empty() {
+        return;
+    }
+    println!("    {}:", "Steps".purple().bold());
+    for span in spans {
+        println!(
+            "      {}{}: {}{}",
+            "  ".repeat(span.depth()),
+            span.name(),
+            time(span.time),
+            if span.count > 1 {
+                format!(" ({} calls)", span.count)
+            } else {
+                String::new()
+            }
+        );
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Output timing comparison
 // -----------------------------------------------------------------------------
-pub(crate) fn print_days_timing(times: &Vec<std::time::Duration>, std_devs: &Vec<f64>) {
+/// Print the times of all days side by side, on a logarithmic scale
+pub fn print_days_timing(days: &[&Entry], statistics: &[Statistics], warmup: u32, parallel: bool) {
     println!("- {}", "Timing Comparison".bold());
-    println!("    {}: {}", "Repetitions".purple().bold(), REPETITIONS);
+    let mode = if parallel { "Parallel" } else { "Sequential" };
+    println!("    {}: {}", "Mode".purple().bold(), mode);
+    println!("    {}: {}", "Warmup".purple().bold(), warmup);
     println!("    {}: Logarithmic", "Scale".purple().bold());
-    println!("    {}", "-".repeat(NUMBER_DASHES - 4).blue().bold());
-    let total: std::time::Duration = times.iter().sum();
-    let longest = (times.iter().max().unwrap().as_nanos() as f64).log10();
-    for (i, (&time, &std_dev)) in times.iter().zip(std_devs).enumerate() {
+    let width = width() - 4;
+    println!("    {}", "-".repeat(width).blue().bold());
+    let total: std::time::Duration = statistics.iter().map(|day| day.mean).sum();
+    let longest = (statistics
+        .iter()
+        .map(|day| day.mean)
+        .max()
+        .unwrap()
+        .as_nanos() as f64)
+        .log10();
+    for (i, (day, day_statistics)) in days.iter().zip(statistics).enumerate() {
+        let mean = day_statistics.mean;
         let part_length = std::cmp::max(
             1,
-            ((NUMBER_DASHES - 4) as f64 * ((time.as_nanos() as f64).log10() / longest)) as usize,
+            (width as f64 * ((mean.as_nanos() as f64).log10() / longest)) as usize,
         );
         let dashes = "-".repeat(part_length);
-        let display_std_dev = if time.as_secs() > 1 {
-            std_dev / 1_000_000.0
-        } else if time.as_millis() > 1 {
-            std_dev / 1_000.0
-        } else {
-            std_dev
-        };
         println!(
-            "    Dec {:02}: {:?} (Std Dev {:.3})",
-            i + 1,
-            time,
-            display_std_dev
+            "    Dec {:02} {}: {} (Std Dev {})",
+            day.number,
+            day.name,
+            time(mean),
+            time(day_statistics.std_dev)
+        );
+        println!(
+            "      Median {}, P90 {}, Min {}, Repetitions {} ({} rejected)",
+            time(day_statistics.median),
+            time(day_statistics.p90),
+            time(day_statistics.min),
+            day_statistics.samples.len() + day_statistics.rejected(),
+            day_statistics.rejected()
         );
         println!(
             "    {}",
@@ -136,7 +329,69 @@ pub(crate) fn print_days_timing(times: &Vec<std::time::Duration>, std_devs: &Vec
             }
         );
     }
-    println!("    {}: {:?}", "Total".purple().bold(), total);
+    println!("    {}: {}", "Total".purple().bold(), time(total));
+}
+
+// -----------------------------------------------------------------------------
+// Output baseline comparison
+// -----------------------------------------------------------------------------
+/// Print each day against the baseline
+pub fn print_comparison(
+    days: &[&Entry],
+    comparisons: &[Option<crate::baseline::Comparison>],
+    threshold: f64,
+) {
+    println!("- {}", "Baseline Comparison".bold());
+    println!("    {}: {}%", "Threshold".purple().bold(), threshold);
+    for (day, comparison) in days.iter().zip(comparisons) {
+        print!("    Dec {:02} {}: ", day.number, day.name);
+        let comparison = match comparison {
+            Some(comparison) => comparison,
+            None => {
+                println!("no baseline");
+                continue;
+            }
+        };
+        let change = if comparison.speedup >= 1.0 {
+            format!("{:.2}x faster", comparison.speedup)
+        } else {
+            format!("{:.2}x slower", 1.0 / comparison.speedup)
+        };
+        let change = if comparison.regression {
+            format!("{} (regression)", change).red().bold()
+        } else if comparison.significant() && comparison.speedup > 1.0 {
+            change.green().bold()
+        } else {
+            change.normal()
+        };
+        println!(
+            "{} -> {}, {} (p = {:.3})",
+            time(comparison.baseline),
+            time(comparison.current),
+            change,
+            comparison.p_value
+        );
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Output budget check
+// -----------------------------------------------------------------------------
+/// Print the phases of each day that ran over budget
+pub fn print_budgets(days: &[&Entry], overruns: &[Vec<crate::budget::Overrun>]) {
+    println!("- {}", "Budget Check".bold());
+    for (day, overruns) in days.iter().zip(overruns) {
+        print!("    Dec {:02} {}: ", day.number, day.name);
+        if overruns.is_empty() {
+            println!("{}", "within budget".green().bold());
+            continue;
+        }
+        let overruns: Vec<String> = overruns
+            .iter()
+            .map(|overrun| text(overrun.to_string()))
+            .collect();
+        println!("{}", overruns.join(", ").red().bold());
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -0,0 +1,1230 @@
+//! Reference:
+//! Plain solvers for each day, written straight from the puzzle text with hash maps and
+//! full simulations and without the shortcuts of the main solutions, such as the round
+//! limit on day 22 or the folded symmetry on day 17. They are only built with the
+//! `reference` feature. [`compare`] runs both versions on generated inputs and reports
+//! the first seed where the answers differ.
+
+use crate::{generate, registry, Answer, Input};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// Constants
+type Solver = fn(&str) -> (Answer, Answer);
+const SOLVERS: [Solver; registry::NUMBER_DAYS] = [
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+];
+
+// -----------------------------------------------------------------------------
+// Solve
+// -----------------------------------------------------------------------------
+/// Answers to both parts from the reference solver for a day; `None` for an unknown day.
+/// The input must be valid, as the reference solvers do not check it.
+pub fn solve(day: usize, buffer: &str) -> Option<(Answer, Answer)> {
+    let solve = SOLVERS.get(day.wrapping_sub(1))?;
+    Some(solve(&Input::normalize(buffer.to_string())))
+}
+
+// -----------------------------------------------------------------------------
+// Divergence struct
+// -----------------------------------------------------------------------------
+/// Generated input where the optimized and reference solvers disagree
+#[derive(Debug)]
+pub struct Divergence {
+    /// Day of December
+    pub day: usize,
+    /// Seed of the generated input
+    pub seed: u64,
+    /// Size of the generated input
+    pub size: usize,
+    /// The generated input
+    pub input: String,
+    /// Answers from the optimized solver, or the error it returned
+    pub optimized: Result<(Answer, Answer), String>,
+    /// Answers from the reference solver
+    pub reference: (Answer, Answer),
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Dec {:02}, seed {}, size {}: ",
+            self.day, self.seed, self.size
+        )?;
+        match &self.optimized {
+            Ok((part_1, part_2)) => write!(
+                f,
+                "optimized answers {} and {}, reference answers {} and {}",
+                part_1, part_2, self.reference.0, self.reference.1
+            )?,
+            Err(error) => write!(f, "optimized solver failed with '{}'", error)?,
+        }
+        write!(
+            f,
+            "\nreproduce with: advent2020 generate --day {} --seed {} --size {}",
+            self.day, self.seed, self.size
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Compare
+// -----------------------------------------------------------------------------
+/// Run the optimized and reference solvers on the generated inputs for each seed; the
+/// number of inputs that agree, or the first one that does not
+pub fn compare(
+    day: usize,
+    seeds: std::ops::Range<u64>,
+    size: usize,
+) -> Result<usize, Box<Divergence>> {
+    let entry = registry::get(day).expect("day not registered");
+    let mut agreed = 0;
+    for seed in seeds {
+        let input = generate::input(day, seed, size).expect("no generator for day");
+        let optimized = (entry.run)(&Input::Buffer(input.clone()))
+            .map(|results| (results.part_1, results.part_2))
+            .map_err(|error| error.to_string());
+        let reference = solve(day, &input).expect("no reference solver for day");
+        if optimized.as_ref() != Ok(&reference) {
+            return Err(Box::new(Divergence {
+                day,
+                seed,
+                size,
+                input,
+                optimized,
+                reference,
+            }));
+        }
+        agreed += 1;
+    }
+    Ok(agreed)
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+fn numbers<T: std::str::FromStr>(s: &str) -> Vec<T>
+where
+    T::Err: std::fmt::Debug,
+{
+    s.split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().unwrap())
+        .collect()
+}
+
+fn grid(buffer: &str) -> Vec<Vec<char>> {
+    buffer.lines().map(|line| line.chars().collect()).collect()
+}
+
+// -----------------------------------------------------------------------------
+// Day 1
+// -----------------------------------------------------------------------------
+fn day01(buffer: &str) -> (Answer, Answer) {
+    let values: Vec<u64> = numbers(buffer);
+    let n = values.len();
+    let mut part_1 = Answer::None;
+    let mut part_2 = Answer::None;
+    for i in 0..n {
+        for j in i + 1..n {
+            if values[i] + values[j] == 2020 {
+                part_1 = (values[i] * values[j]).into();
+            }
+            for k in j + 1..n {
+                if values[i] + values[j] + values[k] == 2020 {
+                    part_2 = (values[i] * values[j] * values[k]).into();
+                }
+            }
+        }
+    }
+    (part_1, part_2)
+}
+
+// -----------------------------------------------------------------------------
+// Day 2
+// -----------------------------------------------------------------------------
+fn day02(buffer: &str) -> (Answer, Answer) {
+    let (mut count_1, mut count_2) = (0, 0);
+    for line in buffer.lines() {
+        let parts: Vec<&str> = line.split([' ', '-', ':']).collect();
+        let (lower, upper): (usize, usize) = (parts[0].parse().unwrap(), parts[1].parse().unwrap());
+        let required = parts[2].chars().next().unwrap();
+        let password: Vec<char> = parts[4].chars().collect();
+        let count = password.iter().filter(|&&c| c == required).count();
+        if lower <= count && count <= upper {
+            count_1 += 1;
+        }
+        if (password[lower - 1] == required) != (password[upper - 1] == required) {
+            count_2 += 1;
+        }
+    }
+    (Answer::from(count_1), Answer::from(count_2))
+}
+
+// -----------------------------------------------------------------------------
+// Day 3
+// -----------------------------------------------------------------------------
+fn day03(buffer: &str) -> (Answer, Answer) {
+    let map = grid(buffer);
+    let trees = |right: usize, down: usize| {
+        (0..map.len())
+            .step_by(down)
+            .filter(|&row| map[row][(row / down * right) % map[row].len()] == '#')
+            .count()
+    };
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    let product: usize = slopes
+        .iter()
+        .map(|&(right, down)| trees(right, down))
+        .product();
+    (trees(3, 1).into(), product.into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 4
+// -----------------------------------------------------------------------------
+fn day04(buffer: &str) -> (Answer, Answer) {
+    const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    let year = |value: &str, lower: u32, upper: u32| {
+        value.len() == 4
+            && value
+                .parse()
+                .is_ok_and(|year| lower <= year && year <= upper)
+    };
+    let valid = |key: &str, value: &str| match key {
+        "byr" => year(value, 1920, 2002),
+        "iyr" => year(value, 2010, 2020),
+        "eyr" => year(value, 2020, 2030),
+        "hgt" => {
+            let (number, unit) = value.split_at(value.len().saturating_sub(2));
+            match (number.parse::<u32>(), unit) {
+                (Ok(height), "cm") => (150..=193).contains(&height),
+                (Ok(height), "in") => (59..=76).contains(&height),
+                _ => false,
+            }
+        }
+        "hcl" => {
+            value.len() == 7
+                && value.starts_with('#')
+                && value[1..]
+                    .chars()
+                    .all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+        }
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value),
+        "pid" => value.len() == 9 && value.chars().all(|c| c.is_ascii_digit()),
+        _ => true,
+    };
+    let (mut count_1, mut count_2) = (0, 0);
+    for passport in buffer.split("\n\n") {
+        let fields: HashMap<&str, &str> = passport
+            .split_whitespace()
+            .map(|field| field.split_once(':').unwrap())
+            .collect();
+        if REQUIRED.iter().all(|key| fields.contains_key(key)) {
+            count_1 += 1;
+            if fields.iter().all(|(key, value)| valid(key, value)) {
+                count_2 += 1;
+            }
+        }
+    }
+    (Answer::from(count_1), Answer::from(count_2))
+}
+
+// -----------------------------------------------------------------------------
+// Day 5
+// -----------------------------------------------------------------------------
+fn day05(buffer: &str) -> (Answer, Answer) {
+    let seats: HashSet<u64> = buffer
+        .lines()
+        .map(|line| {
+            line.chars()
+                .fold(0, |acc, c| 2 * acc + matches!(c, 'B' | 'R') as u64)
+        })
+        .collect();
+    let highest = *seats.iter().max().unwrap();
+    let missing = (1..highest)
+        .find(|seat| {
+            !seats.contains(seat) && seats.contains(&(seat - 1)) && seats.contains(&(seat + 1))
+        })
+        .map_or(Answer::None, Answer::from);
+    (highest.into(), missing)
+}
+
+// -----------------------------------------------------------------------------
+// Day 6
+// -----------------------------------------------------------------------------
+fn day06(buffer: &str) -> (Answer, Answer) {
+    let (mut count_1, mut count_2) = (0, 0);
+    for group in buffer.split("\n\n") {
+        let people: Vec<HashSet<char>> = group.lines().map(|line| line.chars().collect()).collect();
+        let anyone: HashSet<char> = people.iter().flatten().copied().collect();
+        count_1 += anyone.len();
+        count_2 += anyone
+            .iter()
+            .filter(|c| people.iter().all(|person| person.contains(c)))
+            .count();
+    }
+    (count_1.into(), count_2.into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 7
+// -----------------------------------------------------------------------------
+fn day07(buffer: &str) -> (Answer, Answer) {
+    let mut rules: HashMap<&str, Vec<(u64, &str)>> = HashMap::new();
+    for line in buffer.lines() {
+        let (outer, contents) = line.split_once(" bags contain ").unwrap();
+        let mut inner = vec![];
+        if contents != "no other bags." {
+            for bag in contents.split(", ") {
+                let (count, rest) = bag.split_once(' ').unwrap();
+                let name = rest.rsplit_once(' ').unwrap().0;
+                inner.push((count.parse().unwrap(), name));
+            }
+        }
+        rules.insert(outer, inner);
+    }
+    fn holds_gold(rules: &HashMap<&str, Vec<(u64, &str)>>, bag: &str) -> bool {
+        rules[bag]
+            .iter()
+            .any(|&(_, inner)| inner == "shiny gold" || holds_gold(rules, inner))
+    }
+    fn inside(rules: &HashMap<&str, Vec<(u64, &str)>>, bag: &str) -> u64 {
+        rules[bag]
+            .iter()
+            .map(|&(count, inner)| count * (1 + inside(rules, inner)))
+            .sum()
+    }
+    let holders = rules.keys().filter(|bag| holds_gold(&rules, bag)).count();
+    (holders.into(), inside(&rules, "shiny gold").into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 8
+// -----------------------------------------------------------------------------
+// Accumulator when the program loops or ends, and whether it ended
+fn boot(program: &[(&str, i64)]) -> (i64, bool) {
+    let (mut accumulator, mut current) = (0, 0_i64);
+    let mut executed = HashSet::new();
+    while (current as usize) < program.len() && executed.insert(current) {
+        let (operation, value) = program[current as usize];
+        match operation {
+            "acc" => accumulator += value,
+            "jmp" => current += value - 1,
+            _ => (),
+        }
+        current += 1;
+    }
+    (accumulator, current as usize == program.len())
+}
+
+fn day08(buffer: &str) -> (Answer, Answer) {
+    let mut program: Vec<(&str, i64)> = buffer
+        .lines()
+        .map(|line| {
+            let (operation, value) = line.split_once(' ').unwrap();
+            (operation, value.parse().unwrap())
+        })
+        .collect();
+    let part_1 = boot(&program).0;
+    let mut part_2 = Answer::None;
+    for i in 0..program.len() {
+        let original = program[i].0;
+        program[i].0 = match original {
+            "jmp" => "nop",
+            "nop" => "jmp",
+            _ => continue,
+        };
+        if let (accumulator, true) = boot(&program) {
+            part_2 = accumulator.into();
+        }
+        program[i].0 = original;
+    }
+    (part_1.into(), part_2)
+}
+
+// -----------------------------------------------------------------------------
+// Day 9
+// -----------------------------------------------------------------------------
+fn day09(buffer: &str) -> (Answer, Answer) {
+    const WINDOW: usize = 25;
+    let values: Vec<i64> = buffer.lines().map(|line| line.parse().unwrap()).collect();
+    let invalid = (WINDOW..values.len())
+        .map(|i| values[i])
+        .zip(0..)
+        .find(|&(value, i)| {
+            let window = &values[i..i + WINDOW];
+            !window
+                .iter()
+                .any(|&a| window.iter().any(|&b| a != b && a + b == value))
+        });
+    let invalid = match invalid {
+        Some((value, _)) => value,
+        None => return (Answer::None, Answer::None),
+    };
+    for start in 0..values.len() {
+        for end in start + 2..=values.len() {
+            let range = &values[start..end];
+            if range.iter().sum::<i64>() == invalid {
+                let weakness = range.iter().min().unwrap() + range.iter().max().unwrap();
+                return (invalid.into(), weakness.into());
+            }
+        }
+    }
+    (invalid.into(), Answer::None)
+}
+
+// -----------------------------------------------------------------------------
+// Day 10
+// -----------------------------------------------------------------------------
+fn day10(buffer: &str) -> (Answer, Answer) {
+    let mut joltages: Vec<u64> = numbers(buffer);
+    joltages.push(0);
+    joltages.sort_unstable();
+    joltages.push(joltages[joltages.len() - 1] + 3);
+    let mut differences = HashMap::new();
+    for pair in joltages.windows(2) {
+        *differences.entry(pair[1] - pair[0]).or_insert(0_u64) += 1;
+    }
+    let mut ways: HashMap<u64, u64> = HashMap::new();
+    ways.insert(0, 1);
+    for &joltage in &joltages[1..] {
+        let count = (1..=3)
+            .filter(|&step| step <= joltage)
+            .map(|step| ways.get(&(joltage - step)).copied().unwrap_or(0))
+            .sum();
+        ways.insert(joltage, count);
+    }
+    let part_1 = differences.get(&1).unwrap_or(&0) * differences.get(&3).unwrap_or(&0);
+    (part_1.into(), ways[&joltages[joltages.len() - 1]].into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 11
+// -----------------------------------------------------------------------------
+fn seating(map: &[Vec<char>], crowded: usize, in_sight: bool) -> usize {
+    let (rows, columns) = (map.len() as i32, map[0].len() as i32);
+    let mut seats = map.to_vec();
+    loop {
+        let mut next = seats.clone();
+        for row in 0..rows {
+            for column in 0..columns {
+                let mut occupied = 0;
+                for (d_row, d_column) in (-1..=1).flat_map(|d| (-1..=1).map(move |e| (d, e))) {
+                    if (d_row, d_column) == (0, 0) {
+                        continue;
+                    }
+                    let (mut r, mut c) = (row + d_row, column + d_column);
+                    while in_sight
+                        && (0..rows).contains(&r)
+                        && (0..columns).contains(&c)
+                        && seats[r as usize][c as usize] == '.'
+                    {
+                        r += d_row;
+                        c += d_column;
+                    }
+                    if (0..rows).contains(&r)
+                        && (0..columns).contains(&c)
+                        && seats[r as usize][c as usize] == '#'
+                    {
+                        occupied += 1;
+                    }
+                }
+                let seat = &mut next[row as usize][column as usize];
+                match *seat {
+                    'L' if occupied == 0 => *seat = '#',
+                    '#' if occupied >= crowded => *seat = 'L',
+                    _ => (),
+                }
+            }
+        }
+        if next == seats {
+            return seats.iter().flatten().filter(|&&c| c == '#').count();
+        }
+        seats = next;
+    }
+}
+
+fn day11(buffer: &str) -> (Answer, Answer) {
+    let map = grid(buffer);
+    (
+        seating(&map, 4, false).into(),
+        seating(&map, 5, true).into(),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Day 12
+// -----------------------------------------------------------------------------
+fn day12(buffer: &str) -> (Answer, Answer) {
+    let (mut ship_1, mut heading) = ((0_i64, 0_i64), (1_i64, 0_i64));
+    let (mut ship_2, mut waypoint) = ((0_i64, 0_i64), (10_i64, 1_i64));
+    for line in buffer.lines() {
+        let (action, value) = line.split_at(1);
+        let value: i64 = value.parse().unwrap();
+        let step = match action {
+            "N" => (0, value),
+            "S" => (0, -value),
+            "E" => (value, 0),
+            "W" => (-value, 0),
+            _ => (0, 0),
+        };
+        ship_1 = (ship_1.0 + step.0, ship_1.1 + step.1);
+        waypoint = (waypoint.0 + step.0, waypoint.1 + step.1);
+        let turns = match action {
+            "L" => value / 90,
+            "R" => 4 - value / 90 % 4,
+            _ => 0,
+        };
+        for _ in 0..turns {
+            heading = (-heading.1, heading.0);
+            waypoint = (-waypoint.1, waypoint.0);
+        }
+        if action == "F" {
+            ship_1 = (ship_1.0 + value * heading.0, ship_1.1 + value * heading.1);
+            ship_2 = (ship_2.0 + value * waypoint.0, ship_2.1 + value * waypoint.1);
+        }
+    }
+    (
+        (ship_1.0.abs() + ship_1.1.abs()).into(),
+        (ship_2.0.abs() + ship_2.1.abs()).into(),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Day 13
+// -----------------------------------------------------------------------------
+fn day13(buffer: &str) -> (Answer, Answer) {
+    let (earliest, schedule) = buffer.trim_end().split_once('\n').unwrap();
+    let earliest: u64 = earliest.parse().unwrap();
+    let buses: Vec<(u64, u64)> = schedule
+        .split(',')
+        .zip(0..)
+        .filter_map(|(bus, offset)| bus.parse().ok().map(|bus| (bus, offset)))
+        .collect();
+    let (wait, bus) = buses
+        .iter()
+        .map(|&(bus, _)| ((bus - earliest % bus) % bus, bus))
+        .min()
+        .unwrap();
+    // Sieve, one bus at a time
+    let (mut time, mut step) = (0, 1);
+    for &(bus, offset) in &buses {
+        while (time + offset) % bus != 0 {
+            time += step;
+        }
+        step *= bus;
+    }
+    ((wait * bus).into(), time.into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 14
+// -----------------------------------------------------------------------------
+fn day14(buffer: &str) -> (Answer, Answer) {
+    let mut memory_1: HashMap<u64, u64> = HashMap::new();
+    let mut memory_2: HashMap<u64, u64> = HashMap::new();
+    let mut mask = "";
+    for line in buffer.lines() {
+        if let Some(new_mask) = line.strip_prefix("mask = ") {
+            mask = new_mask;
+            continue;
+        }
+        let values: Vec<u64> = numbers(line);
+        let (address, value) = (values[0], values[1]);
+        let mut masked = value;
+        let mut addresses = vec![address];
+        for (bit, c) in mask.chars().rev().enumerate() {
+            match c {
+                '0' => masked &= !(1 << bit),
+                '1' => {
+                    masked |= 1 << bit;
+                    addresses
+                        .iter_mut()
+                        .for_each(|address| *address |= 1 << bit);
+                }
+                _ => {
+                    addresses = addresses
+                        .iter()
+                        .flat_map(|address| vec![address & !(1 << bit), address | 1 << bit])
+                        .collect()
+                }
+            }
+        }
+        memory_1.insert(address, masked);
+        for address in addresses {
+            memory_2.insert(address, value);
+        }
+    }
+    (
+        memory_1.values().sum::<u64>().into(),
+        memory_2.values().sum::<u64>().into(),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Day 15
+// -----------------------------------------------------------------------------
+// Turns are counted from 1, so 0 means not said yet; a hash map works too but takes
+// minutes for part 2
+fn memory_game(starting: &[usize], turns: usize) -> usize {
+    let mut said = vec![0; turns];
+    for (turn, &number) in starting[..starting.len() - 1].iter().enumerate() {
+        said[number] = turn + 1;
+    }
+    let mut last = starting[starting.len() - 1];
+    for turn in starting.len()..turns {
+        let next = match said[last] {
+            0 => 0,
+            previous => turn - previous,
+        };
+        said[last] = turn;
+        last = next;
+    }
+    last
+}
+
+fn day15(buffer: &str) -> (Answer, Answer) {
+    let starting: Vec<usize> = numbers(buffer);
+    (
+        memory_game(&starting, 2020).into(),
+        memory_game(&starting, 30_000_000).into(),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Day 16
+// -----------------------------------------------------------------------------
+fn day16(buffer: &str) -> (Answer, Answer) {
+    let sections: Vec<&str> = buffer.split("\n\n").collect();
+    let fields: Vec<(&str, Vec<u64>)> = sections[0]
+        .lines()
+        .map(|line| {
+            let (name, ranges) = line.split_once(": ").unwrap();
+            (name, numbers(ranges))
+        })
+        .collect();
+    let allowed = |field: &(&str, Vec<u64>), value: u64| {
+        let bounds = &field.1;
+        (bounds[0] <= value && value <= bounds[1]) || (bounds[2] <= value && value <= bounds[3])
+    };
+    let ticket = |line: &str| -> Vec<u64> { numbers(line) };
+    let mine = ticket(sections[1].lines().nth(1).unwrap());
+    let nearby: Vec<Vec<u64>> = sections[2].lines().skip(1).map(ticket).collect();
+
+    // Part 1
+    let mut error_rate = 0;
+    let mut valid = vec![];
+    for ticket in &nearby {
+        let invalid: Vec<u64> = ticket
+            .iter()
+            .copied()
+            .filter(|&value| !fields.iter().any(|field| allowed(field, value)))
+            .collect();
+        error_rate += invalid.iter().sum::<u64>();
+        if invalid.is_empty() {
+            valid.push(ticket);
+        }
+    }
+
+    // Part 2
+    let mut candidates: Vec<HashSet<usize>> = (0..fields.len())
+        .map(|position| {
+            (0..fields.len())
+                .filter(|&field| {
+                    valid
+                        .iter()
+                        .all(|ticket| allowed(&fields[field], ticket[position]))
+                })
+                .collect()
+        })
+        .collect();
+    let mut assigned: HashMap<usize, usize> = HashMap::new();
+    while assigned.len() < fields.len() {
+        let position = match (0..fields.len()).find(|&i| candidates[i].len() == 1) {
+            Some(position) => position,
+            None => return (error_rate.into(), Answer::None),
+        };
+        let field = *candidates[position].iter().next().unwrap();
+        assigned.insert(field, position);
+        candidates.iter_mut().for_each(|candidate| {
+            candidate.remove(&field);
+        });
+    }
+    let product: u64 = (0..fields.len())
+        .filter(|&field| fields[field].0.starts_with("departure"))
+        .map(|field| mine[assigned[&field]])
+        .product();
+    (error_rate.into(), product.into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 17
+// -----------------------------------------------------------------------------
+fn conway_cubes(buffer: &str, dimensions: usize) -> usize {
+    let mut active: HashSet<[i32; 4]> = HashSet::new();
+    for (row, line) in buffer.lines().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            if c == '#' {
+                active.insert([row as i32, column as i32, 0, 0]);
+            }
+        }
+    }
+    let mut offsets = vec![[0; 4]];
+    for dimension in 0..dimensions {
+        offsets = offsets
+            .iter()
+            .flat_map(|offset| {
+                (-1..=1).map(move |d| {
+                    let mut offset = *offset;
+                    offset[dimension] = d;
+                    offset
+                })
+            })
+            .collect();
+    }
+    offsets.retain(|offset| *offset != [0; 4]);
+    for _ in 0..6 {
+        let mut neighbors: HashMap<[i32; 4], usize> = HashMap::new();
+        for cube in &active {
+            for offset in &offsets {
+                let mut neighbor = *cube;
+                (0..4).for_each(|d| neighbor[d] += offset[d]);
+                *neighbors.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+        active = neighbors
+            .into_iter()
+            .filter(|(cube, count)| *count == 3 || (*count == 2 && active.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+    active.len()
+}
+
+fn day17(buffer: &str) -> (Answer, Answer) {
+    (
+        conway_cubes(buffer, 3).into(),
+        conway_cubes(buffer, 4).into(),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Day 18
+// -----------------------------------------------------------------------------
+// Recursive descent; with additions first, an expression is a product of sums
+fn expression(tokens: &[char], position: &mut usize, additions_first: bool) -> u64 {
+    let operand = |position: &mut usize| -> u64 {
+        let token = tokens[*position];
+        *position += 1;
+        if token == '(' {
+            let value = expression(tokens, position, additions_first);
+            *position += 1;
+            value
+        } else {
+            token.to_digit(10).unwrap() as u64
+        }
+    };
+    let mut product = 1;
+    let mut value = operand(position);
+    while *position < tokens.len() && tokens[*position] != ')' {
+        let operator = tokens[*position];
+        *position += 1;
+        let next = operand(position);
+        match (operator, additions_first) {
+            ('+', _) => value += next,
+            (_, false) => value *= next,
+            (_, true) => {
+                product *= value;
+                value = next;
+            }
+        }
+    }
+    product * value
+}
+
+fn day18(buffer: &str) -> (Answer, Answer) {
+    let evaluate = |additions_first: bool| -> u64 {
+        buffer
+            .lines()
+            .map(|line| {
+                let tokens: Vec<char> = line.chars().filter(|&c| c != ' ').collect();
+                expression(&tokens, &mut 0, additions_first)
+            })
+            .sum()
+    };
+    (evaluate(false).into(), evaluate(true).into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 19
+// -----------------------------------------------------------------------------
+enum Rule {
+    Character(u8),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+// Every position where a match of the rule starting at the position can end
+fn match_ends(
+    rules: &HashMap<usize, Rule>,
+    rule: usize,
+    message: &[u8],
+    start: usize,
+) -> Vec<usize> {
+    match &rules[&rule] {
+        Rule::Character(c) => match message.get(start) {
+            Some(next) if next == c => vec![start + 1],
+            _ => vec![],
+        },
+        Rule::Alternatives(alternatives) => {
+            let mut ends = vec![];
+            for sequence in alternatives {
+                let mut positions = vec![start];
+                for &sub_rule in sequence {
+                    positions = positions
+                        .iter()
+                        .filter(|&&position| position < message.len())
+                        .flat_map(|&position| match_ends(rules, sub_rule, message, position))
+                        .collect();
+                }
+                ends.extend(positions);
+            }
+            ends
+        }
+    }
+}
+
+fn day19(buffer: &str) -> (Answer, Answer) {
+    let (rules, messages) = buffer.split_once("\n\n").unwrap();
+    let mut rules: HashMap<usize, Rule> = rules
+        .lines()
+        .map(|line| {
+            let (number, rule) = line.split_once(": ").unwrap();
+            let rule = match rule.strip_prefix('"') {
+                Some(c) => Rule::Character(c.as_bytes()[0]),
+                None => Rule::Alternatives(rule.split(" | ").map(numbers).collect()),
+            };
+            (number.parse().unwrap(), rule)
+        })
+        .collect();
+    let count = |rules: &HashMap<usize, Rule>| {
+        messages
+            .lines()
+            .filter(|message| match_ends(rules, 0, message.as_bytes(), 0).contains(&message.len()))
+            .count()
+    };
+    let count_1 = count(&rules);
+    rules.insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+    rules.insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+    (count_1.into(), count(&rules).into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 20
+// -----------------------------------------------------------------------------
+type Image = Vec<Vec<bool>>;
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+// All eight rotations and reflections
+fn orientations(image: &Image) -> Vec<Image> {
+    let size = image.len();
+    let rotate = |image: &Image| -> Image {
+        (0..size)
+            .map(|i| (0..size).map(|j| image[size - 1 - j][i]).collect())
+            .collect()
+    };
+    let mut all = vec![image.clone()];
+    for _ in 0..3 {
+        all.push(rotate(&all[all.len() - 1]));
+    }
+    let flipped: Vec<Image> = all
+        .iter()
+        .map(|image| image.iter().rev().cloned().collect())
+        .collect();
+    all.extend(flipped);
+    all
+}
+
+fn edges(tile: &Image) -> Vec<Vec<bool>> {
+    let size = tile.len();
+    vec![
+        tile[0].clone(),
+        tile[size - 1].clone(),
+        tile.iter().map(|row| row[0]).collect(),
+        tile.iter().map(|row| row[size - 1]).collect(),
+    ]
+}
+
+// Place tiles in reading order, backtracking on a mismatch
+fn assemble(
+    tiles: &[Vec<Image>],
+    side: usize,
+    placed: &mut Vec<(usize, usize)>,
+    used: &mut Vec<bool>,
+) -> bool {
+    let position = placed.len();
+    if position == side * side {
+        return true;
+    }
+    for tile in 0..tiles.len() {
+        if used[tile] {
+            continue;
+        }
+        for (orientation, image) in tiles[tile].iter().enumerate() {
+            let size = image.len();
+            if !position.is_multiple_of(side) {
+                let (left, left_orientation) = placed[position - 1];
+                let left = &tiles[left][left_orientation];
+                if (0..size).any(|i| left[i][size - 1] != image[i][0]) {
+                    continue;
+                }
+            }
+            if position >= side {
+                let (above, above_orientation) = placed[position - side];
+                if tiles[above][above_orientation][size - 1] != image[0] {
+                    continue;
+                }
+            }
+            placed.push((tile, orientation));
+            used[tile] = true;
+            if assemble(tiles, side, placed, used) {
+                return true;
+            }
+            placed.pop();
+            used[tile] = false;
+        }
+    }
+    false
+}
+
+fn day20(buffer: &str) -> (Answer, Answer) {
+    let mut ids: Vec<u64> = vec![];
+    let mut tiles: Vec<Vec<Image>> = vec![];
+    for block in buffer.split("\n\n") {
+        let mut lines = block.lines();
+        ids.push(numbers(lines.next().unwrap())[0]);
+        let tile: Image = lines
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect();
+        tiles.push(orientations(&tile));
+    }
+    let side = (1..).find(|side| side * side >= tiles.len()).unwrap();
+
+    // Corners have two edges that match no other tile
+    let unmatched = |tile: usize| {
+        edges(&tiles[tile][0])
+            .iter()
+            .filter(|&edge| {
+                let reversed: Vec<bool> = edge.iter().rev().copied().collect();
+                !(0..tiles.len()).any(|other| {
+                    other != tile
+                        && edges(&tiles[other][0])
+                            .iter()
+                            .any(|other| other == edge || *other == reversed)
+                })
+            })
+            .count()
+    };
+    let corners: Vec<usize> = (0..tiles.len())
+        .filter(|&tile| unmatched(tile) == 2)
+        .collect();
+    let product: u64 = corners.iter().map(|&tile| ids[tile]).product();
+
+    // Assemble, starting from each corner in turn
+    let mut placed = vec![];
+    let mut used = vec![false; tiles.len()];
+    let assembled = corners.iter().any(|&corner| {
+        (0..8).any(|orientation| {
+            placed = vec![(corner, orientation)];
+            used = vec![false; tiles.len()];
+            used[corner] = true;
+            assemble(&tiles, side, &mut placed, &mut used)
+        })
+    });
+    if !assembled {
+        return (product.into(), Answer::None);
+    }
+    let size = tiles[0][0].len() - 2;
+    let mut image: Image = vec![vec![false; side * size]; side * size];
+    for (position, &(tile, orientation)) in placed.iter().enumerate() {
+        let (row, column) = (position / side * size, position % side * size);
+        for i in 0..size {
+            for j in 0..size {
+                image[row + i][column + j] = tiles[tile][orientation][i + 1][j + 1];
+            }
+        }
+    }
+
+    // Mark every pixel that is part of a sea monster, in the orientation that has some
+    let monster: Vec<(usize, usize)> = SEA_MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| line.match_indices('#').map(move |(j, _)| (i, j)))
+        .collect();
+    let roughness = orientations(&image)
+        .iter()
+        .map(|image| {
+            let mut marked = HashSet::new();
+            for i in 0..image.len().saturating_sub(2) {
+                for j in 0..image.len().saturating_sub(19) {
+                    if monster.iter().all(|&(di, dj)| image[i + di][j + dj]) {
+                        marked.extend(monster.iter().map(|&(di, dj)| (i + di, j + dj)));
+                    }
+                }
+            }
+            let total = image.iter().flatten().filter(|&&pixel| pixel).count();
+            (marked.len(), total - marked.len())
+        })
+        .max()
+        .unwrap()
+        .1;
+    (product.into(), roughness.into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 21
+// -----------------------------------------------------------------------------
+fn day21(buffer: &str) -> (Answer, Answer) {
+    let foods: Vec<(HashSet<&str>, Vec<&str>)> = buffer
+        .lines()
+        .map(|line| {
+            let (ingredients, allergens) = line.split_once(" (contains ").unwrap();
+            let allergens = allergens.trim_end_matches(')').split(", ").collect();
+            (ingredients.split(' ').collect(), allergens)
+        })
+        .collect();
+    let mut candidates: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (ingredients, allergens) in &foods {
+        for allergen in allergens {
+            let candidate = candidates
+                .entry(allergen)
+                .or_insert_with(|| ingredients.clone());
+            candidate.retain(|ingredient| ingredients.contains(ingredient));
+        }
+    }
+    let unsafe_ingredients: HashSet<&str> = candidates.values().flatten().copied().collect();
+    let safe: usize = foods
+        .iter()
+        .map(|(ingredients, _)| {
+            ingredients
+                .iter()
+                .filter(|ingredient| !unsafe_ingredients.contains(*ingredient))
+                .count()
+        })
+        .sum();
+    let mut dangerous: Vec<(&str, &str)> = vec![];
+    while !candidates.is_empty() {
+        let (allergen, ingredient) = match candidates.iter().find(|(_, c)| c.len() == 1) {
+            Some((allergen, candidate)) => (*allergen, *candidate.iter().next().unwrap()),
+            None => return (safe.into(), Answer::None),
+        };
+        candidates.remove(allergen);
+        candidates.values_mut().for_each(|candidate| {
+            candidate.remove(ingredient);
+        });
+        dangerous.push((allergen, ingredient));
+    }
+    dangerous.sort_unstable();
+    let list: Vec<&str> = dangerous
+        .iter()
+        .map(|&(_, ingredient)| ingredient)
+        .collect();
+    (safe.into(), list.join(",").into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 22
+// -----------------------------------------------------------------------------
+fn score(deck: &VecDeque<usize>) -> usize {
+    deck.iter().rev().zip(1..).map(|(card, i)| card * i).sum()
+}
+
+// Winner, true for player 1, with their deck
+fn recursive_combat(
+    mut player_1: VecDeque<usize>,
+    mut player_2: VecDeque<usize>,
+) -> (bool, VecDeque<usize>) {
+    let mut seen = HashSet::new();
+    while !player_1.is_empty() && !player_2.is_empty() {
+        if !seen.insert((player_1.clone(), player_2.clone())) {
+            return (true, player_1);
+        }
+        let (card_1, card_2) = (player_1.pop_front().unwrap(), player_2.pop_front().unwrap());
+        let player_1_wins = if player_1.len() >= card_1 && player_2.len() >= card_2 {
+            recursive_combat(
+                player_1.iter().take(card_1).copied().collect(),
+                player_2.iter().take(card_2).copied().collect(),
+            )
+            .0
+        } else {
+            card_1 > card_2
+        };
+        match player_1_wins {
+            true => player_1.extend([card_1, card_2].iter()),
+            false => player_2.extend([card_2, card_1].iter()),
+        }
+    }
+    match player_2.is_empty() {
+        true => (true, player_1),
+        false => (false, player_2),
+    }
+}
+
+fn day22(buffer: &str) -> (Answer, Answer) {
+    let (deck_1, deck_2) = buffer.split_once("\n\n").unwrap();
+    let deck = |deck: &str| -> VecDeque<usize> {
+        deck.lines()
+            .skip(1)
+            .map(|line| line.parse().unwrap())
+            .collect()
+    };
+    let (mut player_1, mut player_2) = (deck(deck_1), deck(deck_2));
+    let (start_1, start_2) = (player_1.clone(), player_2.clone());
+    while !player_1.is_empty() && !player_2.is_empty() {
+        let (card_1, card_2) = (player_1.pop_front().unwrap(), player_2.pop_front().unwrap());
+        match card_1 > card_2 {
+            true => player_1.extend([card_1, card_2].iter()),
+            false => player_2.extend([card_2, card_1].iter()),
+        }
+    }
+    let winner = if player_1.is_empty() {
+        player_2
+    } else {
+        player_1
+    };
+    let (_, recursive_winner) = recursive_combat(start_1, start_2);
+    (score(&winner).into(), score(&recursive_winner).into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 23
+// -----------------------------------------------------------------------------
+// The cup after each cup, so a move only changes three links
+fn crab_cups(labels: &[usize], number_cups: usize, moves: usize) -> Vec<usize> {
+    let mut cups: Vec<usize> = labels.to_vec();
+    cups.extend(labels.len() + 1..=number_cups);
+    let mut next = vec![0; number_cups + 1];
+    for i in 0..number_cups {
+        next[cups[i]] = cups[(i + 1) % number_cups];
+    }
+    let mut current = cups[0];
+    for _ in 0..moves {
+        let picked = [
+            next[current],
+            next[next[current]],
+            next[next[next[current]]],
+        ];
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 {
+                number_cups
+            } else {
+                destination - 1
+            };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        next[current] = next[picked[2]];
+        next[picked[2]] = next[destination];
+        next[destination] = picked[0];
+        current = next[current];
+    }
+    next
+}
+
+fn day23(buffer: &str) -> (Answer, Answer) {
+    let labels: Vec<usize> = buffer
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as usize)
+        .collect();
+    // Part 1 by moving the cups around in a vector
+    let mut cups = labels.clone();
+    for _ in 0..100 {
+        let current = cups[0];
+        let picked: Vec<usize> = cups.drain(1..4).collect();
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 { 9 } else { destination - 1 };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        let position = cups.iter().position(|&cup| cup == destination).unwrap();
+        cups.splice(position + 1..position + 1, picked);
+        cups.rotate_left(1);
+    }
+    let one = cups.iter().position(|&cup| cup == 1).unwrap();
+    cups.rotate_left(one);
+    let order: String = cups[1..].iter().map(|cup| cup.to_string()).collect();
+    let next = crab_cups(&labels, 1_000_000, 10_000_000);
+    (order.into(), (next[1] * next[next[1]]).into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 24
+// -----------------------------------------------------------------------------
+fn day24(buffer: &str) -> (Answer, Answer) {
+    const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (-1, 0), (0, 1), (-1, 1), (1, -1), (0, -1)];
+    let mut black: HashSet<(i32, i32)> = HashSet::new();
+    for line in buffer.lines() {
+        let (mut east, mut north_east) = (0, 0);
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            let step = match c {
+                'n' | 's' => format!("{}{}", c, chars.next().unwrap()),
+                _ => c.to_string(),
+            };
+            let index = ["e", "w", "ne", "nw", "se", "sw"]
+                .iter()
+                .position(|&direction| direction == step)
+                .unwrap();
+            east += DIRECTIONS[index].0;
+            north_east += DIRECTIONS[index].1;
+        }
+        if !black.insert((east, north_east)) {
+            black.remove(&(east, north_east));
+        }
+    }
+    let initial = black.len();
+    for _ in 0..100 {
+        let mut neighbors: HashMap<(i32, i32), usize> = HashMap::new();
+        for &(east, north_east) in &black {
+            for &(d_east, d_north_east) in &DIRECTIONS {
+                *neighbors
+                    .entry((east + d_east, north_east + d_north_east))
+                    .or_insert(0) += 1;
+            }
+        }
+        black = neighbors
+            .into_iter()
+            .filter(|(tile, count)| *count == 2 || (*count == 1 && black.contains(tile)))
+            .map(|(tile, _)| tile)
+            .collect();
+    }
+    (initial.into(), black.len().into())
+}
+
+// -----------------------------------------------------------------------------
+// Day 25
+// -----------------------------------------------------------------------------
+fn day25(buffer: &str) -> (Answer, Answer) {
+    const P: u64 = 20_201_227;
+    let keys: Vec<u64> = numbers(buffer);
+    let (mut value, mut loop_size) = (1, 0);
+    while value != keys[0] {
+        value = value * 7 % P;
+        loop_size += 1;
+    }
+    let encryption_key = (0..loop_size).fold(1, |acc, _| acc * keys[1] % P);
+    (encryption_key.into(), Answer::None)
+}
+
+// -----------------------------------------------------------------------------
@@ -0,0 +1,225 @@
+//! Registry:
+//! Each day describes itself through the `Day` trait, and the registry collects the days
+//! in order so the driver, tests and output code all work off the same metadata. Adding
+//! a day means adding the module and one line to `REGISTRY`.
+
+use crate::prelude::*;
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+use crate::{memory, spans};
+
+// -----------------------------------------------------------------------------
+// Day trait
+// -----------------------------------------------------------------------------
+/// A puzzle, with its parser and solvers
+pub trait Day {
+    /// Day of December
+    const NUMBER: usize;
+    /// Puzzle title
+    const NAME: &'static str;
+    /// Labels for the answers to the two parts
+    const LABELS: [&'static str; 2];
+    /// Expected upper bound on the time of each phase, in an optimized build
+    const BUDGET: Budget;
+    /// Parsed input, shared between the parts
+    type Data;
+
+    /// Check the assumptions the solvers make about the input, before parsing; `parse`
+    /// and `combined` may rely on these
+    fn check(_buffer: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Setup, after the input has been read
+    fn parse(buffer: &str) -> Result<Self::Data, Error>;
+
+    /// Solve part 1; parts share the parsed data, so later parts can reuse earlier work
+    fn part_1(data: &mut Self::Data) -> Answer;
+    /// Solve part 2, after part 1 has been run on the same data
+    fn part_2(data: &mut Self::Data) -> Answer;
+
+    /// Both parts in a single pass, if the day supports it
+    fn combined(_buffer: &str) -> Result<Option<(Answer, Answer)>, Error> {
+        Ok(None)
+    }
+
+    /// Print the answers and timing for the day
+    fn report(results: &Results, statistics: &Statistics) {
+        output::print_day(Self::NUMBER, Self::NAME);
+        output::print_part(1, Self::LABELS[0], &results.part_1);
+        output::print_part(2, Self::LABELS[1], &results.part_2);
+        output::print_timing(&results.times, results.memory.as_ref(), statistics);
+        output::print_spans(&results.spans);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Run
+// -----------------------------------------------------------------------------
+// The single pass must agree with the parts it stands in for
+fn check_combined(part: usize, separate: &Answer, combined: &Answer) -> Result<(), Error> {
+    if separate == combined {
+        Ok(())
+    } else {
+        Err(Error::Mismatch {
+            part,
+            separate: separate.to_string(),
+            combined: combined.to_string(),
+        })
+    }
+}
+
+/// Read, parse and solve a day, timing each phase
+pub fn run<D: Day>(input: &Input) -> Result<Results, Error> {
+    // -------------------------------------------------------------------------
+    // Setup
+    // -------------------------------------------------------------------------
+    // Open file
+    spans::take();
+    let memory_setup = memory::Counter::start();
+    let start_setup = Instant::now();
+    let buffer: String = input.read()?;
+
+    // Parse
+    D::check(&buffer)?;
+    let mut data = D::parse(&buffer)?;
+    let time_setup = start_setup.elapsed();
+    let memory_setup = memory_setup.stop();
+
+    // -------------------------------------------------------------------------
+    // Part 1
+    // -------------------------------------------------------------------------
+    let memory_part_1 = memory::Counter::start();
+    let start_part_1 = Instant::now();
+    let part_1 = D::part_1(&mut data);
+    let time_part_1 = start_part_1.elapsed();
+    let memory_part_1 = memory_part_1.stop();
+
+    // -------------------------------------------------------------------------
+    // Part 2
+    // -------------------------------------------------------------------------
+    let memory_part_2 = memory::Counter::start();
+    let start_part_2 = Instant::now();
+    let part_2 = D::part_2(&mut data);
+    let time_part_2 = start_part_2.elapsed();
+    let memory_part_2 = memory_part_2.stop();
+    let spans = spans::take();
+
+    // -------------------------------------------------------------------------
+    // Combined
+    // -------------------------------------------------------------------------
+    let memory_combined = memory::Counter::start();
+    let start_combined = Instant::now();
+    let combined = D::combined(&buffer)?;
+    let (time_combined, memory_combined) = match combined {
+        Some((combined_1, combined_2)) => {
+            let time_combined = start_combined.elapsed();
+            let memory_combined = memory_combined.stop();
+            check_combined(1, &part_1, &combined_1)?;
+            check_combined(2, &part_2, &combined_2)?;
+            (Some(time_combined), Some(memory_combined))
+        }
+        None => (None, None),
+    };
+
+    // -------------------------------------------------------------------------
+    // Return
+    // -------------------------------------------------------------------------
+    let mut results = Results::new(
+        part_1,
+        part_2,
+        Timing::new(time_setup, time_part_1, time_part_2, time_combined),
+    );
+    results.spans = spans;
+    if memory::ENABLED {
+        results.memory = Some(memory::Usage {
+            setup: memory_setup,
+            part_1: memory_part_1,
+            part_2: memory_part_2,
+            combined: memory_combined,
+        });
+    }
+    Ok(results)
+}
+
+// -----------------------------------------------------------------------------
+// Parse
+// -----------------------------------------------------------------------------
+/// Check and parse an input without solving it, the part of [`run`] that sees raw input
+pub fn parse<D: Day>(buffer: &str) -> Result<(), Error> {
+    D::check(buffer)?;
+    D::parse(buffer).map(drop)
+}
+
+// -----------------------------------------------------------------------------
+// Registry
+// -----------------------------------------------------------------------------
+/// A registered day, with the metadata and functions the driver needs
+#[derive(Debug)]
+pub struct Entry {
+    /// Day of December
+    pub number: usize,
+    /// Puzzle title
+    pub name: &'static str,
+    /// Time budget for each phase, see [`Day::BUDGET`]
+    pub budget: Budget,
+    /// Run and time the day, see [`run`]
+    pub run: fn(&Input) -> Result<Results, Error>,
+    /// Check and parse an input, see [`parse`]
+    pub parse: fn(&str) -> Result<(), Error>,
+    /// Print the results, see [`Day::report`]
+    pub report: fn(&Results, &Statistics),
+}
+
+impl Entry {
+    const fn new<D: Day>() -> Self {
+        Self {
+            number: D::NUMBER,
+            name: D::NAME,
+            budget: D::BUDGET,
+            run: run::<D>,
+            parse: parse::<D>,
+            report: D::report,
+        }
+    }
+}
+
+/// Number of days in the calendar
+pub const NUMBER_DAYS: usize = 25;
+/// All of the days, in order
+pub static REGISTRY: [Entry; NUMBER_DAYS] = [
+    Entry::new::<day01::Day01>(),
+    Entry::new::<day02::Day02>(),
+    Entry::new::<day03::Day03>(),
+    Entry::new::<day04::Day04>(),
+    Entry::new::<day05::Day05>(),
+    Entry::new::<day06::Day06>(),
+    Entry::new::<day07::Day07>(),
+    Entry::new::<day08::Day08>(),
+    Entry::new::<day09::Day09>(),
+    Entry::new::<day10::Day10>(),
+    Entry::new::<day11::Day11>(),
+    Entry::new::<day12::Day12>(),
+    Entry::new::<day13::Day13>(),
+    Entry::new::<day14::Day14>(),
+    Entry::new::<day15::Day15>(),
+    Entry::new::<day16::Day16>(),
+    Entry::new::<day17::Day17>(),
+    Entry::new::<day18::Day18>(),
+    Entry::new::<day19::Day19>(),
+    Entry::new::<day20::Day20>(),
+    Entry::new::<day21::Day21>(),
+    Entry::new::<day22::Day22>(),
+    Entry::new::<day23::Day23>(),
+    Entry::new::<day24::Day24>(),
+    Entry::new::<day25::Day25>(),
+];
+
+/// Look up a day by number
+pub fn get(number: usize) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.number == number)
+}
+
+// -----------------------------------------------------------------------------
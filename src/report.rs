@@ -0,0 +1,300 @@
+//! Report:
+//! Writes a self-contained Markdown or HTML report with the answers, a timing table, the
+//! per-day bar chart as an inline SVG, and the notes from the top of each day. The notes
+//! are read from the source files at compile time.
+
+use crate::export::Summary;
+use crate::registry::NUMBER_DAYS;
+use std::fmt::Write;
+
+// Constants
+const CHART_LABEL_WIDTH: usize = 230;
+const CHART_BAR_WIDTH: usize = 460;
+const CHART_VALUE_WIDTH: usize = 110;
+const CHART_ROW_HEIGHT: usize = 20;
+const SOURCES: [&str; NUMBER_DAYS] = [
+    include_str!("day01.rs"),
+    include_str!("day02.rs"),
+    include_str!("day03.rs"),
+    include_str!("day04.rs"),
+    include_str!("day05.rs"),
+    include_str!("day06.rs"),
+    include_str!("day07.rs"),
+    include_str!("day08.rs"),
+    include_str!("day09.rs"),
+    include_str!("day10.rs"),
+    include_str!("day11.rs"),
+    include_str!("day12.rs"),
+    include_str!("day13.rs"),
+    include_str!("day14.rs"),
+    include_str!("day15.rs"),
+    include_str!("day16.rs"),
+    include_str!("day17.rs"),
+    include_str!("day18.rs"),
+    include_str!("day19.rs"),
+    include_str!("day20.rs"),
+    include_str!("day21.rs"),
+    include_str!("day22.rs"),
+    include_str!("day23.rs"),
+    include_str!("day24.rs"),
+    include_str!("day25.rs"),
+];
+
+// -----------------------------------------------------------------------------
+// Notes
+// -----------------------------------------------------------------------------
+/// Paragraphs of the module notes for a day, without the 'Day N:' heading
+pub fn notes(day: usize) -> Vec<String> {
+    let mut paragraphs = vec![];
+    let mut paragraph = String::new();
+    let lines = SOURCES[day - 1]
+        .lines()
+        .take_while(|line| line.starts_with("//!"))
+        .skip(1);
+    for line in lines {
+        let line = line.trim_start_matches("//!").trim();
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(std::mem::take(&mut paragraph));
+            }
+            continue;
+        }
+        if !paragraph.is_empty() {
+            paragraph.push(' ');
+        }
+        paragraph.push_str(line);
+    }
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph);
+    }
+    paragraphs
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn mode(parallel: bool) -> &'static str {
+    if parallel {
+        "parallel"
+    } else {
+        "sequential"
+    }
+}
+
+// Header and rows of the timing table
+fn table(summaries: &[Summary]) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let header = vec![
+        "Day",
+        "Name",
+        "Part 1",
+        "Part 2",
+        "Setup Time",
+        "Part 1 Time",
+        "Part 2 Time",
+        "Mean",
+        "Std Dev",
+        "Median",
+        "Min",
+    ];
+    let rows = summaries
+        .iter()
+        .map(|summary| {
+            let (result, times, statistics) =
+                (&summary.samples[0], summary.average, summary.statistics);
+            vec![
+                format!("{:02}", summary.day.number),
+                summary.day.name.to_string(),
+                result.part_1.to_string(),
+                result.part_2.to_string(),
+                format!("{:?}", times.setup),
+                format!("{:?}", times.part_1),
+                format!("{:?}", times.part_2),
+                format!("{:?}", statistics.mean),
+                format!("{:?}", statistics.std_dev),
+                format!("{:?}", statistics.median),
+                format!("{:?}", statistics.min),
+            ]
+        })
+        .collect();
+    (header, rows)
+}
+
+// -----------------------------------------------------------------------------
+// Chart
+// -----------------------------------------------------------------------------
+/// Mean time of each day as an SVG bar chart, on a logarithmic scale
+pub fn svg_chart(summaries: &[Summary]) -> String {
+    let width = CHART_LABEL_WIDTH + CHART_BAR_WIDTH + CHART_VALUE_WIDTH;
+    let height = CHART_ROW_HEIGHT * summaries.len();
+    let longest = (summaries
+        .iter()
+        .map(|summary| summary.statistics.mean.as_nanos())
+        .max()
+        .unwrap_or(0)
+        .max(10) as f64)
+        .log10();
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         font-family=\"monospace\" font-size=\"12\">",
+        width, height
+    )
+    .unwrap();
+    for (i, summary) in summaries.iter().enumerate() {
+        let mean = summary.statistics.mean;
+        let nanoseconds = (mean.as_nanos().max(1) as f64).log10();
+        let length = std::cmp::max(1, (CHART_BAR_WIDTH as f64 * nanoseconds / longest) as usize);
+        let y = i * CHART_ROW_HEIGHT;
+        let text_y = y + CHART_ROW_HEIGHT * 3 / 4;
+        let color = if i % 2 == 0 { "#c0392b" } else { "#27ae60" };
+        writeln!(
+            svg,
+            "  <text x=\"0\" y=\"{}\">Dec {:02} {}</text>",
+            text_y,
+            summary.day.number,
+            escape(summary.day.name)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            CHART_LABEL_WIDTH,
+            y + 2,
+            length,
+            CHART_ROW_HEIGHT - 4,
+            color
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "  <text x=\"{}\" y=\"{}\">{:?}</text>",
+            CHART_LABEL_WIDTH + length + 5,
+            text_y,
+            mean
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// -----------------------------------------------------------------------------
+// Markdown
+// -----------------------------------------------------------------------------
+/// Results of all days as a Markdown document
+pub fn markdown(summaries: &[Summary], warmup: u32, parallel: bool) -> String {
+    let mut output = String::new();
+    writeln!(output, "# Advent of Code 2020\n").unwrap();
+    writeln!(output, "Mode: {}, warmup: {}\n", mode(parallel), warmup).unwrap();
+
+    // Table
+    writeln!(output, "## Results\n").unwrap();
+    let (header, rows) = table(summaries);
+    let cell = |s: &str| s.replace('|', "\\|");
+    writeln!(output, "| {} |", header.join(" | ")).unwrap();
+    writeln!(output, "|{}", "---|".repeat(header.len())).unwrap();
+    for row in rows {
+        let row: Vec<String> = row.iter().map(|value| cell(value)).collect();
+        writeln!(output, "| {} |", row.join(" | ")).unwrap();
+    }
+    let total: std::time::Duration = summaries
+        .iter()
+        .map(|summary| summary.statistics.mean)
+        .sum();
+    writeln!(output, "\nTotal: {:?}\n", total).unwrap();
+
+    // Chart
+    writeln!(output, "## Timing\n").unwrap();
+    writeln!(output, "{}", svg_chart(summaries)).unwrap();
+
+    // Notes
+    writeln!(output, "## Notes\n").unwrap();
+    for summary in summaries {
+        writeln!(
+            output,
+            "### Day {:02}: {}\n",
+            summary.day.number, summary.day.name
+        )
+        .unwrap();
+        for paragraph in notes(summary.day.number) {
+            writeln!(output, "{}\n", paragraph).unwrap();
+        }
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// HTML
+// -----------------------------------------------------------------------------
+/// Results of all days as a standalone HTML document
+pub fn html(summaries: &[Summary], warmup: u32, parallel: bool) -> String {
+    let mut output = String::new();
+    output.push_str(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code 2020</title>\n<style>\n\
+         body { font-family: sans-serif; max-width: 60em; margin: auto; }\n\
+         table { border-collapse: collapse; }\n\
+         th, td { border: 1px solid #ccc; padding: 0.2em 0.5em; text-align: right; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    writeln!(output, "<h1>Advent of Code 2020</h1>").unwrap();
+    writeln!(
+        output,
+        "<p>Mode: {}, warmup: {}</p>",
+        mode(parallel),
+        warmup
+    )
+    .unwrap();
+
+    // Table
+    writeln!(output, "<h2>Results</h2>\n<table>").unwrap();
+    let (header, rows) = table(summaries);
+    let header: Vec<String> = header
+        .iter()
+        .map(|name| format!("<th>{}</th>", name))
+        .collect();
+    writeln!(output, "<tr>{}</tr>", header.join("")).unwrap();
+    for row in rows {
+        let row: Vec<String> = row
+            .iter()
+            .map(|value| format!("<td>{}</td>", escape(value)))
+            .collect();
+        writeln!(output, "<tr>{}</tr>", row.join("")).unwrap();
+    }
+    let total: std::time::Duration = summaries
+        .iter()
+        .map(|summary| summary.statistics.mean)
+        .sum();
+    writeln!(output, "</table>\n<p>Total: {:?}</p>", total).unwrap();
+
+    // Chart
+    writeln!(output, "<h2>Timing</h2>").unwrap();
+    output.push_str(&svg_chart(summaries));
+
+    // Notes
+    writeln!(output, "<h2>Notes</h2>").unwrap();
+    for summary in summaries {
+        writeln!(
+            output,
+            "<h3>Day {:02}: {}</h3>",
+            summary.day.number,
+            escape(summary.day.name)
+        )
+        .unwrap();
+        for paragraph in notes(summary.day.number) {
+            writeln!(output, "<p>{}</p>", escape(&paragraph)).unwrap();
+        }
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+// -----------------------------------------------------------------------------
@@ -0,0 +1,166 @@
+//! Spans:
+//! Named timing spans inside the solvers. The time until a span guard is dropped is added
+//! to its step, and spans opened inside of other spans are nested under them. Without the
+//! `spans` feature the guard is an empty struct and the calls compile away. Spans are
+//! recorded per thread, so steps that run on the rayon thread pool are not counted.
+
+use std::time::Duration;
+
+// -----------------------------------------------------------------------------
+// SpanTime struct
+// -----------------------------------------------------------------------------
+/// Total time spent in a named step of a solver
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanTime {
+    /// Names of the enclosing spans, outermost first, ending with this span
+    pub path: Vec<&'static str>,
+    /// Total time spent in the span
+    pub time: Duration,
+    /// Number of times the span was entered
+    pub count: u32,
+}
+
+impl SpanTime {
+    /// Name of the span
+    pub fn name(&self) -> &'static str {
+        self.path[self.path.len() - 1]
+    }
+
+    /// Nesting depth, 0 for a span that is not inside of another span
+    pub fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+/// Average span times over repetitions, in order of first appearance
+pub fn average(repetitions: &[&[SpanTime]]) -> Vec<SpanTime> {
+    let mut totals: Vec<SpanTime> = vec![];
+    for span in repetitions.iter().flat_map(|spans| spans.iter()) {
+        match totals.iter_mut().find(|total| total.path == span.path) {
+            Some(total) => {
+                total.time += span.time;
+                total.count += span.count;
+            }
+            None => totals.push(span.clone()),
+        }
+    }
+    let repetitions = std::cmp::max(1, repetitions.len()) as u32;
+    totals.iter_mut().for_each(|total| {
+        total.time /= repetitions;
+        total.count /= repetitions;
+    });
+    totals
+}
+
+// -----------------------------------------------------------------------------
+// Recorder
+// -----------------------------------------------------------------------------
+#[cfg(feature = "spans")]
+mod recorder {
+    use super::SpanTime;
+    use std::cell::RefCell;
+    use std::time::{Duration, Instant};
+
+    #[derive(Default)]
+    struct Recorder {
+        stack: Vec<&'static str>,
+        spans: Vec<SpanTime>,
+    }
+
+    thread_local! {
+        static RECORDER: RefCell<Recorder> = RefCell::new(Recorder::default());
+    }
+
+    /// Guard for an open span
+    #[derive(Debug)]
+    #[must_use = "the span closes when the guard is dropped"]
+    pub struct Span {
+        start: Instant,
+    }
+
+    pub fn enter(name: &'static str) -> Span {
+        RECORDER.with(|recorder| {
+            let recorder = &mut *recorder.borrow_mut();
+            recorder.stack.push(name);
+            // Add the span on entry, so parents come before their children
+            if !recorder
+                .spans
+                .iter()
+                .any(|span| span.path == recorder.stack)
+            {
+                recorder.spans.push(SpanTime {
+                    path: recorder.stack.clone(),
+                    time: Duration::ZERO,
+                    count: 0,
+                });
+            }
+        });
+        Span {
+            start: Instant::now(),
+        }
+    }
+
+    impl Drop for Span {
+        fn drop(&mut self) {
+            let time = self.start.elapsed();
+            RECORDER.with(|recorder| {
+                let recorder = &mut *recorder.borrow_mut();
+                // Nothing to add to if the span was discarded by take
+                let stack = &recorder.stack;
+                if let Some(span) = recorder.spans.iter_mut().find(|span| &span.path == stack) {
+                    span.time += time;
+                    span.count += 1;
+                }
+                recorder.stack.pop();
+            });
+        }
+    }
+
+    pub fn take() -> Vec<SpanTime> {
+        RECORDER.with(|recorder| {
+            let recorder = &mut *recorder.borrow_mut();
+            recorder.stack.clear();
+            std::mem::take(&mut recorder.spans)
+        })
+    }
+}
+
+#[cfg(not(feature = "spans"))]
+mod recorder {
+    use super::SpanTime;
+
+    /// Guard for an open span
+    #[derive(Debug)]
+    #[must_use = "the span closes when the guard is dropped"]
+    pub struct Span;
+
+    #[inline(always)]
+    pub fn enter(_name: &'static str) -> Span {
+        Span
+    }
+
+    #[inline(always)]
+    pub fn take() -> Vec<SpanTime> {
+        vec![]
+    }
+}
+
+/// Guard for an open span; the span closes when the guard is dropped
+pub use recorder::Span;
+
+/// Whether spans are recorded
+pub const ENABLED: bool = cfg!(feature = "spans");
+
+/// Open a span for a named step
+#[inline(always)]
+pub fn enter(name: &'static str) -> Span {
+    recorder::enter(name)
+}
+
+/// Spans recorded on this thread since the last call, with any open spans discarded
+#[inline(always)]
+pub fn take() -> Vec<SpanTime> {
+    recorder::take()
+}
+
+// -----------------------------------------------------------------------------
@@ -0,0 +1,122 @@
+//! Statistics:
+//! Summary statistics over the timing repetitions. Outliers are rejected with the median
+//! absolute deviation, and the median, 90th percentile and minimum are reported next to
+//! the mean.
+
+use std::time::Duration;
+
+// Constants
+const OUTLIER_CUTOFF: f64 = 3.5;
+const MAD_SCALE: f64 = 0.6745;
+
+// -----------------------------------------------------------------------------
+// Statistics struct
+// -----------------------------------------------------------------------------
+/// Summary of the repetition samples of a day, in nanoseconds
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    /// Samples that were kept, in nanoseconds
+    pub samples: Vec<f64>,
+    /// Outlier flag for each of the original samples
+    pub outliers: Vec<bool>,
+    /// Mean of the kept samples
+    pub mean: Duration,
+    /// Sample standard deviation of the kept samples
+    pub std_dev: Duration,
+    /// Median of the kept samples
+    pub median: Duration,
+    /// 90th percentile of the kept samples
+    pub p90: Duration,
+    /// Fastest kept sample
+    pub min: Duration,
+}
+
+impl Statistics {
+    /// Statistics over all samples, with the outliers rejected
+    pub fn new(all_samples: &[f64]) -> Self {
+        let outliers = outliers(all_samples);
+        let samples: Vec<f64> = all_samples
+            .iter()
+            .zip(&outliers)
+            .filter(|(_, &outlier)| !outlier)
+            .map(|(&sample, _)| sample)
+            .collect();
+        let (mean, variance) = mean_variance(&samples);
+        let mut sorted = samples.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let nanoseconds = |value: f64| Duration::from_nanos(value.round() as u64);
+        Self {
+            mean: nanoseconds(mean),
+            std_dev: nanoseconds(variance.sqrt()),
+            median: nanoseconds(median(&sorted)),
+            p90: nanoseconds(percentile(&sorted, 0.9)),
+            min: nanoseconds(sorted[0]),
+            samples,
+            outliers,
+        }
+    }
+
+    /// Number of samples rejected as outliers
+    pub fn rejected(&self) -> usize {
+        self.outliers.iter().filter(|&&outlier| outlier).count()
+    }
+
+    /// Standard error of the mean, relative to the mean
+    pub fn relative_error(&self) -> f64 {
+        if self.samples.len() < 2 {
+            return f64::INFINITY;
+        }
+        self.std_dev.as_nanos() as f64
+            / (self.samples.len() as f64).sqrt()
+            / self.mean.as_nanos().max(1) as f64
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+/// Sample mean and (unbiased) variance
+pub fn mean_variance(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    if samples.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = samples
+        .iter()
+        .map(|sample| (sample - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0);
+    (mean, variance)
+}
+
+// Median of sorted values
+fn median(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    (sorted[(n - 1) / 2] + sorted[n / 2]) / 2.0
+}
+
+// Nearest rank percentile of sorted values
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = (fraction * sorted.len() as f64).ceil() as usize;
+    sorted[rank.max(1) - 1]
+}
+
+// Flag samples with a modified z-score above the cutoff
+fn outliers(samples: &[f64]) -> Vec<bool> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let center = median(&sorted);
+    let mut deviations: Vec<f64> = samples
+        .iter()
+        .map(|sample| (sample - center).abs())
+        .collect();
+    deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mad = median(&deviations);
+    samples
+        .iter()
+        .map(|sample| mad > 0.0 && MAD_SCALE * (sample - center).abs() / mad > OUTLIER_CUTOFF)
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
@@ -0,0 +1,62 @@
+//! Watch:
+//! Watch mode runs a single day, then polls its input file and runs the day again each
+//! time the file changes, printing the change in timing against the run before it.
+
+use crate::cli;
+use advent2020::{output, registry, Input};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+// Modification time and length, to notice a changed file
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+// -----------------------------------------------------------------------------
+// Watch
+// -----------------------------------------------------------------------------
+pub(crate) fn watch(options: &cli::Options, interval: Duration) -> ! {
+    let day = registry::get(options.days[0]).expect("day not registered");
+    let input = match options.input(day.number) {
+        Input::Path(path) => path.clone(),
+        Input::Buffer(_) => {
+            eprintln!("watch needs an input file to poll, not stdin");
+            std::process::exit(2);
+        }
+    };
+    let mut previous = None;
+    let mut input_stamp = stamp(&input);
+
+    output::print_header();
+    println!("- Watching {}, Ctrl-C to stop", input.display());
+    loop {
+        // Run
+        match crate::measure(day, options) {
+            Ok((results, statistics)) => {
+                let summary = crate::summary(&results, &statistics);
+                (day.report)(&summary, &statistics);
+                if let Some(previous) = &previous {
+                    output::print_delta(previous, &summary.times);
+                }
+                previous = Some(summary.times);
+            }
+            // The input may be half written, so wait for the next change
+            Err(error) => eprintln!("error: Dec {:02} {}: {}", day.number, day.name, error),
+        }
+
+        // Wait for a change
+        loop {
+            std::thread::sleep(interval);
+            if stamp(&input) != input_stamp {
+                input_stamp = stamp(&input);
+                break;
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------